@@ -6,6 +6,7 @@ use crate::card::{Card, ParseError};
 use crate::constants::{
     CARD_COUNT, FLUSH_CHECK_MASK32, FLUSH_CHECK_MASK64, MAX_HAND_SIZE, SUITS_SHIFT,
 };
+use crate::suit::Suit;
 use crate::CARDS;
 
 /// An unordered collection of 0-7 cards from a standard 52-card deck.
@@ -82,6 +83,21 @@ impl Hand {
         self.mask & other.mask == 0
     }
 
+    /// Returns a 13-bit mask of the ranks of the cards of the given suit
+    /// present in the hand, with the deuce as the least significant bit.
+    #[inline]
+    pub fn suit_mask(&self, suit: Suit) -> u16 {
+        ((self.mask >> (16 * suit as usize)) & 0x1fff) as u16
+    }
+
+    /// Returns a 13-bit mask of the ranks present in the hand regardless of
+    /// suit, with the deuce as the least significant bit.
+    #[inline]
+    pub fn rank_mask(&self) -> u16 {
+        let mask = self.mask | (self.mask >> 16) | (self.mask >> 32) | (self.mask >> 48);
+        (mask & 0x1fff) as u16
+    }
+
     /// Returns a key corresponding uniquely to the ranks (with multiplicity)
     /// present in the hand.
     #[inline]
@@ -331,6 +347,22 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[case("", 0, 0)]
+    #[case("2c", 0, 0b1)]
+    #[case("Ah As Kh 2d", 0b1, 0b1100000000001)]
+    #[case("5s 6s 7d 8s 9h", 0b100000, 0b11111000)]
+    fn masks(
+        #[case] cards: &str,
+        #[case] diamonds: u16,
+        #[case] ranks: u16,
+    ) -> Result<(), ParseError> {
+        let hand: Hand = cards.parse()?;
+        assert_eq!(hand.suit_mask(Suit::Diamonds), diamonds);
+        assert_eq!(hand.rank_mask(), ranks);
+        Ok(())
+    }
+
     #[rstest]
     #[case(&[])]
     #[case(&["4c"])]
@@ -3,7 +3,7 @@ use core::{convert::TryFrom, str::FromStr};
 use super::card::ParseError;
 
 /// One of the thirteen ranks of a standard French 52-playing card deck.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
 pub enum Rank {
     Two = 0,
     Three,
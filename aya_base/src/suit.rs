@@ -3,7 +3,7 @@ use core::{convert::TryFrom, str::FromStr};
 use super::card::ParseError;
 
 /// One of the four French playing card suits.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub enum Suit {
     Clubs = 0,
     Diamonds,
@@ -0,0 +1,280 @@
+//! The `equity` command, which computes the showdown equities of hands or
+//! hold'em ranges.
+
+use std::fmt;
+
+use aya_poker::{
+    base::Hand,
+    equity::{
+        exact_equity, range_equity, sampled_equity, sampled_range_equity, EquityError,
+        EquityResult, Game, PlayerEquity, RangeEquityResult, SamplingOptions,
+    },
+    range::Range,
+    registry::GameDef,
+};
+use serde::Serialize;
+
+use crate::input::{card_strings, parse_cards, Error};
+
+/// The equity of a single hand or range.
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct PlayerReport {
+    pub hand: String,
+    pub equity: f64,
+    pub win: f64,
+    pub tie: f64,
+    pub std_error: f64,
+}
+
+/// The result of an equity calculation.
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct EquityReport {
+    pub game: &'static str,
+    pub board: Vec<String>,
+    pub dead: Vec<String>,
+    pub exact: bool,
+    pub runouts: u64,
+    pub players: Vec<PlayerReport>,
+}
+
+/// The stopping rules of a sampled equity calculation, given on the command
+/// line.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Sampling {
+    /// The number of runouts to sample, or `None` to enumerate every runout
+    /// where possible.
+    pub samples: Option<u64>,
+    pub seed: u64,
+}
+
+/// The number of runouts sampled when an exact calculation is not possible
+/// and no number was given.
+const DEFAULT_SAMPLES: u64 = 100_000;
+
+/// Computes the equities of the hands, or, in hold'em, of two ranges if
+/// either of them is not a single hand.
+///
+/// Hold'em hands and postflop ranges are enumerated exactly unless a number
+/// of samples is given, while every other calculation is sampled.
+pub fn equity(
+    game: &'static GameDef,
+    hands: &[String],
+    board: &Hand,
+    dead: &Hand,
+    sampling: Sampling,
+) -> Result<EquityReport, Error> {
+    let equity_game = game
+        .equity_game
+        .ok_or_else(|| Error::UnsupportedGame(game.id.to_string()))?;
+    let options = SamplingOptions::new(sampling.seed)
+        .with_max_samples(sampling.samples.unwrap_or(DEFAULT_SAMPLES));
+
+    let holes = hands
+        .iter()
+        .map(|hand| parse_cards(hand))
+        .collect::<Result<Vec<_>, _>>();
+    let (exact, runouts, players) = match holes {
+        Ok(holes) => {
+            let exact = equity_game == Game::Holdem && sampling.samples.is_none();
+            let result: EquityResult = match exact {
+                true => exact_equity(&holes, board, dead)?,
+                false => sampled_equity(equity_game, &holes, board, dead, &options)?,
+            };
+            (exact, result.runouts, result.players)
+        }
+        Err(error) if equity_game != Game::Holdem => return Err(error),
+        Err(_) => {
+            let ranges = hands
+                .iter()
+                .map(|hand| parse_range(hand))
+                .collect::<Result<Vec<_>, _>>()?;
+            if ranges.len() != 2 {
+                return Err(EquityError::InvalidPlayerCount.into());
+            }
+            let exact = board.len() >= 3 && sampling.samples.is_none();
+            let result: RangeEquityResult = match exact {
+                true => range_equity(&ranges[0], &ranges[1], board, dead)?,
+                false => sampled_range_equity(&ranges[0], &ranges[1], board, dead, &options)?,
+            };
+            (exact, result.runouts, result.players)
+        }
+    };
+
+    Ok(EquityReport {
+        game: game.id,
+        board: card_strings(board),
+        dead: card_strings(dead),
+        exact,
+        runouts,
+        players: hands
+            .iter()
+            .zip(players)
+            .map(|(hand, equity): (&String, PlayerEquity)| PlayerReport {
+                hand: hand.clone(),
+                equity: equity.equity,
+                win: equity.win,
+                tie: equity.tie,
+                std_error: equity.std_error,
+            })
+            .collect(),
+    })
+}
+
+/// Parses a range, or a single hand written in any of the notations accepted
+/// by [`parse_cards`].
+fn parse_range(text: &str) -> Result<Range, Error> {
+    match parse_cards(text) {
+        Ok(hole) if hole.len() == 2 => {
+            let mut range = Range::new();
+            range.set_weight(&hole, 1.0);
+            Ok(range)
+        }
+        _ => text
+            .parse()
+            .map_err(|_| Error::InvalidRange(text.to_string())),
+    }
+}
+
+impl fmt::Display for EquityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{}", "Game", self.game)?;
+        if !self.board.is_empty() {
+            writeln!(f, "{:<16}{}", "Board", self.board.join(" "))?;
+        }
+        if !self.dead.is_empty() {
+            writeln!(f, "{:<16}{}", "Dead", self.dead.join(" "))?;
+        }
+        let kind = match self.exact {
+            true => "exact",
+            false => "sampled",
+        };
+        writeln!(f, "{:<16}{} ({})", "Runouts", self.runouts, kind)?;
+        writeln!(f)?;
+        writeln!(f, "{:<24}{:>9}{:>9}{:>9}", "Hand", "Equity", "Win", "Tie")?;
+        for player in self.players.iter() {
+            write!(
+                f,
+                "{:<24}{:>8.2}%{:>8.2}%{:>8.2}%",
+                player.hand,
+                100.0 * player.equity,
+                100.0 * player.win,
+                100.0 * player.tie
+            )?;
+            if !self.exact {
+                write!(f, "  ± {:.2}%", 100.0 * player.std_error)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::parse_game;
+
+    fn hands(hands: &[&str]) -> Vec<String> {
+        hands.iter().map(|hand| hand.to_string()).collect()
+    }
+
+    const EXACT: Sampling = Sampling {
+        samples: None,
+        seed: 42,
+    };
+
+    #[test]
+    fn computes_exact_holdem_equities() {
+        let game = parse_game("holdem").unwrap();
+        let board = parse_cards("Qd Jh Th 2c").unwrap();
+        let report = equity(
+            game,
+            &hands(&["AhKd", "Ac Kc"]),
+            &board,
+            &Hand::new(),
+            EXACT,
+        )
+        .unwrap();
+        assert!(report.exact);
+        assert_eq!(report.runouts, 44);
+        assert_eq!(report.players[0].hand, "AhKd");
+        assert_eq!(report.players[0].tie, 1.0);
+    }
+
+    #[test]
+    fn computes_range_equities() {
+        let game = parse_game("holdem").unwrap();
+        let board = parse_cards("Kd 7h 2h").unwrap();
+        let report = equity(
+            game,
+            &hands(&["AhKh", "QQ+, AK"]),
+            &board,
+            &Hand::new(),
+            EXACT,
+        )
+        .unwrap();
+        assert!(report.exact);
+        assert!(report.players[0].equity > 0.7);
+    }
+
+    #[test]
+    fn samples_omaha_equities() {
+        let game = parse_game("plo").unwrap();
+        let sampling = Sampling {
+            samples: Some(2_000),
+            seed: 42,
+        };
+        let holes = hands(&["As Ks Jh Td", "Qc Qd 8c 7d"]);
+        let report = equity(game, &holes, &Hand::new(), &Hand::new(), sampling).unwrap();
+        assert!(!report.exact);
+        assert_eq!(report.runouts, 2_000);
+        assert!(report.players[0].std_error > 0.0);
+    }
+
+    #[test]
+    fn rejects_invalid_calculations() {
+        let game = parse_game("razz").unwrap();
+        assert_eq!(
+            equity(
+                game,
+                &hands(&["Ah Kd", "Qs Qc"]),
+                &Hand::new(),
+                &Hand::new(),
+                EXACT
+            ),
+            Err(Error::UnsupportedGame("razz".to_string()))
+        );
+
+        let game = parse_game("holdem").unwrap();
+        assert_eq!(
+            equity(
+                game,
+                &hands(&["Ah Kd", "Ah Qc"]),
+                &Hand::new(),
+                &Hand::new(),
+                EXACT
+            ),
+            Err(Error::InvalidDeal(EquityError::DuplicateCard))
+        );
+        assert_eq!(
+            equity(
+                game,
+                &hands(&["Ah Kd", "QQ+", "JJ"]),
+                &Hand::new(),
+                &Hand::new(),
+                EXACT
+            ),
+            Err(Error::InvalidDeal(EquityError::InvalidPlayerCount))
+        );
+        assert_eq!(
+            equity(
+                game,
+                &hands(&["Ah Kd", "QX+"]),
+                &Hand::new(),
+                &Hand::new(),
+                EXACT
+            ),
+            Err(Error::InvalidRange("QX+".to_string()))
+        );
+    }
+}
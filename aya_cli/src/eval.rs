@@ -0,0 +1,417 @@
+//! The `eval` and `compare` commands, which rank hands of any game in the
+//! registry.
+
+use std::fmt;
+
+use aya_poker::{
+    base::{Card, Hand, Rank},
+    equity::EquityError,
+    registry::{BestHand, GameDef, HandCategory, HandValue, Ranking},
+    PokerRankCategory,
+};
+use serde::Serialize;
+
+use crate::input::{card_strings, Error};
+
+const RANK_NAMES: [&str; 13] = [
+    "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+    "King", "Ace",
+];
+
+const PLURAL_RANK_NAMES: [&str; 13] = [
+    "Twos", "Threes", "Fours", "Fives", "Sixes", "Sevens", "Eights", "Nines", "Tens", "Jacks",
+    "Queens", "Kings", "Aces",
+];
+
+/// Returns `true` if aces are the lowest cards of the ranking.
+fn aces_low(ranking: Ranking) -> bool {
+    matches!(ranking, Ranking::AceFive | Ranking::Badugi)
+}
+
+fn is_lowball(ranking: Ranking) -> bool {
+    matches!(ranking, Ranking::AceFive | Ranking::DeuceSeven)
+}
+
+/// The best hand that can be made for one half of the pot.
+#[derive(Serialize, PartialEq, Eq, Clone, Debug)]
+pub struct Half {
+    /// The value of the hand, as ranked by the registry.
+    pub rank: u16,
+    pub category: Option<String>,
+    pub description: Option<String>,
+    pub best_five: Option<Vec<String>>,
+}
+
+/// The ranking of a single hand.
+#[derive(Serialize, PartialEq, Eq, Clone, Debug)]
+pub struct HandReport {
+    pub game: &'static str,
+    pub cards: Vec<String>,
+    pub board: Vec<String>,
+    pub high: Half,
+    pub low: Option<Half>,
+}
+
+/// The rankings of several hands sharing a board and the winners of each
+/// half of the pot.
+#[derive(Serialize, PartialEq, Eq, Clone, Debug)]
+pub struct CompareReport {
+    pub game: &'static str,
+    pub board: Vec<String>,
+    pub hands: Vec<HandReport>,
+    pub high_winners: Vec<usize>,
+    pub low_winners: Vec<usize>,
+}
+
+/// Ranks the best hand that can be made from the hole and board cards.
+pub fn evaluate(game: &'static GameDef, hole: &Hand, board: &Hand) -> Result<HandReport, Error> {
+    let rank = game.evaluate(hole, board)?;
+    let best = game.best_hands(hole, board)?;
+    let high = half(game.hi_ranking, rank.hi, best.hi);
+    let low = match (game.lo_ranking, rank.lo) {
+        (Some(ranking), Some(value)) => Some(half(ranking, value, best.lo)),
+        _ => None,
+    };
+
+    Ok(HandReport {
+        game: game.id,
+        cards: card_strings(hole),
+        board: card_strings(board),
+        high,
+        low,
+    })
+}
+
+/// Ranks the hands of several players sharing a board.
+pub fn compare(
+    game: &'static GameDef,
+    holes: &[Hand],
+    board: &Hand,
+) -> Result<CompareReport, Error> {
+    for (i, hole) in holes.iter().enumerate() {
+        if holes[i + 1..].iter().any(|other| !hole.is_disjoint(other)) {
+            return Err(EquityError::DuplicateCard.into());
+        }
+    }
+    let hands = holes
+        .iter()
+        .map(|hole| evaluate(game, hole, board))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let best_high = hands.iter().map(|h| h.high.rank).max();
+    let best_low = hands
+        .iter()
+        .filter_map(|h| h.low.as_ref())
+        .map(|l| l.rank)
+        .max();
+    let high_winners = (0..hands.len())
+        .filter(|&i| Some(hands[i].high.rank) == best_high)
+        .collect();
+    let low_winners = (0..hands.len())
+        .filter(|&i| best_low.is_some() && hands[i].low.as_ref().map(|l| l.rank) == best_low)
+        .collect();
+
+    Ok(CompareReport {
+        game: game.id,
+        board: card_strings(board),
+        hands,
+        high_winners,
+        low_winners,
+    })
+}
+
+fn half(ranking: Ranking, value: HandValue, best: Option<BestHand>) -> Half {
+    match best {
+        Some(best) => {
+            let cards = ordered(ranking, best.category, &best.cards);
+            Half {
+                rank: value.0,
+                category: Some(best.category.to_string()),
+                description: Some(describe(ranking, best.category, &cards)),
+                best_five: Some(card_strings(&cards)),
+            }
+        }
+        None => Half {
+            rank: value.0,
+            category: None,
+            description: None,
+            best_five: None,
+        },
+    }
+}
+
+fn rank_value(ranking: Ranking, rank: Rank) -> i32 {
+    match rank {
+        Rank::Ace if aces_low(ranking) => -1,
+        _ => rank as i32,
+    }
+}
+
+/// Orders the cards of a hand by the size of their rank group and then by
+/// rank, with the ace of a five-high straight last.
+fn ordered(ranking: Ranking, category: HandCategory, hand: &Hand) -> Vec<Card> {
+    let count = |card: &Card| hand.iter().filter(|c| c.rank() == card.rank()).count();
+    let mut cards: Vec<Card> = hand.iter().copied().collect();
+    cards.sort_by_key(|card| {
+        (
+            std::cmp::Reverse(count(card)),
+            std::cmp::Reverse(rank_value(ranking, card.rank())),
+        )
+    });
+
+    let straight = matches!(
+        category,
+        HandCategory::Poker(PokerRankCategory::Straight | PokerRankCategory::StraightFlush)
+    );
+    if straight && cards[0].rank() == Rank::Ace && cards[1].rank() != Rank::King {
+        cards.rotate_left(1);
+    }
+    cards
+}
+
+fn rank_char(card: &Card) -> char {
+    format!("{:?}", card).chars().next().unwrap()
+}
+
+/// Describes a hand whose cards are ordered by [`ordered`].
+fn describe(ranking: Ranking, category: HandCategory, cards: &[Card]) -> String {
+    let name = |i: usize| RANK_NAMES[cards[i].rank() as usize];
+    let plural = |i: usize| PLURAL_RANK_NAMES[cards[i].rank() as usize];
+    let ranks = || {
+        cards
+            .iter()
+            .map(|c| rank_char(c).to_string())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+
+    let category = match category {
+        HandCategory::Poker(category) => category,
+        HandCategory::Badugi(category) => return format!("{}, {}", category, ranks()),
+    };
+    if ranking == Ranking::DeucesWild {
+        return category.to_string();
+    }
+    match category {
+        PokerRankCategory::Ineligible => category.to_string(),
+        PokerRankCategory::HighCard if is_lowball(ranking) => {
+            format!("{} Low, {}", name(0), ranks())
+        }
+        PokerRankCategory::HighCard => format!("High Card, {}", name(0)),
+        PokerRankCategory::Pair => format!("Pair of {}", plural(0)),
+        PokerRankCategory::TwoPair => format!("Two Pair, {} and {}", plural(0), plural(2)),
+        PokerRankCategory::ThreeOfAKind => format!("Three of a Kind, {}", plural(0)),
+        PokerRankCategory::Straight | PokerRankCategory::StraightFlush => {
+            format!("{}, {} to {}", category, name(cards.len() - 1), name(0))
+        }
+        PokerRankCategory::Flush => format!("Flush, {} High", name(0)),
+        PokerRankCategory::FullHouse => {
+            format!("Full House, {} full of {}", plural(0), plural(3))
+        }
+        PokerRankCategory::FourOfAKind => format!("Four of a Kind, {}", plural(0)),
+        PokerRankCategory::RoyalFlush | PokerRankCategory::FiveOfAKind => category.to_string(),
+    }
+}
+
+/// Writes the rows describing one half of a hand, with labels starting with
+/// the given prefix, e.g. `"Low"`.
+fn write_half(f: &mut fmt::Formatter<'_>, prefix: &str, half: &Half) -> fmt::Result {
+    let label = |name: &str| match prefix {
+        "" => format!("{}{}", name[..1].to_uppercase(), &name[1..]),
+        _ => format!("{} {}", prefix, name),
+    };
+    writeln!(f, "{:<16}{}", label("rank"), half.rank)?;
+    if let (Some(category), Some(description), Some(best)) =
+        (&half.category, &half.description, &half.best_five)
+    {
+        writeln!(f, "{:<16}{}", label("category"), category)?;
+        writeln!(f, "{:<16}{}", label("description"), description)?;
+        writeln!(f, "{:<16}{}", label("best five"), best.join(" "))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for HandReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{}", "Game", self.game)?;
+        writeln!(f, "{:<16}{}", "Cards", self.cards.join(" "))?;
+        if !self.board.is_empty() {
+            writeln!(f, "{:<16}{}", "Board", self.board.join(" "))?;
+        }
+        write_half(f, "", &self.high)?;
+        match &self.low {
+            Some(low) => write_half(f, "Low", low),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for CompareReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{}", "Game", self.game)?;
+        if !self.board.is_empty() {
+            writeln!(f, "{:<16}{}", "Board", self.board.join(" "))?;
+        }
+        for (i, hand) in self.hands.iter().enumerate() {
+            write!(f, "{:<16}", hand.cards.join(" "))?;
+            write!(
+                f,
+                "{}",
+                hand.high.description.as_deref().unwrap_or("Unranked")
+            )?;
+            if self.high_winners.contains(&i) {
+                write!(f, " (wins high)")?;
+            }
+            if let Some(low) = &hand.low {
+                write!(f, "; {}", low.description.as_deref().unwrap_or("Unranked"))?;
+                if self.low_winners.contains(&i) {
+                    write!(f, " (wins low)")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{parse_cards, parse_game};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "holdem",
+        "Ah Kd",
+        "Qs Jh Tc 2d 3c",
+        "Straight, Ten to Ace",
+        "Ah Kd Qs Jh Tc"
+    )]
+    #[case(
+        "holdem",
+        "5h 4d",
+        "Ac 2s 3c Kd Kc",
+        "Straight, Ace to Five",
+        "5h 4d 3c 2s Ac"
+    )]
+    #[case(
+        "holdem",
+        "Kh Kd",
+        "Ks 7h 7c 2d 3c",
+        "Full House, Kings full of Sevens",
+        "Kd Kh Ks 7c 7h"
+    )]
+    #[case("holdem", "Ah Kd", "", "High Card, Ace", "Ah Kd")]
+    #[case(
+        "omaha",
+        "Ah Ad Kh Qh",
+        "2h 3h 4h 5c 6c",
+        "Flush, Ace High",
+        "Ah Kh 4h 3h 2h"
+    )]
+    #[case(
+        "short-deck",
+        "Ah 9d",
+        "6c 7s 8h Kd Kc",
+        "Straight, Ace to Nine",
+        "9d 8h 7s 6c Ah"
+    )]
+    #[case(
+        "razz",
+        "Ac 2d 3h 4s 5c Kd Kc",
+        "",
+        "Five Low, 5-4-3-2-A",
+        "5c 4s 3h 2d Ac"
+    )]
+    #[case(
+        "2-7td",
+        "7c 5d 4h 3s 2c",
+        "",
+        "Seven Low, 7-5-4-3-2",
+        "7c 5d 4h 3s 2c"
+    )]
+    #[case("badugi", "Kc 4d 3h 2h", "", "Three Cards, K-4-2", "Kc 4d 2h")]
+    #[case(
+        "manila",
+        "Ah 9d",
+        "Ts 8h 7h Kh Kc",
+        "Straight, Ace to Ten",
+        "Ts 9d 8h 7h Ah"
+    )]
+    #[case(
+        "deuces-wild",
+        "2c 2d Ah As Kd",
+        "",
+        "Four of a Kind",
+        "Ah As 2c 2d Kd"
+    )]
+    #[case(
+        "5cd",
+        "Ah Ad Ac Kd Ks",
+        "",
+        "Full House, Aces full of Kings",
+        "Ac Ad Ah Kd Ks"
+    )]
+    fn evaluates_hands(
+        #[case] game: &str,
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] description: &str,
+        #[case] best: &str,
+    ) {
+        let game = parse_game(game).unwrap();
+        let hole = parse_cards(hole).unwrap();
+        let board = parse_cards(board).unwrap();
+        let report = evaluate(game, &hole, &board).unwrap();
+        assert_eq!(report.high.description.as_deref(), Some(description));
+        assert_eq!(report.high.best_five.unwrap().join(" "), best);
+        assert_eq!(report.low, None);
+    }
+
+    #[test]
+    fn evaluates_low_halves() {
+        let game = parse_game("plo8").unwrap();
+        let hole = parse_cards("Ah 2d Kc Kd").unwrap();
+        let board = parse_cards("3c 7s 8h Ks Qd").unwrap();
+        let report = evaluate(game, &hole, &board).unwrap();
+        assert_eq!(
+            report.high.description.as_deref(),
+            Some("Three of a Kind, Kings")
+        );
+        let low = report.low.unwrap();
+        assert_eq!(low.description.as_deref(), Some("Eight Low, 8-7-3-2-A"));
+        assert_eq!(low.best_five.unwrap().join(" "), "8h 7s 3c 2d Ah");
+
+        let hole = parse_cards("Kh Qc Kc Kd").unwrap();
+        assert_eq!(evaluate(game, &hole, &board).unwrap().low, None);
+    }
+
+    #[test]
+    fn leaves_unranked_hands_undescribed() {
+        let game = parse_game("omaha").unwrap();
+        let report = evaluate(game, &parse_cards("Ah Ad Kh Qh").unwrap(), &Hand::new()).unwrap();
+        assert_eq!(report.high.rank, 0);
+        assert_eq!(report.high.category, None);
+    }
+
+    #[test]
+    fn compares_hands() {
+        let game = parse_game("holdem").unwrap();
+        let holes = [
+            parse_cards("Ah Kd").unwrap(),
+            parse_cards("Qh Qd").unwrap(),
+            parse_cards("Ac Kc").unwrap(),
+        ];
+        let board = parse_cards("Qs Jh Tc 2d 3c").unwrap();
+        let report = compare(game, &holes, &board).unwrap();
+        assert_eq!(report.high_winners, [0, 2]);
+        assert!(report.low_winners.is_empty());
+
+        let holes = [holes[0], parse_cards("Ah Qd").unwrap()];
+        assert_eq!(
+            compare(game, &holes, &board),
+            Err(Error::InvalidDeal(EquityError::DuplicateCard))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Parsing of the cards and games given on the command line.
+
+use std::fmt;
+
+use aya_poker::{
+    base::{Card, Hand},
+    equity::EquityError,
+    registry::{find_game, GameDef},
+};
+
+/// An error which can be returned when running a command.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Error {
+    /// The game is not known to the registry.
+    UnknownGame(String),
+    /// The game is not supported by the command.
+    UnsupportedGame(String),
+    /// The cards could not be parsed.
+    InvalidCards(String),
+    /// The range could not be parsed.
+    InvalidRange(String),
+    /// The cards do not describe a valid deal.
+    InvalidDeal(EquityError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownGame(name) => write!(f, "unknown game '{}'", name),
+            Error::UnsupportedGame(name) => write!(f, "game '{}' is not supported", name),
+            Error::InvalidCards(text) => write!(f, "invalid cards '{}'", text),
+            Error::InvalidRange(text) => write!(f, "invalid range '{}'", text),
+            Error::InvalidDeal(error) => write!(f, "invalid deal: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<EquityError> for Error {
+    fn from(error: EquityError) -> Error {
+        Error::InvalidDeal(error)
+    }
+}
+
+/// Looks up the rules of a game by its id or one of its aliases.
+pub fn parse_game(name: &str) -> Result<&'static GameDef, Error> {
+    find_game(name).ok_or_else(|| Error::UnknownGame(name.to_string()))
+}
+
+/// Parses cards written either separated by spaces or commas, e.g.
+/// `"Ah Kd"`, or run together, e.g. `"AhKd"`.
+pub fn parse_cards(text: &str) -> Result<Hand, Error> {
+    let invalid = || Error::InvalidCards(text.to_string());
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect();
+    if chars.len() % 2 != 0 {
+        return Err(invalid());
+    }
+
+    let mut hand = Hand::new();
+    for pair in chars.chunks(2) {
+        let card: Card = pair
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| invalid())?;
+        if hand.contains(&card) || hand.len() == 7 {
+            return Err(invalid());
+        }
+        hand.insert_unchecked(&card);
+    }
+    Ok(hand)
+}
+
+/// Returns the cards in the plain two-character notation, e.g. `"Ah"`.
+pub fn card_strings<'a>(cards: impl IntoIterator<Item = &'a Card>) -> Vec<String> {
+    cards
+        .into_iter()
+        .map(|card| format!("{:?}", card))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Ah Kd", 2)]
+    #[case("AhKd", 2)]
+    #[case("Ah,Kd,2c", 3)]
+    #[case("", 0)]
+    fn parses_cards(#[case] text: &str, #[case] len: usize) {
+        assert_eq!(parse_cards(text).unwrap().len(), len);
+    }
+
+    #[rstest]
+    #[case("Ah K")]
+    #[case("Ah Ah")]
+    #[case("Xh")]
+    #[case("Ah Kd Qs Jh Tc 9d 8s 7c")]
+    fn rejects_invalid_cards(#[case] text: &str) {
+        assert_eq!(
+            parse_cards(text),
+            Err(Error::InvalidCards(text.to_string()))
+        );
+    }
+}
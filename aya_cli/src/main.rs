@@ -0,0 +1,171 @@
+//! `aya`, a command-line tool for poker hand evaluation, equities and ranges
+//! built on the `aya_poker` crate.
+//!
+//! ```text
+//! aya eval holdem "Ah Kd" --board "Qs Jh Tc 2d 3c"
+//! aya compare plo8 "Ah 2d Kc Kd" "Qs Qh 3h 4h" --board "3c 7s 8h Ks Qd"
+//! aya equity AhKh "QQ+, AK" --board "Kd 7h 2h"
+//! aya range expand "QQ+, AKs" --dead As --json
+//! ```
+//!
+//! Games are looked up in the registry of `aya_poker` by their id or any of
+//! their aliases, e.g. `nlhe`, `plo8` or `2-7td`. Every command prints a
+//! table by default, or a JSON object for scripting with `--json`.
+
+mod equity;
+mod eval;
+mod input;
+mod range;
+
+use std::{
+    fmt,
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crate::{
+    equity::Sampling,
+    input::{parse_cards, parse_game, Error},
+};
+
+/// Poker hand evaluation, equities and ranges.
+#[derive(Parser, Debug)]
+#[command(name = "aya", version)]
+struct Cli {
+    /// Print the results as JSON.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Rank the best hand that can be made from the cards.
+    Eval {
+        /// The game, e.g. `holdem`, `plo8` or `razz`.
+        game: String,
+        /// The hole cards, e.g. `"Ah Kd"` or `AhKd`.
+        cards: String,
+        /// The board cards.
+        #[arg(long, default_value = "")]
+        board: String,
+    },
+    /// Rank several hands sharing a board and find the winners.
+    Compare {
+        /// The game, e.g. `holdem`, `plo8` or `razz`.
+        game: String,
+        /// The hole cards of each player.
+        #[arg(required = true, num_args = 2..)]
+        hands: Vec<String>,
+        /// The board cards.
+        #[arg(long, default_value = "")]
+        board: String,
+    },
+    /// Compute the showdown equities of hands, or of two hold'em ranges.
+    Equity {
+        /// The hole cards or ranges of each player.
+        #[arg(required = true, num_args = 2..)]
+        hands: Vec<String>,
+        /// The game, one of the hold'em or Omaha games.
+        #[arg(long, default_value = "holdem")]
+        game: String,
+        /// The board cards.
+        #[arg(long, default_value = "")]
+        board: String,
+        /// Cards removed from the deck.
+        #[arg(long, default_value = "")]
+        dead: String,
+        /// Sample the given number of runouts instead of enumerating them.
+        #[arg(long)]
+        samples: Option<u64>,
+        /// The random seed of sampled calculations.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Work with hold'em ranges.
+    #[command(subcommand)]
+    Range(RangeCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum RangeCommand {
+    /// List every combination of hole cards in a range.
+    Expand {
+        /// The range, e.g. `"QQ+, AKs, 76s:0.5"`.
+        range: String,
+        /// Cards which cannot be held.
+        #[arg(long, default_value = "")]
+        dead: String,
+    },
+}
+
+/// Prints the report as a table or as JSON.
+///
+/// Errors writing to stdout, e.g. when it is piped into a command which exits
+/// early, are ignored.
+fn print<T: Serialize + fmt::Display>(report: &T, json: bool) {
+    let mut stdout = io::stdout().lock();
+    let _ = match json {
+        true => writeln!(stdout, "{}", serde_json::to_string_pretty(report).unwrap()),
+        false => write!(stdout, "{}", report),
+    };
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    match cli.command {
+        Command::Eval { game, cards, board } => {
+            let report = eval::evaluate(
+                parse_game(&game)?,
+                &parse_cards(&cards)?,
+                &parse_cards(&board)?,
+            )?;
+            print(&report, cli.json);
+        }
+        Command::Compare { game, hands, board } => {
+            let holes = hands
+                .iter()
+                .map(|hand| parse_cards(hand))
+                .collect::<Result<Vec<_>, _>>()?;
+            let report = eval::compare(parse_game(&game)?, &holes, &parse_cards(&board)?)?;
+            print(&report, cli.json);
+        }
+        Command::Equity {
+            hands,
+            game,
+            board,
+            dead,
+            samples,
+            seed,
+        } => {
+            let sampling = Sampling { samples, seed };
+            let report = equity::equity(
+                parse_game(&game)?,
+                &hands,
+                &parse_cards(&board)?,
+                &parse_cards(&dead)?,
+                sampling,
+            )?;
+            print(&report, cli.json);
+        }
+        Command::Range(RangeCommand::Expand { range, dead }) => {
+            let report = range::expand(&range, &parse_cards(&dead)?)?;
+            print(&report, cli.json);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
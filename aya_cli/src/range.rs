@@ -0,0 +1,88 @@
+//! The `range` commands, which work with hold'em ranges.
+
+use std::fmt;
+
+use aya_poker::{base::Hand, range::Range};
+use serde::Serialize;
+
+use crate::input::{card_strings, Error};
+
+/// A combination of hole cards in a range.
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct ComboReport {
+    pub hand: String,
+    pub weight: f64,
+}
+
+/// The combinations of hole cards in a range.
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct ExpandReport {
+    pub range: String,
+    pub dead: Vec<String>,
+    pub combo_count: f64,
+    pub combos: Vec<ComboReport>,
+}
+
+/// Lists every combination of hole cards in the range which does not hold a
+/// dead card, with its weight.
+pub fn expand(range: &str, dead: &Hand) -> Result<ExpandReport, Error> {
+    let parsed = range
+        .parse::<Range>()
+        .map_err(|_| Error::InvalidRange(range.to_string()))?;
+    let combos = parsed.without_blocked(dead);
+
+    Ok(ExpandReport {
+        range: parsed.to_string(),
+        dead: card_strings(dead),
+        combo_count: combos.combo_count(),
+        combos: combos
+            .combos()
+            .map(|(hole, weight)| ComboReport {
+                hand: card_strings(&hole).concat(),
+                weight,
+            })
+            .collect(),
+    })
+}
+
+impl fmt::Display for ExpandReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{}", "Range", self.range)?;
+        if !self.dead.is_empty() {
+            writeln!(f, "{:<16}{}", "Dead", self.dead.join(" "))?;
+        }
+        writeln!(f, "{:<16}{}", "Combos", self.combo_count)?;
+        writeln!(f)?;
+        for combo in self.combos.iter() {
+            match combo.weight < 1.0 {
+                true => writeln!(f, "{}:{}", combo.hand, combo.weight)?,
+                false => writeln!(f, "{}", combo.hand)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::parse_cards;
+
+    #[test]
+    fn expands_ranges() {
+        let report = expand("KK+, AKs:0.5", &parse_cards("As").unwrap()).unwrap();
+        assert_eq!(report.range, "KK+, AKs:0.5");
+        assert_eq!(report.combo_count, 3.0 + 6.0 + 0.5 * 3.0);
+        assert_eq!(report.combos.len(), 12);
+        assert_eq!(report.combos.iter().filter(|c| c.weight == 0.5).count(), 3);
+        assert!(report.combos.iter().all(|c| !c.hand.contains("As")));
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        assert_eq!(
+            expand("KK+, AX", &Hand::new()),
+            Err(Error::InvalidRange("KK+, AX".to_string()))
+        );
+    }
+}
@@ -2,6 +2,7 @@ mod ace_five;
 mod baduci;
 mod badugi;
 mod deuce_seven;
+mod preflop;
 mod six_plus;
 mod standard;
 pub(crate) mod utils;
@@ -10,6 +11,7 @@ pub use ace_five::AceFiveLowballLookup;
 pub use baduci::BaduciLookup;
 pub use badugi::BadugiLookup;
 pub use deuce_seven::DeuceSevenLowballLookup;
+pub use preflop::PreflopEquityLookup;
 pub use six_plus::SixPlusPokerLookup;
 pub use standard::PokerLookup;
 
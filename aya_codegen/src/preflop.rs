@@ -0,0 +1,223 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use aya_base::{
+    constants::{CARDS, RANK_COUNT},
+    Card, Hand,
+};
+
+use crate::PokerLookup;
+
+const CLASS_COUNT: usize = RANK_COUNT * RANK_COUNT;
+
+/// Generates tables of preflop hold'em equities for the 169 starting hand
+/// classes, estimated by seeded Monte Carlo simulation.
+///
+/// Classes are indexed as on a 13x13 grid with aces in the first row and
+/// column, pairs on the diagonal, suited hands above it and offsuit hands
+/// below it, i.e. AA is 0, AKs is 1 and AKo is 13. The tables are written as
+/// array literals of equities in hundredths of a percent.
+pub struct PreflopEquityLookup {
+    ranks_lookup: Vec<(u32, u16)>,
+    flush_lookup: Vec<u16>,
+}
+
+impl PreflopEquityLookup {
+    pub fn new() -> PreflopEquityLookup {
+        let lookup = PokerLookup::new();
+
+        let mut ranks_lookup = lookup.ranks_lookup.into_iter().collect::<Vec<_>>();
+        ranks_lookup.sort_unstable();
+
+        let mut flush_lookup = vec![0; 1 << RANK_COUNT];
+        for (key, value) in lookup.flush_lookup {
+            flush_lookup[key as usize] = value;
+        }
+
+        PreflopEquityLookup {
+            ranks_lookup,
+            flush_lookup,
+        }
+    }
+
+    /// Generates the 169x169 heads-up equity matrix, flattened in row-major
+    /// order, whose entry at `169 * i + j` is the equity of class `i` against
+    /// class `j`, averaged over all non-conflicting pairs of combos.
+    ///
+    /// Every one of the `boards` random boards is used to play out all of the
+    /// non-conflicting pairs of combos at once.
+    pub fn generate_heads_up_table(&self, boards: usize, seed: u64) -> String {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let combos = (0..CLASS_COUNT)
+            .flat_map(|class| class_combos(class).into_iter().map(move |h| (h, class)))
+            .collect::<Vec<_>>();
+
+        let mut shares = vec![0.0; CLASS_COUNT * CLASS_COUNT];
+        let mut counts = vec![0u64; CLASS_COUNT * CLASS_COUNT];
+        let mut ranks = Vec::with_capacity(combos.len());
+
+        for _ in 0..boards {
+            let mut deck = CARDS.to_vec();
+            let board = deal(&mut rng, &mut deck, 5).iter().collect::<Hand>();
+
+            ranks.clear();
+            ranks.extend(
+                combos
+                    .iter()
+                    .filter(|(hole, _)| hole.is_disjoint(&board))
+                    .map(|(hole, class)| {
+                        let mut hand = *hole;
+                        hand.extend(board.iter());
+                        (*hole, *class, self.rank(&hand))
+                    }),
+            );
+
+            for (i, (hole1, class1, rank1)) in ranks.iter().enumerate() {
+                for (hole2, class2, rank2) in &ranks[(i + 1)..] {
+                    if !hole1.is_disjoint(hole2) {
+                        continue;
+                    }
+
+                    let share = match rank1.cmp(rank2) {
+                        Ordering::Greater => 1.0,
+                        Ordering::Equal => 0.5,
+                        Ordering::Less => 0.0,
+                    };
+                    shares[CLASS_COUNT * class1 + class2] += share;
+                    shares[CLASS_COUNT * class2 + class1] += 1.0 - share;
+                    counts[CLASS_COUNT * class1 + class2] += 1;
+                    counts[CLASS_COUNT * class2 + class1] += 1;
+                }
+            }
+        }
+
+        let table = shares
+            .iter()
+            .zip(counts.iter())
+            .map(|(&share, &count)| share / count as f64)
+            .collect::<Vec<_>>();
+        format_table(&table)
+    }
+
+    /// Generates the table of equities against random hands, flattened in
+    /// row-major order, whose entry at `max_opponents * i + n - 1` is the
+    /// equity of class `i` against `n` opponents holding random hands,
+    /// estimated from `samples` random deals.
+    pub fn generate_multiway_table(
+        &self,
+        max_opponents: usize,
+        samples: usize,
+        seed: u64,
+    ) -> String {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let mut table = Vec::with_capacity(CLASS_COUNT * max_opponents);
+        let mut ranks = Vec::with_capacity(max_opponents + 1);
+
+        for class in 0..CLASS_COUNT {
+            let combos = class_combos(class);
+
+            for opponents in 1..=max_opponents {
+                let mut shares = 0.0;
+
+                for sample in 0..samples {
+                    // Cycle through the combos to reduce the variance.
+                    let hole = combos[sample % combos.len()];
+                    let mut deck = CARDS
+                        .iter()
+                        .filter(|c| !hole.contains(c))
+                        .copied()
+                        .collect::<Vec<_>>();
+                    let board = deal(&mut rng, &mut deck, 5).iter().collect::<Hand>();
+
+                    ranks.clear();
+                    ranks.push(self.rank_with_board(&hole, &board));
+                    for _ in 0..opponents {
+                        let opponent = deal(&mut rng, &mut deck, 2).iter().collect::<Hand>();
+                        ranks.push(self.rank_with_board(&opponent, &board));
+                    }
+
+                    let best_rank = *ranks.iter().max().unwrap();
+                    if ranks[0] == best_rank {
+                        let winners = ranks.iter().filter(|&&r| r == best_rank).count();
+                        shares += 1.0 / winners as f64;
+                    }
+                }
+
+                table.push(shares / samples as f64);
+            }
+        }
+
+        format_table(&table)
+    }
+
+    fn rank_with_board(&self, hole: &Hand, board: &Hand) -> u16 {
+        let mut hand = *hole;
+        hand.extend(board.iter());
+        self.rank(&hand)
+    }
+
+    fn rank(&self, hand: &Hand) -> u16 {
+        if hand.has_flush() {
+            self.flush_lookup[hand.flush_key() as usize]
+        } else {
+            let key = hand.rank_key();
+            let idx = self
+                .ranks_lookup
+                .binary_search_by_key(&key, |(k, _)| *k)
+                .unwrap();
+            self.ranks_lookup[idx].1
+        }
+    }
+}
+
+impl Default for PreflopEquityLookup {
+    fn default() -> PreflopEquityLookup {
+        PreflopEquityLookup::new()
+    }
+}
+
+/// Returns all the 2-card combos belonging to the starting hand class.
+fn class_combos(class: usize) -> Vec<Hand> {
+    let (row, col) = (class / 13, class % 13);
+    let high = 12 - row.min(col);
+    let low = 12 - row.max(col);
+
+    let mut combos = Vec::new();
+    for s1 in 0..4 {
+        for s2 in 0..4 {
+            let is_combo = match row.cmp(&col) {
+                Ordering::Equal => s1 < s2,
+                Ordering::Less => s1 == s2,
+                Ordering::Greater => s1 != s2,
+            };
+            if is_combo {
+                let mut hand = Hand::new();
+                hand.insert_unchecked(&CARDS[4 * high + s1]);
+                hand.insert_unchecked(&CARDS[4 * low + s2]);
+                combos.push(hand);
+            }
+        }
+    }
+    combos
+}
+
+/// Removes `n` random cards from the end of the deck and returns them.
+fn deal(rng: &mut fastrand::Rng, deck: &mut Vec<Card>, n: usize) -> Vec<Card> {
+    let len = deck.len();
+    for i in 0..n {
+        let j = rng.usize(..(len - i));
+        deck.swap(j, len - i - 1);
+    }
+    deck.split_off(len - n)
+}
+
+fn format_table(table: &[f64]) -> String {
+    let mut result = String::from("[");
+    for (i, equity) in table.iter().enumerate() {
+        if i % 13 == 0 {
+            result.push_str("\n    ");
+        }
+        write!(&mut result, "{}, ", (equity * 10000.0).round() as u16).unwrap();
+    }
+    result.push_str("\n]");
+    result
+}
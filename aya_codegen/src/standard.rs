@@ -12,8 +12,8 @@ use crate::{
 };
 
 pub struct PokerLookup {
-    flush_lookup: HashMap<u32, u16>,
-    ranks_lookup: HashMap<u32, u16>,
+    pub(crate) flush_lookup: HashMap<u32, u16>,
+    pub(crate) ranks_lookup: HashMap<u32, u16>,
 }
 
 impl PokerLookup {
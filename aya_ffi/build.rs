@@ -0,0 +1,26 @@
+//! Regenerates the C header `include/aya_poker.h` from the exported functions
+//! when the `header` feature is enabled.
+//!
+//! The header is committed to the repository, so that ordinary builds neither
+//! need cbindgen nor write to the source tree.
+
+fn main() {
+    #[cfg(feature = "header")]
+    generate_header();
+}
+
+#[cfg(feature = "header")]
+fn generate_header() {
+    use std::{env, path::PathBuf};
+
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    match cbindgen::generate(&crate_dir) {
+        Ok(bindings) => {
+            bindings.write_to_file(crate_dir.join("include").join("aya_poker.h"));
+        }
+        Err(error) => println!("cargo:warning=unable to generate the C header: {}", error),
+    }
+}
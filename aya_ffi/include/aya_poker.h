@@ -0,0 +1,301 @@
+/* Generated by cbindgen from the aya_ffi crate. Do not edit. */
+
+#ifndef AYA_POKER_H
+#define AYA_POKER_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The number of cards in a standard deck.
+#define AYA_CARD_COUNT 52
+
+// The maximum number of cards in a hand.
+#define AYA_MAX_HAND_SIZE 7
+
+// The result of a call which can fail.
+typedef enum AyaStatus {
+  // The call succeeded.
+  AYA_STATUS_OK,
+  // A pointer argument was null.
+  AYA_STATUS_NULL_POINTER,
+  // A card index was not less than 52.
+  AYA_STATUS_INVALID_CARD,
+  // A card appeared more than once in a hand.
+  AYA_STATUS_DUPLICATE_CARD,
+  // A hand would have held more than 7 cards.
+  AYA_STATUS_HAND_FULL,
+  // A string was not valid card or hand notation.
+  AYA_STATUS_PARSE_ERROR,
+} AyaStatus;
+
+// A poker hand-ranking category, i.e. a straight, a flush, etc.
+typedef enum AyaPokerRankCategory {
+  // A hand without a valid ranking, or a rank which is not valid.
+  AYA_POKER_RANK_CATEGORY_INELIGIBLE,
+  // A valid hand that does not fall into any of the other categories.
+  AYA_POKER_RANK_CATEGORY_HIGH_CARD,
+  // Two cards of one rank, and three cards of three other ranks.
+  AYA_POKER_RANK_CATEGORY_PAIR,
+  // Two cards of one rank, two cards of another rank and a fifth card of
+  // a different, third rank.
+  AYA_POKER_RANK_CATEGORY_TWO_PAIR,
+  // Three cards of the same rank, and two cards of two other ranks.
+  AYA_POKER_RANK_CATEGORY_THREE_OF_A_KIND,
+  // Five cards of sequential rank, with at least two different suits.
+  AYA_POKER_RANK_CATEGORY_STRAIGHT,
+  // Five cards of the same suit, but without sequential rank.
+  AYA_POKER_RANK_CATEGORY_FLUSH,
+  // Three cards of one rank and two cards of another rank.
+  AYA_POKER_RANK_CATEGORY_FULL_HOUSE,
+  // Four cards of the same rank and one card of another rank.
+  AYA_POKER_RANK_CATEGORY_FOUR_OF_A_KIND,
+  // Five cards of sequential rank, all of the same suit, excluding an
+  // ace-high sequence.
+  AYA_POKER_RANK_CATEGORY_STRAIGHT_FLUSH,
+  // The sequence A-K-Q-J-T all of the same suit.
+  AYA_POKER_RANK_CATEGORY_ROYAL_FLUSH,
+  // Five cards of the same rank, made with the help of wild cards.
+  AYA_POKER_RANK_CATEGORY_FIVE_OF_A_KIND,
+} AyaPokerRankCategory;
+
+// A Badugi/Baduci hand-ranking category corresponding to the size of the
+// made hand.
+typedef enum AyaBadugiRankCategory {
+  // A rank which is not valid.
+  AYA_BADUGI_RANK_CATEGORY_INVALID,
+  // A single card.
+  AYA_BADUGI_RANK_CATEGORY_ONE_CARD,
+  // Two cards with different suits and ranks.
+  AYA_BADUGI_RANK_CATEGORY_TWO_CARDS,
+  // Three cards with three distinct ranks and suits.
+  AYA_BADUGI_RANK_CATEGORY_THREE_CARDS,
+  // Four cards with four distinct ranks and suits.
+  AYA_BADUGI_RANK_CATEGORY_FOUR_CARDS,
+} AyaBadugiRankCategory;
+
+// A card, given by its index `4 * rank + suit` in the deck.
+typedef uint8_t AyaCard;
+
+// An unordered hand of up to 7 distinct cards.
+//
+// Only the first `len` cards are part of the hand. Hands should be built
+// with the `aya_hand_*` functions, which keep them valid.
+typedef struct AyaHand {
+  // The cards of the hand.
+  AyaCard cards[AYA_MAX_HAND_SIZE];
+  // The number of cards in the hand.
+  uint8_t len;
+} AyaHand;
+
+// The rankings of both halves of a Badeucy hand.
+typedef struct AyaBadeucyRank {
+  // The Baduci ranking of the hand.
+  uint16_t baduci;
+  // The deuce-seven lowball ranking of the hand.
+  uint16_t deuce_seven;
+} AyaBadeucyRank;
+
+// The rankings of both halves of a Badacey hand.
+typedef struct AyaBadaceyRank {
+  // The Badugi ranking of the hand.
+  uint16_t badugi;
+  // The ace-five lowball ranking of the hand.
+  uint16_t ace_five;
+} AyaBadaceyRank;
+
+// The card returned in place of a card which does not exist.
+#define AYA_INVALID_CARD 255
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the card of the given rank, from 0 for a two to 12 for an ace, and
+// suit, from 0 to 3 for clubs, diamonds, hearts and spades, or
+// `AYA_INVALID_CARD` if either is out of range.
+AyaCard aya_card_new(uint8_t rank, uint8_t suit);
+
+// Returns the rank of the card, from 0 for a two to 12 for an ace, or 255 if
+// the card is not valid.
+uint8_t aya_card_rank(AyaCard card);
+
+// Returns the suit of the card, from 0 to 3 for clubs, diamonds, hearts and
+// spades, or 255 if the card is not valid.
+uint8_t aya_card_suit(AyaCard card);
+
+// Parses a card written as a rank and a suit, e.g. `"Ah"` or `"Tc"`.
+//
+// # Safety
+//
+// `text` must be null or point to a null-terminated string, and `out` must
+// be null or valid for writes.
+enum AyaStatus aya_card_parse(const char *text, AyaCard *out);
+
+// Writes the card as a null-terminated string of a rank and a suit, e.g.
+// `"Ah"`, to a buffer of at least 3 bytes.
+//
+// # Safety
+//
+// `out` must be null or valid for writes of 3 bytes.
+enum AyaStatus aya_card_format(AyaCard card, char *out);
+
+// Returns an empty hand.
+struct AyaHand aya_hand_new(void);
+
+// Adds a card to the hand.
+//
+// # Safety
+//
+// `hand` must be null or valid for reads and writes.
+enum AyaStatus aya_hand_insert(struct AyaHand *hand, AyaCard card);
+
+// Builds a hand from an array of `len` cards.
+//
+// # Safety
+//
+// `cards` must be null or valid for reads of `len` cards, and `out` must be
+// null or valid for writes.
+enum AyaStatus aya_hand_from_cards(const AyaCard *cards, size_t len, struct AyaHand *out);
+
+// Parses a hand of space-separated cards, e.g. `"Ah Kd 7c"`.
+//
+// # Safety
+//
+// `text` must be null or point to a null-terminated string, and `out` must
+// be null or valid for writes.
+enum AyaStatus aya_hand_parse(const char *text, struct AyaHand *out);
+
+// Returns `true` if the hand holds the card.
+bool aya_hand_contains(struct AyaHand hand, AyaCard card);
+
+// Returns the rank of the best 5-card poker hand that can be made from the
+// cards, or 0 if the hand is not valid.
+uint16_t aya_poker_rank(struct AyaHand hand);
+
+// Returns the rank of the best 5-card short-deck (6+) poker hand that can be
+// made from the cards, or 0 if the hand is not valid.
+uint16_t aya_short_deck_rank(struct AyaHand hand);
+
+// Returns the rank of the best 5-card ace-five lowball hand that can be made
+// from the cards, or 0 if the hand is not valid.
+uint16_t aya_ace_five_rank(struct AyaHand hand);
+
+// Returns the rank of the best 5-card deuce-seven lowball hand that can be
+// made from the cards, or 0 if the hand is not valid.
+uint16_t aya_deuce_seven_rank(struct AyaHand hand);
+
+// Returns the rank of the best Badugi hand that can be made from the cards,
+// or 0 if the hand is not valid.
+uint16_t aya_badugi_rank(struct AyaHand hand);
+
+// Returns the rank of the best Baduci hand that can be made from the cards,
+// or 0 if the hand is not valid.
+uint16_t aya_baduci_rank(struct AyaHand hand);
+
+// Returns the rank of the best 5-card poker hand that can be made from the
+// cards with deuces wild, or 0 if the hand is not valid.
+uint16_t aya_deuces_wild_rank(struct AyaHand hand);
+
+// Returns the Baduci and deuce-seven lowball ranks of a Badeucy hand, or
+// zeroes if the hand is not valid.
+struct AyaBadeucyRank aya_badeucy_rank(struct AyaHand hand);
+
+// Returns the Badugi and ace-five lowball ranks of a Badacey hand, or zeroes
+// if the hand is not valid.
+struct AyaBadaceyRank aya_badacey_rank(struct AyaHand hand);
+
+// Returns the rank of the best 5-card poker hand that can be made with two
+// hole cards and three board cards.
+//
+// Returns 0 if either hand is not valid, they share a card, or there are
+// fewer than 2 hole cards or 3 board cards.
+uint16_t aya_omaha_rank(struct AyaHand hole, struct AyaHand board);
+
+// Returns the rank of the best 5-card ace-five lowball hand that can be made
+// with two hole cards and three board cards.
+//
+// Returns 0 if either hand is not valid, they share a card, or there are
+// fewer than 2 hole cards or 3 board cards.
+uint16_t aya_omaha_lo_rank(struct AyaHand hole, struct AyaHand board);
+
+// Returns the rank of the best 5-card poker hand that can be made with
+// exactly `hole_count` hole cards and `5 - hole_count` board cards.
+//
+// Returns 0 if either hand is not valid, they share a card, `hole_count` is
+// greater than 5, or there are too few hole or board cards.
+uint16_t aya_exact_hole_rank(struct AyaHand hole, struct AyaHand board, uint8_t hole_count);
+
+// Returns the rank of the best 5-card Manila poker hand that can be made
+// with two hole cards and three board cards.
+//
+// Returns 0 if either hand is not valid, they share a card, or there are
+// fewer than 2 hole cards or 3 board cards. The caller is responsible for
+// verifying that no card is of rank less than 7.
+uint16_t aya_manila_rank(struct AyaHand hole, struct AyaHand board);
+
+// Returns the rank of the best 5-card poker hand that can be made from the
+// cards and the given number of jokers, which are fully wild.
+//
+// Returns 0 if the hand is not valid or it holds more than 7 cards
+// including the jokers.
+uint16_t aya_joker_rank(struct AyaHand cards, uint8_t jokers);
+
+// Returns the rank of the best 5-card poker hand that can be made from the
+// cards and the given number of jokers, which can only complete straights
+// and flushes or otherwise play as aces.
+//
+// Returns 0 if the hand is not valid or it holds more than 7 cards
+// including the jokers.
+uint16_t aya_bug_rank(struct AyaHand cards, uint8_t jokers);
+
+// Converts an ace-five lowball rank into an ace-five eight-or-better rank,
+// i.e. returns 0 for hands which do not qualify.
+uint16_t aya_ace_five_to_lo_8_rank(uint16_t rank);
+
+// Converts a deuce-seven lowball rank into a deuce-seven eight-or-better
+// rank, i.e. returns 0 for hands which do not qualify.
+uint16_t aya_deuce_seven_to_lo_8_rank(uint16_t rank);
+
+// Returns the category of a rank returned by `aya_poker_rank` or any of the
+// other functions ranking regular poker hands, or `Ineligible` if the rank
+// is not valid.
+enum AyaPokerRankCategory aya_poker_rank_category(uint16_t rank);
+
+// Returns the category of a rank returned by `aya_short_deck_rank` or
+// `aya_manila_rank`, or `Ineligible` if the rank is not valid.
+enum AyaPokerRankCategory aya_short_deck_rank_category(uint16_t rank);
+
+// Returns the category of a rank returned by `aya_ace_five_rank` or
+// `aya_omaha_lo_rank`, or `Ineligible` if the rank is not valid.
+enum AyaPokerRankCategory aya_ace_five_rank_category(uint16_t rank);
+
+// Returns the category of a rank returned by `aya_deuce_seven_rank`, or
+// `Ineligible` if the rank is not valid.
+enum AyaPokerRankCategory aya_deuce_seven_rank_category(uint16_t rank);
+
+// Returns the category of a rank returned by `aya_badugi_rank`, or
+// `Invalid` if the rank is not valid.
+enum AyaBadugiRankCategory aya_badugi_rank_category(uint16_t rank);
+
+// Returns the category of a rank returned by `aya_baduci_rank`, or
+// `Invalid` if the rank is not valid.
+enum AyaBadugiRankCategory aya_baduci_rank_category(uint16_t rank);
+
+// Returns the name of an `AyaPokerRankCategory` as a static null-terminated
+// string, e.g. `"Full House"`, or `"Unknown"` if the value is not a
+// category.
+const char *aya_poker_rank_category_name(uint32_t category);
+
+// Returns the name of an `AyaBadugiRankCategory` as a static
+// null-terminated string, e.g. `"Three Cards"`, or `"Unknown"` if the value
+// is not a category.
+const char *aya_badugi_rank_category_name(uint32_t category);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AYA_POKER_H */
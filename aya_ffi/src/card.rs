@@ -0,0 +1,139 @@
+use std::ffi::{c_char, CStr};
+
+use aya_poker::base::{Card, CARDS};
+
+use crate::AyaStatus;
+
+/// A card, given by its index `4 * rank + suit` in the deck.
+pub type AyaCard = u8;
+
+/// The number of cards in a standard deck.
+pub const AYA_CARD_COUNT: u8 = 52;
+
+/// The card returned in place of a card which does not exist.
+pub const AYA_INVALID_CARD: AyaCard = 255;
+
+pub(crate) fn to_card(card: AyaCard) -> Option<Card> {
+    CARDS.get(card as usize).copied()
+}
+
+/// Returns the card of the given rank, from 0 for a two to 12 for an ace, and
+/// suit, from 0 to 3 for clubs, diamonds, hearts and spades, or
+/// `AYA_INVALID_CARD` if either is out of range.
+#[no_mangle]
+pub extern "C" fn aya_card_new(rank: u8, suit: u8) -> AyaCard {
+    match rank < 13 && suit < 4 {
+        true => 4 * rank + suit,
+        false => AYA_INVALID_CARD,
+    }
+}
+
+/// Returns the rank of the card, from 0 for a two to 12 for an ace, or 255 if
+/// the card is not valid.
+#[no_mangle]
+pub extern "C" fn aya_card_rank(card: AyaCard) -> u8 {
+    to_card(card).map_or(255, |card| card.rank() as u8)
+}
+
+/// Returns the suit of the card, from 0 to 3 for clubs, diamonds, hearts and
+/// spades, or 255 if the card is not valid.
+#[no_mangle]
+pub extern "C" fn aya_card_suit(card: AyaCard) -> u8 {
+    to_card(card).map_or(255, |card| card.suit() as u8)
+}
+
+/// Parses a card written as a rank and a suit, e.g. `"Ah"` or `"Tc"`.
+///
+/// # Safety
+///
+/// `text` must be null or point to a null-terminated string, and `out` must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn aya_card_parse(text: *const c_char, out: *mut AyaCard) -> AyaStatus {
+    if text.is_null() || out.is_null() {
+        return AyaStatus::NullPointer;
+    }
+    let card = CStr::from_ptr(text)
+        .to_str()
+        .ok()
+        .and_then(|text| text.parse::<Card>().ok());
+    match card {
+        Some(card) => {
+            *out = card.idx() as AyaCard;
+            AyaStatus::Ok
+        }
+        None => AyaStatus::ParseError,
+    }
+}
+
+/// Writes the card as a null-terminated string of a rank and a suit, e.g.
+/// `"Ah"`, to a buffer of at least 3 bytes.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes of 3 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aya_card_format(card: AyaCard, out: *mut c_char) -> AyaStatus {
+    if out.is_null() {
+        return AyaStatus::NullPointer;
+    }
+    let card = match to_card(card) {
+        Some(card) => card,
+        None => return AyaStatus::InvalidCard,
+    };
+    let text = format!("{:?}", card);
+    for (i, byte) in text.bytes().chain(Some(0)).enumerate() {
+        *out.add(i) = byte as c_char;
+    }
+    AyaStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn constructs_cards() {
+        let ace_of_hearts = aya_card_new(12, 2);
+        assert_eq!(to_card(ace_of_hearts), Some("Ah".parse().unwrap()));
+        assert_eq!(aya_card_rank(ace_of_hearts), 12);
+        assert_eq!(aya_card_suit(ace_of_hearts), 2);
+        assert_eq!(aya_card_new(13, 0), AYA_INVALID_CARD);
+        assert_eq!(aya_card_new(0, 4), AYA_INVALID_CARD);
+        assert_eq!(aya_card_rank(AYA_CARD_COUNT), 255);
+    }
+
+    #[test]
+    fn parses_and_formats_cards() {
+        let mut card = AYA_INVALID_CARD;
+        let text = CString::new("Tc").unwrap();
+        assert_eq!(
+            unsafe { aya_card_parse(text.as_ptr(), &mut card) },
+            AyaStatus::Ok
+        );
+        assert_eq!(card, aya_card_new(8, 0));
+
+        let mut buffer: [c_char; 3] = [1; 3];
+        assert_eq!(
+            unsafe { aya_card_format(card, buffer.as_mut_ptr()) },
+            AyaStatus::Ok
+        );
+        let formatted = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        assert_eq!(formatted.to_str(), Ok("Tc"));
+
+        let text = CString::new("1c").unwrap();
+        assert_eq!(
+            unsafe { aya_card_parse(text.as_ptr(), &mut card) },
+            AyaStatus::ParseError
+        );
+        assert_eq!(
+            unsafe { aya_card_parse(std::ptr::null(), &mut card) },
+            AyaStatus::NullPointer
+        );
+        assert_eq!(
+            unsafe { aya_card_format(AYA_INVALID_CARD, buffer.as_mut_ptr()) },
+            AyaStatus::InvalidCard
+        );
+    }
+}
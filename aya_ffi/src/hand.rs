@@ -0,0 +1,219 @@
+use std::ffi::{c_char, CStr};
+
+use aya_poker::base::Hand;
+
+use crate::{card::to_card, AyaCard, AyaStatus, AYA_INVALID_CARD};
+
+/// The maximum number of cards in a hand.
+pub const AYA_MAX_HAND_SIZE: usize = 7;
+
+/// An unordered hand of up to 7 distinct cards.
+///
+/// Only the first `len` cards are part of the hand. Hands should be built
+/// with the `aya_hand_*` functions, which keep them valid.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct AyaHand {
+    /// The cards of the hand.
+    pub cards: [AyaCard; AYA_MAX_HAND_SIZE],
+    /// The number of cards in the hand.
+    pub len: u8,
+}
+
+impl AyaHand {
+    /// Converts the hand, returning `None` if it holds too many cards, an
+    /// invalid card or a card twice.
+    pub(crate) fn to_hand(self) -> Option<Hand> {
+        let cards = self.cards.get(..self.len as usize)?;
+        let mut hand = Hand::new();
+        for &card in cards {
+            let card = to_card(card)?;
+            if hand.contains(&card) {
+                return None;
+            }
+            hand.insert_unchecked(&card);
+        }
+        Some(hand)
+    }
+
+    fn from_hand(hand: &Hand) -> AyaHand {
+        let mut result = aya_hand_new();
+        for card in hand.iter() {
+            result.cards[result.len as usize] = card.idx() as AyaCard;
+            result.len += 1;
+        }
+        result
+    }
+}
+
+/// Returns an empty hand.
+#[no_mangle]
+pub extern "C" fn aya_hand_new() -> AyaHand {
+    AyaHand {
+        cards: [AYA_INVALID_CARD; AYA_MAX_HAND_SIZE],
+        len: 0,
+    }
+}
+
+/// Adds a card to the hand.
+///
+/// # Safety
+///
+/// `hand` must be null or valid for reads and writes.
+#[no_mangle]
+pub unsafe extern "C" fn aya_hand_insert(hand: *mut AyaHand, card: AyaCard) -> AyaStatus {
+    let hand = match hand.as_mut() {
+        Some(hand) => hand,
+        None => return AyaStatus::NullPointer,
+    };
+    if to_card(card).is_none() {
+        return AyaStatus::InvalidCard;
+    }
+    if aya_hand_contains(*hand, card) {
+        return AyaStatus::DuplicateCard;
+    }
+    if hand.len as usize >= AYA_MAX_HAND_SIZE {
+        return AyaStatus::HandFull;
+    }
+    hand.cards[hand.len as usize] = card;
+    hand.len += 1;
+    AyaStatus::Ok
+}
+
+/// Builds a hand from an array of `len` cards.
+///
+/// # Safety
+///
+/// `cards` must be null or valid for reads of `len` cards, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn aya_hand_from_cards(
+    cards: *const AyaCard,
+    len: usize,
+    out: *mut AyaHand,
+) -> AyaStatus {
+    if cards.is_null() || out.is_null() {
+        return AyaStatus::NullPointer;
+    }
+    let mut hand = aya_hand_new();
+    for &card in std::slice::from_raw_parts(cards, len) {
+        let status = aya_hand_insert(&mut hand, card);
+        if status != AyaStatus::Ok {
+            return status;
+        }
+    }
+    *out = hand;
+    AyaStatus::Ok
+}
+
+/// Parses a hand of space-separated cards, e.g. `"Ah Kd 7c"`.
+///
+/// # Safety
+///
+/// `text` must be null or point to a null-terminated string, and `out` must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn aya_hand_parse(text: *const c_char, out: *mut AyaHand) -> AyaStatus {
+    if text.is_null() || out.is_null() {
+        return AyaStatus::NullPointer;
+    }
+    let hand = CStr::from_ptr(text)
+        .to_str()
+        .ok()
+        .and_then(|text| text.parse::<Hand>().ok());
+    match hand {
+        Some(hand) => {
+            *out = AyaHand::from_hand(&hand);
+            AyaStatus::Ok
+        }
+        None => AyaStatus::ParseError,
+    }
+}
+
+/// Returns `true` if the hand holds the card.
+#[no_mangle]
+pub extern "C" fn aya_hand_contains(hand: AyaHand, card: AyaCard) -> bool {
+    hand.cards
+        .iter()
+        .take(hand.len as usize)
+        .any(|&held| held == card)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aya_card_new;
+    use std::ffi::CString;
+
+    #[test]
+    fn builds_hands() {
+        let mut hand = aya_hand_new();
+        let ace = aya_card_new(12, 0);
+        assert_eq!(unsafe { aya_hand_insert(&mut hand, ace) }, AyaStatus::Ok);
+        assert!(aya_hand_contains(hand, ace));
+        assert_eq!(
+            unsafe { aya_hand_insert(&mut hand, ace) },
+            AyaStatus::DuplicateCard
+        );
+        assert_eq!(
+            unsafe { aya_hand_insert(&mut hand, AYA_INVALID_CARD) },
+            AyaStatus::InvalidCard
+        );
+        for card in 0..6 {
+            assert_eq!(unsafe { aya_hand_insert(&mut hand, card) }, AyaStatus::Ok);
+        }
+        assert_eq!(
+            unsafe { aya_hand_insert(&mut hand, 6) },
+            AyaStatus::HandFull
+        );
+        assert_eq!(hand.to_hand().unwrap().len(), 7);
+    }
+
+    #[test]
+    fn builds_hands_from_cards() {
+        let cards = [aya_card_new(12, 2), aya_card_new(11, 1)];
+        let mut hand = aya_hand_new();
+        let status = unsafe { aya_hand_from_cards(cards.as_ptr(), cards.len(), &mut hand) };
+        assert_eq!(status, AyaStatus::Ok);
+        assert_eq!(hand.to_hand(), Some("Ah Kd".parse().unwrap()));
+
+        let cards = [cards[0], cards[0]];
+        let status = unsafe { aya_hand_from_cards(cards.as_ptr(), cards.len(), &mut hand) };
+        assert_eq!(status, AyaStatus::DuplicateCard);
+    }
+
+    #[test]
+    fn parses_hands() {
+        let mut hand = aya_hand_new();
+        let text = CString::new("Ah Kd 7c").unwrap();
+        assert_eq!(
+            unsafe { aya_hand_parse(text.as_ptr(), &mut hand) },
+            AyaStatus::Ok
+        );
+        assert_eq!(hand.len, 3);
+        assert!(aya_hand_contains(hand, aya_card_new(5, 0)));
+
+        let text = CString::new("Ah Ah").unwrap();
+        assert_eq!(
+            unsafe { aya_hand_parse(text.as_ptr(), &mut hand) },
+            AyaStatus::ParseError
+        );
+        assert_eq!(
+            unsafe { aya_hand_parse(text.as_ptr(), std::ptr::null_mut()) },
+            AyaStatus::NullPointer
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_hands() {
+        let mut hand = aya_hand_new();
+        hand.len = 8;
+        assert_eq!(hand.to_hand(), None);
+        hand.len = 1;
+        hand.cards[0] = AYA_INVALID_CARD;
+        assert_eq!(hand.to_hand(), None);
+        hand.len = 2;
+        hand.cards = [3; AYA_MAX_HAND_SIZE];
+        assert_eq!(hand.to_hand(), None);
+    }
+}
@@ -0,0 +1,56 @@
+//! C ABI bindings for the AyaPoker hand evaluators.
+//!
+//! This crate builds a shared and a static library exposing the construction
+//! of cards and hands and every hand-ranking function of `aya_poker` through a
+//! stable C ABI. The C header `include/aya_poker.h` is generated from this
+//! crate by [cbindgen](https://github.com/mozilla/cbindgen), and can be
+//! regenerated by building the crate with the `header` feature.
+//!
+//! Cards are passed as their index in the deck, `4 * rank + suit`, where the
+//! ranks are numbered from 0 for a two to 12 for an ace, and the suits from 0
+//! to 3 for clubs, diamonds, hearts and spades. Hands are passed by value as
+//! [`AyaHand`]s, which hold up to 7 cards. Ranks are returned as integers
+//! which, as in `aya_poker`, are only comparable with ranks returned by the
+//! same function, with greater values being better.
+//!
+//! None of the functions panic. Ranking functions return the invalid rank 0
+//! when given hands which are not valid, e.g. which contain a card twice.
+//!
+//! # Examples
+//!
+//! ```c
+//! #include "aya_poker.h"
+//!
+//! AyaHand hand;
+//! if (aya_hand_parse("Ks Kd Ac 6s 4c Jc Th", &hand) == AYA_STATUS_OK) {
+//!     uint16_t rank = aya_poker_rank(hand);
+//!     AyaPokerRankCategory category = aya_poker_rank_category(rank);
+//!     printf("%s\n", aya_poker_rank_category_name(category)); // Pair
+//! }
+//! ```
+
+mod card;
+mod hand;
+mod rank;
+
+pub use card::*;
+pub use hand::*;
+pub use rank::*;
+
+/// The result of a call which can fail.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AyaStatus {
+    /// The call succeeded.
+    Ok,
+    /// A pointer argument was null.
+    NullPointer,
+    /// A card index was not less than 52.
+    InvalidCard,
+    /// A card appeared more than once in a hand.
+    DuplicateCard,
+    /// A hand would have held more than 7 cards.
+    HandFull,
+    /// A string was not valid card or hand notation.
+    ParseError,
+}
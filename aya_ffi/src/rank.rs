@@ -0,0 +1,567 @@
+use std::ffi::c_char;
+
+use aya_poker::{
+    base::{Hand, CARDS},
+    PokerRankCategory,
+};
+
+use crate::AyaHand;
+
+/// The rankings of both halves of a Badeucy hand.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct AyaBadeucyRank {
+    /// The Baduci ranking of the hand.
+    pub baduci: u16,
+    /// The deuce-seven lowball ranking of the hand.
+    pub deuce_seven: u16,
+}
+
+/// The rankings of both halves of a Badacey hand.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct AyaBadaceyRank {
+    /// The Badugi ranking of the hand.
+    pub badugi: u16,
+    /// The ace-five lowball ranking of the hand.
+    pub ace_five: u16,
+}
+
+/// A poker hand-ranking category, i.e. a straight, a flush, etc.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AyaPokerRankCategory {
+    /// A hand without a valid ranking, or a rank which is not valid.
+    Ineligible,
+    /// A valid hand that does not fall into any of the other categories.
+    HighCard,
+    /// Two cards of one rank, and three cards of three other ranks.
+    Pair,
+    /// Two cards of one rank, two cards of another rank and a fifth card of
+    /// a different, third rank.
+    TwoPair,
+    /// Three cards of the same rank, and two cards of two other ranks.
+    ThreeOfAKind,
+    /// Five cards of sequential rank, with at least two different suits.
+    Straight,
+    /// Five cards of the same suit, but without sequential rank.
+    Flush,
+    /// Three cards of one rank and two cards of another rank.
+    FullHouse,
+    /// Four cards of the same rank and one card of another rank.
+    FourOfAKind,
+    /// Five cards of sequential rank, all of the same suit, excluding an
+    /// ace-high sequence.
+    StraightFlush,
+    /// The sequence A-K-Q-J-T all of the same suit.
+    RoyalFlush,
+    /// Five cards of the same rank, made with the help of wild cards.
+    FiveOfAKind,
+}
+
+impl From<PokerRankCategory> for AyaPokerRankCategory {
+    fn from(category: PokerRankCategory) -> AyaPokerRankCategory {
+        match category {
+            PokerRankCategory::Ineligible => AyaPokerRankCategory::Ineligible,
+            PokerRankCategory::HighCard => AyaPokerRankCategory::HighCard,
+            PokerRankCategory::Pair => AyaPokerRankCategory::Pair,
+            PokerRankCategory::TwoPair => AyaPokerRankCategory::TwoPair,
+            PokerRankCategory::ThreeOfAKind => AyaPokerRankCategory::ThreeOfAKind,
+            PokerRankCategory::Straight => AyaPokerRankCategory::Straight,
+            PokerRankCategory::Flush => AyaPokerRankCategory::Flush,
+            PokerRankCategory::FullHouse => AyaPokerRankCategory::FullHouse,
+            PokerRankCategory::FourOfAKind => AyaPokerRankCategory::FourOfAKind,
+            PokerRankCategory::StraightFlush => AyaPokerRankCategory::StraightFlush,
+            PokerRankCategory::RoyalFlush => AyaPokerRankCategory::RoyalFlush,
+            PokerRankCategory::FiveOfAKind => AyaPokerRankCategory::FiveOfAKind,
+        }
+    }
+}
+
+/// A Badugi/Baduci hand-ranking category corresponding to the size of the
+/// made hand.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AyaBadugiRankCategory {
+    /// A rank which is not valid.
+    Invalid,
+    /// A single card.
+    OneCard,
+    /// Two cards with different suits and ranks.
+    TwoCards,
+    /// Three cards with three distinct ranks and suits.
+    ThreeCards,
+    /// Four cards with four distinct ranks and suits.
+    FourCards,
+}
+
+impl From<aya_poker::BadugiRankCategory> for AyaBadugiRankCategory {
+    fn from(category: aya_poker::BadugiRankCategory) -> AyaBadugiRankCategory {
+        match category {
+            aya_poker::BadugiRankCategory::OneCard => AyaBadugiRankCategory::OneCard,
+            aya_poker::BadugiRankCategory::TwoCards => AyaBadugiRankCategory::TwoCards,
+            aya_poker::BadugiRankCategory::ThreeCards => AyaBadugiRankCategory::ThreeCards,
+            aya_poker::BadugiRankCategory::FourCards => AyaBadugiRankCategory::FourCards,
+        }
+    }
+}
+
+/// Builds a hand from indices into [`CARDS`].
+fn hand_of(indices: &[usize]) -> Hand {
+    indices.iter().map(|&idx| CARDS[idx]).collect()
+}
+
+/// Returns both hands if they are valid and do not share a card.
+fn to_hands(hole: AyaHand, board: AyaHand) -> Option<(Hand, Hand)> {
+    let (hole, board) = (hole.to_hand()?, board.to_hand()?);
+    match hole.iter().any(|card| board.contains(card)) {
+        true => None,
+        false => Some((hole, board)),
+    }
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards, or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_poker_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::poker_rank(&hand).0)
+}
+
+/// Returns the rank of the best 5-card short-deck (6+) poker hand that can be
+/// made from the cards, or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_short_deck_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::short_deck_rank(&hand).0)
+}
+
+/// Returns the rank of the best 5-card ace-five lowball hand that can be made
+/// from the cards, or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_ace_five_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::ace_five_rank(&hand).0)
+}
+
+/// Returns the rank of the best 5-card deuce-seven lowball hand that can be
+/// made from the cards, or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_deuce_seven_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::deuce_seven_rank(&hand).0)
+}
+
+/// Returns the rank of the best Badugi hand that can be made from the cards,
+/// or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_badugi_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::badugi_rank(&hand).0)
+}
+
+/// Returns the rank of the best Baduci hand that can be made from the cards,
+/// or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_baduci_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::baduci_rank(&hand).0)
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards with deuces wild, or 0 if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_deuces_wild_rank(hand: AyaHand) -> u16 {
+    hand.to_hand()
+        .map_or(0, |hand| aya_poker::deuces_wild_rank(&hand).0)
+}
+
+/// Returns the Baduci and deuce-seven lowball ranks of a Badeucy hand, or
+/// zeroes if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_badeucy_rank(hand: AyaHand) -> AyaBadeucyRank {
+    let rank = hand.to_hand().map(|hand| aya_poker::badeucy_rank(&hand));
+    AyaBadeucyRank {
+        baduci: rank.map_or(0, |rank| rank.baduci.0),
+        deuce_seven: rank.map_or(0, |rank| rank.deuce_seven.0),
+    }
+}
+
+/// Returns the Badugi and ace-five lowball ranks of a Badacey hand, or zeroes
+/// if the hand is not valid.
+#[no_mangle]
+pub extern "C" fn aya_badacey_rank(hand: AyaHand) -> AyaBadaceyRank {
+    let rank = hand.to_hand().map(|hand| aya_poker::badacey_rank(&hand));
+    AyaBadaceyRank {
+        badugi: rank.map_or(0, |rank| rank.badugi.0),
+        ace_five: rank.map_or(0, |rank| rank.ace_five.0),
+    }
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made with two
+/// hole cards and three board cards.
+///
+/// Returns 0 if either hand is not valid, they share a card, or there are
+/// fewer than 2 hole cards or 3 board cards.
+#[no_mangle]
+pub extern "C" fn aya_omaha_rank(hole: AyaHand, board: AyaHand) -> u16 {
+    match to_hands(hole, board) {
+        Some((hole, board)) if hole.len() >= 2 && board.len() >= 3 => {
+            aya_poker::omaha_rank(&hole, &board).0
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the rank of the best 5-card ace-five lowball hand that can be made
+/// with two hole cards and three board cards.
+///
+/// Returns 0 if either hand is not valid, they share a card, or there are
+/// fewer than 2 hole cards or 3 board cards.
+#[no_mangle]
+pub extern "C" fn aya_omaha_lo_rank(hole: AyaHand, board: AyaHand) -> u16 {
+    match to_hands(hole, board) {
+        Some((hole, board)) if hole.len() >= 2 && board.len() >= 3 => {
+            aya_poker::omaha_lo_rank(&hole, &board).0
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made with
+/// exactly `hole_count` hole cards and `5 - hole_count` board cards.
+///
+/// Returns 0 if either hand is not valid, they share a card, `hole_count` is
+/// greater than 5, or there are too few hole or board cards.
+#[no_mangle]
+pub extern "C" fn aya_exact_hole_rank(hole: AyaHand, board: AyaHand, hole_count: u8) -> u16 {
+    match to_hands(hole, board) {
+        Some((hole, board)) if hole_count <= 5 => {
+            aya_poker::exact_hole_rank(&hole, &board, hole_count as usize).0
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the rank of the best 5-card Manila poker hand that can be made
+/// with two hole cards and three board cards.
+///
+/// Returns 0 if either hand is not valid, they share a card, or there are
+/// fewer than 2 hole cards or 3 board cards. The caller is responsible for
+/// verifying that no card is of rank less than 7.
+#[no_mangle]
+pub extern "C" fn aya_manila_rank(hole: AyaHand, board: AyaHand) -> u16 {
+    to_hands(hole, board)
+        .and_then(|(hole, board)| aya_poker::manila_rank(&hole, &board))
+        .map_or(0, |rank| rank.0)
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards and the given number of jokers, which are fully wild.
+///
+/// Returns 0 if the hand is not valid or it holds more than 7 cards
+/// including the jokers.
+#[no_mangle]
+pub extern "C" fn aya_joker_rank(cards: AyaHand, jokers: u8) -> u16 {
+    match cards.to_hand() {
+        Some(hand) if hand.len() + jokers as usize <= 7 => {
+            aya_poker::joker_rank(&aya_poker::JokerHand::new(hand, jokers)).0
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards and the given number of jokers, which can only complete straights
+/// and flushes or otherwise play as aces.
+///
+/// Returns 0 if the hand is not valid or it holds more than 7 cards
+/// including the jokers.
+#[no_mangle]
+pub extern "C" fn aya_bug_rank(cards: AyaHand, jokers: u8) -> u16 {
+    match cards.to_hand() {
+        Some(hand) if hand.len() + jokers as usize <= 7 => {
+            aya_poker::bug_rank(&aya_poker::JokerHand::new(hand, jokers)).0
+        }
+        _ => 0,
+    }
+}
+
+/// Converts an ace-five lowball rank into an ace-five eight-or-better rank,
+/// i.e. returns 0 for hands which do not qualify.
+#[no_mangle]
+pub extern "C" fn aya_ace_five_to_lo_8_rank(rank: u16) -> u16 {
+    aya_poker::AceFiveHandRank(rank).to_lo_8_rank().0
+}
+
+/// Converts a deuce-seven lowball rank into a deuce-seven eight-or-better
+/// rank, i.e. returns 0 for hands which do not qualify.
+#[no_mangle]
+pub extern "C" fn aya_deuce_seven_to_lo_8_rank(rank: u16) -> u16 {
+    aya_poker::DeuceSevenHandRank(rank).to_lo_8_rank().0
+}
+
+/// Returns the category of a rank returned by `aya_poker_rank` or any of the
+/// other functions ranking regular poker hands, or `Ineligible` if the rank
+/// is not valid.
+#[no_mangle]
+pub extern "C" fn aya_poker_rank_category(rank: u16) -> AyaPokerRankCategory {
+    // The best possible hand is five aces, which needs wild cards.
+    let best = aya_poker::deuces_wild_rank(&hand_of(&[48, 49, 50, 51, 0]));
+    match rank <= best.0 {
+        true => aya_poker::PokerHandRank(rank).rank_category().into(),
+        false => AyaPokerRankCategory::Ineligible,
+    }
+}
+
+/// Returns the category of a rank returned by `aya_short_deck_rank` or
+/// `aya_manila_rank`, or `Ineligible` if the rank is not valid.
+#[no_mangle]
+pub extern "C" fn aya_short_deck_rank_category(rank: u16) -> AyaPokerRankCategory {
+    // Unlike the other rankings, a short-deck rank of 0 would be categorized
+    // as a high card.
+    let best = aya_poker::short_deck_rank(&hand_of(&[51, 47, 43, 39, 35]));
+    match rank != 0 && rank <= best.0 {
+        true => aya_poker::ShortDeckHandRank(rank).rank_category().into(),
+        false => AyaPokerRankCategory::Ineligible,
+    }
+}
+
+/// Returns the category of a rank returned by `aya_ace_five_rank` or
+/// `aya_omaha_lo_rank`, or `Ineligible` if the rank is not valid.
+#[no_mangle]
+pub extern "C" fn aya_ace_five_rank_category(rank: u16) -> AyaPokerRankCategory {
+    let best = aya_poker::ace_five_rank(&hand_of(&[48, 1, 6, 11, 12]));
+    match rank <= best.0 {
+        true => aya_poker::AceFiveHandRank(rank).rank_category().into(),
+        false => AyaPokerRankCategory::Ineligible,
+    }
+}
+
+/// Returns the category of a rank returned by `aya_deuce_seven_rank`, or
+/// `Ineligible` if the rank is not valid.
+#[no_mangle]
+pub extern "C" fn aya_deuce_seven_rank_category(rank: u16) -> AyaPokerRankCategory {
+    let best = aya_poker::deuce_seven_rank(&hand_of(&[20, 13, 10, 7, 0]));
+    match rank <= best.0 {
+        true => aya_poker::DeuceSevenHandRank(rank).rank_category().into(),
+        false => AyaPokerRankCategory::Ineligible,
+    }
+}
+
+/// Returns the category of a rank returned by `aya_badugi_rank`, or
+/// `Invalid` if the rank is not valid.
+#[no_mangle]
+pub extern "C" fn aya_badugi_rank_category(rank: u16) -> AyaBadugiRankCategory {
+    let best = aya_poker::badugi_rank(&hand_of(&[48, 1, 6, 11]));
+    match rank != 0 && rank <= best.0 {
+        true => aya_poker::BadugiHandRank(rank).rank_category().into(),
+        false => AyaBadugiRankCategory::Invalid,
+    }
+}
+
+/// Returns the category of a rank returned by `aya_baduci_rank`, or
+/// `Invalid` if the rank is not valid.
+#[no_mangle]
+pub extern "C" fn aya_baduci_rank_category(rank: u16) -> AyaBadugiRankCategory {
+    let best = aya_poker::baduci_rank(&hand_of(&[0, 5, 10, 15]));
+    match rank != 0 && rank <= best.0 {
+        true => aya_poker::BaduciHandRank(rank).rank_category().into(),
+        false => AyaBadugiRankCategory::Invalid,
+    }
+}
+
+/// The names of the poker rank categories, in the order of their values.
+const POKER_RANK_CATEGORY_NAMES: [&[u8]; 12] = [
+    b"Ineligible\0",
+    b"High Card\0",
+    b"Pair\0",
+    b"Two Pair\0",
+    b"Three of a Kind\0",
+    b"Straight\0",
+    b"Flush\0",
+    b"Full House\0",
+    b"Four of a Kind\0",
+    b"Straight Flush\0",
+    b"Royal Flush\0",
+    b"Five of a Kind\0",
+];
+
+/// The names of the Badugi rank categories, in the order of their values.
+const BADUGI_RANK_CATEGORY_NAMES: [&[u8]; 5] = [
+    b"Invalid\0",
+    b"One Card\0",
+    b"Two Cards\0",
+    b"Three Cards\0",
+    b"Four Cards\0",
+];
+
+fn category_name(names: &[&'static [u8]], category: u32) -> *const c_char {
+    let name = names.get(category as usize).copied();
+    name.unwrap_or(b"Unknown\0").as_ptr() as *const c_char
+}
+
+/// Returns the name of an `AyaPokerRankCategory` as a static null-terminated
+/// string, e.g. `"Full House"`, or `"Unknown"` if the value is not a
+/// category.
+#[no_mangle]
+pub extern "C" fn aya_poker_rank_category_name(category: u32) -> *const c_char {
+    category_name(&POKER_RANK_CATEGORY_NAMES, category)
+}
+
+/// Returns the name of an `AyaBadugiRankCategory` as a static
+/// null-terminated string, e.g. `"Three Cards"`, or `"Unknown"` if the value
+/// is not a category.
+#[no_mangle]
+pub extern "C" fn aya_badugi_rank_category_name(category: u32) -> *const c_char {
+    category_name(&BADUGI_RANK_CATEGORY_NAMES, category)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::ffi::{CStr, CString};
+
+    fn parse(text: &str) -> AyaHand {
+        let mut hand = crate::aya_hand_new();
+        let text = CString::new(text).unwrap();
+        assert_eq!(
+            unsafe { crate::aya_hand_parse(text.as_ptr(), &mut hand) },
+            crate::AyaStatus::Ok
+        );
+        hand
+    }
+
+    #[rstest]
+    #[case("Ks Kd Ac 6s 4c Jc Th", AyaPokerRankCategory::Pair)]
+    #[case("Ah Kh Qh Jh Th", AyaPokerRankCategory::RoyalFlush)]
+    #[case("Ah Kd", AyaPokerRankCategory::HighCard)]
+    fn ranks_poker_hands(#[case] cards: &str, #[case] category: AyaPokerRankCategory) {
+        let rank = aya_poker_rank(parse(cards));
+        assert_eq!(rank, aya_poker::poker_rank(&cards.parse().unwrap()).0);
+        assert_eq!(aya_poker_rank_category(rank), category);
+    }
+
+    #[test]
+    fn ranks_split_hands() {
+        let hand = parse("Ac 2d 3h 4s");
+        let rank = aya_badacey_rank(hand);
+        assert_eq!(rank.badugi, aya_badugi_rank(hand));
+        assert_eq!(rank.ace_five, aya_ace_five_rank(hand));
+        assert_eq!(
+            aya_badugi_rank_category(rank.badugi),
+            AyaBadugiRankCategory::FourCards
+        );
+
+        let rank = aya_badeucy_rank(hand);
+        assert_eq!(rank.baduci, aya_baduci_rank(hand));
+        assert_eq!(rank.deuce_seven, aya_deuce_seven_rank(hand));
+    }
+
+    #[test]
+    fn ranks_hole_and_board() {
+        let hole = parse("Jd 7s 4d 2c");
+        let board = parse("4s 6c Jc 2d Js");
+        assert_eq!(
+            aya_omaha_rank(hole, board),
+            aya_poker::omaha_rank(&hole.to_hand().unwrap(), &board.to_hand().unwrap()).0
+        );
+        assert_eq!(
+            aya_exact_hole_rank(hole, board, 2),
+            aya_omaha_rank(hole, board)
+        );
+        assert_ne!(aya_omaha_lo_rank(hole, board), 0);
+        assert_eq!(aya_exact_hole_rank(hole, board, 6), 0);
+        assert_eq!(aya_omaha_rank(hole, parse("4s 6c")), 0);
+        assert_eq!(aya_omaha_rank(hole, parse("4s 6c Jd")), 0);
+        assert_eq!(aya_omaha_lo_rank(hole, parse("Jd 6c 3s")), 0);
+        assert_eq!(aya_manila_rank(parse("Ah 9d"), parse("Ah 8h 7h")), 0);
+        assert_eq!(
+            aya_short_deck_rank_category(aya_manila_rank(parse("Ah 9d"), parse("Ts 8h 7h Kh Kc"))),
+            AyaPokerRankCategory::Straight
+        );
+    }
+
+    #[test]
+    fn ranks_wild_hands() {
+        let hand = parse("Ah Ad Ac 2s");
+        assert_eq!(
+            aya_poker_rank_category(aya_deuces_wild_rank(hand)),
+            AyaPokerRankCategory::FourOfAKind
+        );
+        assert_eq!(
+            aya_poker_rank_category(aya_joker_rank(parse("Ah Ad Ac As"), 1)),
+            AyaPokerRankCategory::FiveOfAKind
+        );
+        assert_eq!(
+            aya_poker_rank_category(aya_bug_rank(parse("Ah Kh Qh Jh"), 1)),
+            AyaPokerRankCategory::RoyalFlush
+        );
+        assert_eq!(aya_joker_rank(parse("Ah Kh Qh Jh Th 9h"), 2), 0);
+    }
+
+    #[test]
+    fn converts_lowball_ranks() {
+        let nine_high = aya_ace_five_rank(parse("9h 4h 3d 5d 2c"));
+        assert_eq!(aya_ace_five_to_lo_8_rank(nine_high), 0);
+        let six_high = aya_ace_five_rank(parse("6s 5s 4s 3s 2s"));
+        assert_eq!(aya_ace_five_to_lo_8_rank(six_high), six_high);
+
+        let pair = aya_deuce_seven_rank(parse("6h 6c 5c 4s 3d"));
+        assert_eq!(aya_deuce_seven_to_lo_8_rank(pair), 0);
+        assert_eq!(
+            aya_deuce_seven_rank_category(pair),
+            AyaPokerRankCategory::Pair
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_ranks() {
+        let invalid_manila = aya_manila_rank(parse("Ah 9d"), parse("Ah 8h 7h"));
+        assert_eq!(invalid_manila, 0);
+        assert_eq!(
+            aya_short_deck_rank_category(invalid_manila),
+            AyaPokerRankCategory::Ineligible
+        );
+        assert_eq!(
+            aya_poker_rank_category(u16::MAX),
+            AyaPokerRankCategory::Ineligible
+        );
+        assert_eq!(
+            aya_short_deck_rank_category(u16::MAX),
+            AyaPokerRankCategory::Ineligible
+        );
+        assert_eq!(
+            aya_ace_five_rank_category(u16::MAX),
+            AyaPokerRankCategory::Ineligible
+        );
+        assert_eq!(
+            aya_deuce_seven_rank_category(u16::MAX),
+            AyaPokerRankCategory::Ineligible
+        );
+        assert_eq!(
+            aya_badugi_rank_category(u16::MAX),
+            AyaBadugiRankCategory::Invalid
+        );
+        assert_eq!(aya_baduci_rank_category(0), AyaBadugiRankCategory::Invalid);
+
+        let mut hand = crate::aya_hand_new();
+        hand.len = 2;
+        assert_eq!(aya_poker_rank(hand), 0);
+    }
+
+    #[test]
+    fn names_categories() {
+        let name = |name| unsafe { CStr::from_ptr(name) }.to_str().unwrap();
+        let full_house = AyaPokerRankCategory::FullHouse as u32;
+        assert_eq!(name(aya_poker_rank_category_name(full_house)), "Full House");
+        let three_cards = AyaBadugiRankCategory::ThreeCards as u32;
+        assert_eq!(
+            name(aya_badugi_rank_category_name(three_cards)),
+            "Three Cards"
+        );
+        assert_eq!(name(aya_poker_rank_category_name(12)), "Unknown");
+        assert_eq!(name(aya_badugi_rank_category_name(u32::MAX)), "Unknown");
+    }
+}
@@ -2,11 +2,21 @@ use std::{env, fs::File, io::BufWriter, io::Write, path::Path};
 
 use aya_codegen::{
     AceFiveLowballLookup, BaduciLookup, BadugiLookup, DeuceSevenLowballLookup, PokerLookup,
-    SixPlusPokerLookup,
+    PreflopEquityLookup, SixPlusPokerLookup,
 };
 
+const PREFLOP_SEED: u64 = 0x5eed;
+const PREFLOP_BOARDS: usize = 10_000;
+const PREFLOP_SAMPLES: usize = 10_000;
+const PREFLOP_MAX_OPPONENTS: usize = players::MAX_PLAYERS - 1;
+
+#[allow(dead_code)]
+#[path = "src/players.rs"]
+mod players;
+
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/players.rs");
 
     // Standard poker
     let path = Path::new(&env::var("OUT_DIR").unwrap()).join("holdem.rs");
@@ -96,4 +106,28 @@ fn main() {
         flush_phf
     )
     .unwrap();
+
+    // Preflop hold'em equities
+    if env::var_os("CARGO_FEATURE_PREFLOP_TABLES").is_some() {
+        let path = Path::new(&env::var("OUT_DIR").unwrap()).join("preflop.rs");
+        let mut file = BufWriter::new(File::create(path).unwrap());
+
+        let builder = PreflopEquityLookup::new();
+        let heads_up = builder.generate_heads_up_table(PREFLOP_BOARDS, PREFLOP_SEED);
+        let multiway =
+            builder.generate_multiway_table(PREFLOP_MAX_OPPONENTS, PREFLOP_SAMPLES, PREFLOP_SEED);
+
+        writeln!(
+            &mut file,
+            "static HEADS_UP_EQUITIES: [u16; HAND_CLASS_COUNT * HAND_CLASS_COUNT] = {};\n",
+            heads_up
+        )
+        .unwrap();
+        writeln!(
+            &mut file,
+            "static MULTIWAY_EQUITIES: [u16; HAND_CLASS_COUNT * MAX_OPPONENTS] = {};\n",
+            multiway
+        )
+        .unwrap();
+    }
 }
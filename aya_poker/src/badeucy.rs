@@ -0,0 +1,203 @@
+use core::cmp::Ordering;
+
+use aya_base::Hand;
+
+use crate::{
+    ace_five_rank, baduci_rank, badugi_rank, deuce_seven_rank, AceFiveHandRank, BaduciHandRank,
+    BadugiHandRank, DeuceSevenHandRank,
+};
+
+/// The strength ranking of both halves of a hand in Badeucy, i.e. the Baduci
+/// and the deuce-seven lowball rankings.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct BadeucyHandRank {
+    /// The ranking of the best Baduci hand.
+    pub baduci: BaduciHandRank,
+    /// The ranking of the 5-card deuce-seven lowball hand.
+    pub deuce_seven: DeuceSevenHandRank,
+}
+
+/// The strength ranking of both halves of a hand in Badacey, i.e. the Badugi
+/// and the ace-five lowball rankings.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct BadaceyHandRank {
+    /// The ranking of the best Badugi hand.
+    pub badugi: BadugiHandRank,
+    /// The ranking of the 5-card ace-five lowball hand.
+    pub ace_five: AceFiveHandRank,
+}
+
+/// The result of comparing two hands in a split-pot Badugi game, half by
+/// half.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct SplitResult {
+    /// The comparison of the Badugi (or Baduci) halves.
+    pub badugi: Ordering,
+    /// The comparison of the lowball halves.
+    pub lowball: Ordering,
+}
+
+impl SplitResult {
+    /// Returns `true` if the first hand wins both halves of the pot.
+    pub fn is_scoop(&self) -> bool {
+        self.badugi == Ordering::Greater && self.lowball == Ordering::Greater
+    }
+
+    /// Returns the fraction of the pot won by the first hand in a heads-up
+    /// showdown, with tied halves being split evenly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::badeucy_rank;
+    ///
+    /// let player = badeucy_rank(&"2c 3d 4h 6s 7c".parse()?);
+    /// let opponent = badeucy_rank(&"2d 3c 4s 5h 8d".parse()?);
+    /// let result = player.compare(&opponent);
+    /// assert_eq!(result.pot_share(), 0.5);
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn pot_share(&self) -> f64 {
+        let half_share = |ordering: Ordering| match ordering {
+            Ordering::Greater => 0.5,
+            Ordering::Equal => 0.25,
+            Ordering::Less => 0.0,
+        };
+        half_share(self.badugi) + half_share(self.lowball)
+    }
+}
+
+/// Returns the rankings of both halves of a Badeucy hand, i.e. of the best
+/// Baduci hand and the deuce-seven lowball hand that can be made from the
+/// given cards.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{badeucy_rank, BadugiRankCategory, PokerRankCategory};
+///
+/// let rank = badeucy_rank(&"Kc 2d 3h 4s 7d".parse()?);
+/// assert_eq!(rank.baduci.rank_category(), BadugiRankCategory::FourCards);
+/// assert_eq!(rank.deuce_seven.rank_category(), PokerRankCategory::HighCard);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn badeucy_rank(hand: &Hand) -> BadeucyHandRank {
+    BadeucyHandRank {
+        baduci: baduci_rank(hand),
+        deuce_seven: deuce_seven_rank(hand),
+    }
+}
+
+/// Returns the rankings of both halves of a Badacey hand, i.e. of the best
+/// Badugi hand and the ace-five lowball hand that can be made from the given
+/// cards.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{badacey_rank, BadugiRankCategory, PokerRankCategory};
+///
+/// let rank = badacey_rank(&"Ac 2d 3h 5s 5d".parse()?);
+/// assert_eq!(rank.badugi.rank_category(), BadugiRankCategory::FourCards);
+/// assert_eq!(rank.ace_five.rank_category(), PokerRankCategory::Pair);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn badacey_rank(hand: &Hand) -> BadaceyHandRank {
+    BadaceyHandRank {
+        badugi: badugi_rank(hand),
+        ace_five: ace_five_rank(hand),
+    }
+}
+
+impl BadeucyHandRank {
+    /// Compares both halves of the hand against another Badeucy hand.
+    pub fn compare(&self, other: &BadeucyHandRank) -> SplitResult {
+        SplitResult {
+            badugi: self.baduci.cmp(&other.baduci),
+            lowball: self.deuce_seven.cmp(&other.deuce_seven),
+        }
+    }
+}
+
+impl BadaceyHandRank {
+    /// Compares both halves of the hand against another Badacey hand.
+    pub fn compare(&self, other: &BadaceyHandRank) -> SplitResult {
+        SplitResult {
+            badugi: self.badugi.cmp(&other.badugi),
+            lowball: self.ace_five.cmp(&other.ace_five),
+        }
+    }
+}
+
+impl core::fmt::Display for BadeucyHandRank {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} / {}", self.baduci, self.deuce_seven)
+    }
+}
+
+impl core::fmt::Display for BadaceyHandRank {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} / {}", self.badugi, self.ace_five)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::scoop(
+        "2c 3d 4h 5s 7c",
+        "Ac 3h 4d 6s 8h",
+        Ordering::Greater,
+        Ordering::Greater
+    )]
+    #[case::split("2c 3d 4h 6s 7c", "2d 3c 4s 5h 8d", Ordering::Less, Ordering::Greater)]
+    #[case::aces_high("Ac 2d 3h 4s 7c", "Kc 2h 3d 4c 7s", Ordering::Equal, Ordering::Less)]
+    #[case::tie("2c 3d 4h 5s 7c", "2d 3c 4s 5h 7d", Ordering::Equal, Ordering::Equal)]
+    fn badeucy_compare(
+        #[case] hand: &str,
+        #[case] other: &str,
+        #[case] badugi: Ordering,
+        #[case] lowball: Ordering,
+    ) -> Result<(), ParseError> {
+        let rank = badeucy_rank(&hand.parse()?);
+        let other = badeucy_rank(&other.parse()?);
+        assert_eq!(rank.compare(&other), SplitResult { badugi, lowball });
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::scoop(
+        "Ac 2d 3h 4s 5c",
+        "2c 3d 4h 6s 7c",
+        Ordering::Greater,
+        Ordering::Greater
+    )]
+    #[case::split("Ac 2d 3h 4c 5c", "2c 3d 4h 6s 7c", Ordering::Less, Ordering::Greater)]
+    #[case::paired("Ac 2d 3h 4s 4c", "2c 3d 4h 6s 8c", Ordering::Greater, Ordering::Less)]
+    fn badacey_compare(
+        #[case] hand: &str,
+        #[case] other: &str,
+        #[case] badugi: Ordering,
+        #[case] lowball: Ordering,
+    ) -> Result<(), ParseError> {
+        let rank = badacey_rank(&hand.parse()?);
+        let other = badacey_rank(&other.parse()?);
+        assert_eq!(rank.compare(&other), SplitResult { badugi, lowball });
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Ordering::Greater, Ordering::Greater, 1.0)]
+    #[case(Ordering::Greater, Ordering::Equal, 0.75)]
+    #[case(Ordering::Less, Ordering::Greater, 0.5)]
+    #[case(Ordering::Equal, Ordering::Equal, 0.5)]
+    #[case(Ordering::Less, Ordering::Equal, 0.25)]
+    fn pot_share(#[case] badugi: Ordering, #[case] lowball: Ordering, #[case] expected: f64) {
+        let result = SplitResult { badugi, lowball };
+        assert_eq!(result.pot_share(), expected);
+    }
+}
@@ -39,7 +39,8 @@ impl core::fmt::Display for DeuceSevenHandRank {
             }
             PokerRankCategory::Pair
             | PokerRankCategory::ThreeOfAKind
-            | PokerRankCategory::FourOfAKind => {
+            | PokerRankCategory::FourOfAKind
+            | PokerRankCategory::FiveOfAKind => {
                 let r = determinant / 256;
                 write!(f, "{}, {}", rc, PLURAL_RANK_NAMES[12 - r])
             }
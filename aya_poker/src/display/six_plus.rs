@@ -31,7 +31,8 @@ impl core::fmt::Display for ShortDeckHandRank {
             }
             PokerRankCategory::Pair
             | PokerRankCategory::ThreeOfAKind
-            | PokerRankCategory::FourOfAKind => {
+            | PokerRankCategory::FourOfAKind
+            | PokerRankCategory::FiveOfAKind => {
                 let r = determinant / 256;
                 write!(f, "{}, {}", rc, PLURAL_RANK_NAMES[r])
             }
@@ -39,7 +39,8 @@ impl core::fmt::Display for PokerHandRank {
             }
             PokerRankCategory::Pair
             | PokerRankCategory::ThreeOfAKind
-            | PokerRankCategory::FourOfAKind => {
+            | PokerRankCategory::FourOfAKind
+            | PokerRankCategory::FiveOfAKind => {
                 let r = determinant / 256;
                 write!(f, "{}, {}", rc, PLURAL_RANK_NAMES[r])
             }
@@ -65,7 +66,7 @@ impl core::fmt::Display for PokerHandRank {
 
 #[cfg(test)]
 mod tests {
-    use crate::{base::ParseError, poker_rank};
+    use crate::{base::ParseError, joker_rank, poker_rank};
     use rstest::rstest;
 
     #[rstest]
@@ -85,4 +86,15 @@ mod tests {
         assert_eq!(&rank.to_string(), expected);
         Ok(())
     }
+
+    #[rstest]
+    #[case::pair("Jk 8h 6c 4s 3d", "Pair, Eights")]
+    #[case::straight("Jk 2c 3d 4s 5h", "Straight, Six-high")]
+    #[case::five_of_a_kind("Jk 9c 9d 9h 9s", "Five of a Kind, Nines")]
+    fn joker_rank_name(#[case] hand: &str, #[case] expected: &str) -> Result<(), ParseError> {
+        let hand = hand.parse()?;
+        let rank = joker_rank(&hand);
+        assert_eq!(&rank.to_string(), expected);
+        Ok(())
+    }
 }
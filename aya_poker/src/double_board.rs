@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+
+use aya_base::Hand;
+
+/// Returns the share of the pot won by each player at a double-board
+/// showdown.
+///
+/// In double-board games, each of the two boards plays for half of the pot,
+/// which is awarded to the player holding the best hand on that board and
+/// split evenly in case of a tie. The hands are ranked by calling `rank_fn`
+/// with a player's hole cards and one of the boards, so any evaluator taking
+/// the hole and board cards separately, such as [`omaha_rank`], can be used
+/// directly, while the remaining evaluators can be used by combining the
+/// cards first.
+///
+/// The returned shares are in the same order as `holes` and add up to 1,
+/// unless `holes` is empty.
+///
+/// [`omaha_rank`]: crate::omaha_rank
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{base::Hand, double_board_shares, poker_rank};
+///
+/// let holes: [Hand; 3] = ["Ah Ad".parse()?, "Kh Kd".parse()?, "Ac As".parse()?];
+/// let boards = ["Kc 7d 2s 9h 4c".parse()?, "Jc Jd 5h 3s 8c".parse()?];
+/// let shares = double_board_shares(&holes, &boards, |hole, board| {
+///     let mut hand = *hole;
+///     hand.extend(board.iter());
+///     poker_rank(&hand)
+/// });
+/// assert_eq!(shares, [0.25, 0.5, 0.25]);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn double_board_shares<R: Ord>(
+    holes: &[Hand],
+    boards: &[Hand; 2],
+    rank_fn: impl Fn(&Hand, &Hand) -> R,
+) -> Vec<f64> {
+    let mut shares = alloc::vec![0.0; holes.len()];
+
+    for board in boards {
+        let ranks = holes
+            .iter()
+            .map(|hole| rank_fn(hole, board))
+            .collect::<Vec<_>>();
+        let best_rank = match ranks.iter().max() {
+            Some(rank) => rank,
+            None => return shares,
+        };
+
+        let winners = ranks.iter().filter(|&r| r == best_rank).count();
+        for (share, rank) in shares.iter_mut().zip(ranks.iter()) {
+            if rank == best_rank {
+                *share += 0.5 / winners as f64;
+            }
+        }
+    }
+
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base::ParseError, omaha_rank};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::scoop(
+        ["Ah Ad Kc Qd", "7h 7s 2c 3d"],
+        ["As Kd 9h 4c 2s", "Ac Qs 8h 5d 6c"],
+        [1.0, 0.0]
+    )]
+    #[case::split(
+        ["Ah Ad Kc Qd", "7h 7s 2c 3d"],
+        ["As Kd 9h 4c 2s", "7c Qs 8h 5d 6c"],
+        [0.5, 0.5]
+    )]
+    #[case::chopped_board(
+        ["Ah Kd Jc 2d", "As Kh Jd 3c", "7h 7s 2c 3d"],
+        ["Qh Td 9c 4c 4s", "8h 8d 9s 4h 5s"],
+        [0.25, 0.25, 0.5]
+    )]
+    fn double_board_omaha<const N: usize>(
+        #[case] holes: [&str; N],
+        #[case] boards: [&str; 2],
+        #[case] expected: [f64; N],
+    ) -> Result<(), ParseError> {
+        let holes = holes
+            .iter()
+            .map(|h| h.parse())
+            .collect::<Result<Vec<Hand>, _>>()?;
+        let boards = [boards[0].parse()?, boards[1].parse()?];
+
+        let shares = double_board_shares(&holes, &boards, omaha_rank);
+        assert_eq!(shares, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn no_players() -> Result<(), ParseError> {
+        let boards = ["As Kd 9h 4c 2s".parse()?, "Ac Qs 8h 5d 6c".parse()?];
+        let shares = double_board_shares(&[], &boards, omaha_rank);
+        assert!(shares.is_empty());
+        Ok(())
+    }
+}
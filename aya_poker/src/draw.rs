@@ -0,0 +1,590 @@
+//! Draw-outcome analysis for single and triple draw lowball games.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::base::Rank;
+//! use aya_poker::draw::{best_discard, exact_draw_outcomes, Lowball};
+//!
+//! let hand = "7c 5d 3h 2s Kd".parse()?;
+//! let dead = "4c 6s".parse()?;
+//! let outcomes = exact_draw_outcomes(Lowball::DeuceSeven, &hand, &dead);
+//! let best = best_discard(&outcomes, Rank::Nine);
+//! assert_eq!(best.discard, "Kd".parse()?);
+//!
+//! // Of the 45 draws, 6 make a seven, but only the 3 fours make a 7-5-4-3-2.
+//! let distribution = &best.distribution;
+//! assert_eq!(distribution.probability_at_least(&"7c 5d 4h 3s 2d".parse()?), 3.0 / 45.0);
+//! assert_eq!(distribution.probability_at_most(Rank::Seven), 6.0 / 45.0);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use aya_base::{
+    constants::{CARD_COUNT, RANK_COUNT},
+    Card, Hand, Rank, CARDS,
+};
+
+use crate::{
+    ace_five_rank, deck::Deck, deuce_seven_rank, AceFiveHandRank, DeuceSevenHandRank,
+    PokerRankCategory,
+};
+
+/// Rank mask with only the ace bit set.
+const ACE_MASK: u16 = 1 << Rank::Ace as usize;
+
+/// A lowball poker variant played with five-card hands.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Lowball {
+    /// Deuce-to-seven lowball, where aces are high and straights and flushes
+    /// count against the hand.
+    DeuceSeven,
+    /// Ace-to-five lowball, where aces are low and straights and flushes are
+    /// ignored.
+    AceFive,
+}
+
+impl Lowball {
+    /// Returns the rank of a hand, where greater ranks are better.
+    fn rank(self, hand: &Hand) -> u16 {
+        match self {
+            Lowball::DeuceSeven => deuce_seven_rank(hand).0,
+            Lowball::AceFive => ace_five_rank(hand).0,
+        }
+    }
+
+    /// Returns the hand-ranking category of a rank returned by
+    /// [`Lowball::rank`].
+    fn category(self, rank: u16) -> PokerRankCategory {
+        match self {
+            Lowball::DeuceSeven => DeuceSevenHandRank(rank).rank_category(),
+            Lowball::AceFive => AceFiveHandRank(rank).rank_category(),
+        }
+    }
+
+    /// Returns the highest card of a made (unpaired) lowball hand, or `None`
+    /// if the hand is not a made hand.
+    fn high_card(self, hand: &Hand) -> Option<usize> {
+        let category = self.category(self.rank(hand));
+        if category != PokerRankCategory::HighCard || hand.len() < 5 {
+            return None;
+        }
+
+        let mut rank_mask = hand.rank_mask();
+        if self == Lowball::AceFive {
+            rank_mask &= !ACE_MASK;
+        }
+        Some(15 - rank_mask.leading_zeros() as usize)
+    }
+
+    /// Returns `true` if a card of the given rank plays no worse than
+    /// `target` in a lowball hand.
+    fn is_at_most(self, rank: usize, target: Rank) -> bool {
+        rank <= target as usize || (self == Lowball::AceFive && rank == Rank::Ace as usize)
+    }
+}
+
+/// An error which can be returned when sampling the outcomes of draws.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DrawError {
+    /// A runout needed more replacement cards than were left in the deck.
+    NotEnoughCards,
+}
+
+impl fmt::Display for DrawError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrawError::NotEnoughCards => write!(f, "not enough cards left in the deck"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DrawError {}
+
+/// The distribution of the final hands reached from a single starting hand.
+///
+/// Final hands are counted by their full lowball rank, so that e.g. a
+/// 7-5-4-3-2 and a 7-6-5-4-3 are told apart. Made hands, i.e. hands that are
+/// not paired (or for deuce-to-seven, do not make a straight or a flush), can
+/// also be counted by their highest card.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct DrawDistribution {
+    game: Lowball,
+    ranks: BTreeMap<u16, u64>,
+    made: [u64; RANK_COUNT],
+    unmade: u64,
+}
+
+impl DrawDistribution {
+    fn new(game: Lowball) -> DrawDistribution {
+        DrawDistribution {
+            game,
+            ranks: BTreeMap::new(),
+            made: [0; RANK_COUNT],
+            unmade: 0,
+        }
+    }
+
+    /// Returns the total number of final hands counted.
+    pub fn total(&self) -> u64 {
+        self.made.iter().sum::<u64>() + self.unmade
+    }
+
+    /// Returns the ranks of the final hands, best first, with the number of
+    /// final hands of each rank.
+    ///
+    /// The ranks are those returned by [`deuce_seven_rank`] or
+    /// [`ace_five_rank`], depending on the game.
+    pub fn ranks(&self) -> impl Iterator<Item = (u16, u64)> + '_ {
+        self.ranks.iter().rev().map(|(&rank, &count)| (rank, count))
+    }
+
+    /// Returns the number of final hands ranked the same as `hand`.
+    pub fn count(&self, hand: &Hand) -> u64 {
+        let rank = self.game.rank(hand);
+        self.ranks.get(&rank).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of final hands of the given hand-ranking category,
+    /// e.g. the number of paired hands.
+    pub fn category_count(&self, category: PokerRankCategory) -> u64 {
+        self.ranks()
+            .filter(|&(rank, _)| self.game.category(rank) == category)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Returns the number of final hands which are made hands with the given
+    /// highest card.
+    pub fn made(&self, high_card: Rank) -> u64 {
+        self.made[high_card as usize]
+    }
+
+    /// Returns the number of final hands which are not made hands.
+    pub fn unmade(&self) -> u64 {
+        self.unmade
+    }
+
+    /// Returns the probability of finishing with a made hand with the given
+    /// highest card, or 0 if no final hands were counted.
+    pub fn probability(&self, high_card: Rank) -> f64 {
+        self.fraction(self.made(high_card))
+    }
+
+    /// Returns the probability of finishing with a made hand whose highest
+    /// card is no worse than `high_card`, e.g. an eight-or-better, or 0 if
+    /// no final hands were counted.
+    pub fn probability_at_most(&self, high_card: Rank) -> f64 {
+        let made = self.made[..=(high_card as usize)].iter().sum::<u64>();
+        self.fraction(made)
+    }
+
+    /// Returns the probability of finishing without a made hand, or 0 if no
+    /// final hands were counted.
+    pub fn probability_unmade(&self) -> f64 {
+        self.fraction(self.unmade)
+    }
+
+    /// Returns the probability of finishing with a hand ranked no worse than
+    /// `hand`, or 0 if no final hands were counted.
+    pub fn probability_at_least(&self, hand: &Hand) -> f64 {
+        let rank = self.game.rank(hand);
+        self.fraction(self.ranks.range(rank..).map(|(_, &count)| count).sum())
+    }
+
+    fn fraction(&self, count: u64) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => count as f64 / total as f64,
+        }
+    }
+
+    fn insert(&mut self, hand: &Hand) {
+        *self.ranks.entry(self.game.rank(hand)).or_insert(0) += 1;
+        match self.game.high_card(hand) {
+            Some(r) => self.made[r] += 1,
+            None => self.unmade += 1,
+        }
+    }
+}
+
+/// The final hand distribution of replacing some of the cards of a hand.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct DiscardOption {
+    /// The cards thrown away from the starting hand.
+    pub discard: Hand,
+    /// The distribution of the final hands.
+    pub distribution: DrawDistribution,
+}
+
+/// Returns the exact final hand distribution of each of the 32 ways to
+/// discard cards from a 5-card hand with one draw remaining.
+///
+/// The `i`-th entry corresponds to discarding the cards whose positions in
+/// `hand.iter()` are the set bits of `i`, i.e. the first entry stands pat and
+/// the last entry draws five new cards. The replacement cards are drawn from
+/// the cards that are neither in `hand` nor in `dead`, and every possible
+/// draw is enumerated.
+///
+/// # Panics
+///
+/// Panics if `hand` does not contain exactly 5 cards.
+pub fn exact_draw_outcomes(game: Lowball, hand: &Hand, dead: &Hand) -> [DiscardOption; 32] {
+    let (cards, deck, deck_len) = prepare(hand, dead);
+
+    let mut outcomes = empty_outcomes(game);
+    for (discard_bits, outcome) in outcomes.iter_mut().enumerate() {
+        let (held, discard) = split(&cards, discard_bits);
+        outcome.discard = discard;
+        enumerate_draws(
+            held,
+            &deck[..deck_len],
+            discard.len(),
+            &mut outcome.distribution,
+        );
+    }
+
+    outcomes
+}
+
+/// Returns the sampled final hand distribution of each of the 32 ways to
+/// discard cards from a 5-card hand with `draws` draws remaining.
+///
+/// The entries are ordered as in [`exact_draw_outcomes`]. For each discard
+/// option, `samples` runouts are simulated from a deck seeded with `seed`.
+/// On each of the later draws, the player stands pat with a made hand no
+/// worse than `target`-high, and otherwise keeps one card of each rank no
+/// worse than `target` and discards the rest.
+///
+/// If `draws` is 0, no cards can be replaced and every distribution is
+/// empty. Otherwise, every distribution counts exactly `samples` final hands.
+///
+/// Returns an error if a runout needs more replacement cards than are left
+/// in the deck, as can happen with many draws and dead cards.
+///
+/// # Panics
+///
+/// Panics if `hand` does not contain exactly 5 cards.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::base::Rank;
+/// use aya_poker::draw::{sampled_draw_outcomes, Lowball};
+///
+/// let hand = "8c 6d 4h 3s 3d".parse()?;
+/// let outcomes = sampled_draw_outcomes(
+///     Lowball::DeuceSeven,
+///     &hand,
+///     &"".parse()?,
+///     3,
+///     Rank::Eight,
+///     1_000,
+///     42,
+/// )
+/// .unwrap();
+/// assert!(outcomes.iter().all(|o| o.distribution.total() == 1_000));
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn sampled_draw_outcomes(
+    game: Lowball,
+    hand: &Hand,
+    dead: &Hand,
+    draws: usize,
+    target: Rank,
+    samples: usize,
+    seed: u64,
+) -> Result<[DiscardOption; 32], DrawError> {
+    let (cards, deck_cards, deck_len) = prepare(hand, dead);
+    let mut deck = Deck::with_seed(deck_cards[..deck_len].iter(), seed);
+
+    let mut outcomes = empty_outcomes(game);
+    for (discard_bits, outcome) in outcomes.iter_mut().enumerate() {
+        let (held, discard) = split(&cards, discard_bits);
+        outcome.discard = discard;
+        if draws == 0 {
+            continue;
+        }
+
+        for _ in 0..samples {
+            let hand = sample_runout(game, &mut deck, held, discard.len(), draws, target)
+                .ok_or(DrawError::NotEnoughCards)?;
+            outcome.distribution.insert(&hand);
+        }
+    }
+
+    Ok(outcomes)
+}
+
+/// Returns the discard option with the greatest probability of finishing
+/// with a made hand no worse than `target`-high.
+///
+/// If several options are equally good, the one discarding the fewest cards
+/// is returned.
+pub fn best_discard(outcomes: &[DiscardOption], target: Rank) -> &DiscardOption {
+    let mut best = &outcomes[0];
+    for outcome in outcomes {
+        let p = outcome.distribution.probability_at_most(target);
+        let best_p = best.distribution.probability_at_most(target);
+        if p > best_p || (p == best_p && outcome.discard.len() < best.discard.len()) {
+            best = outcome;
+        }
+    }
+    best
+}
+
+/// Returns the final hand of a single sampled runout, or `None` if the deck
+/// runs out of cards.
+fn sample_runout(
+    game: Lowball,
+    deck: &mut Deck,
+    held: Hand,
+    discards: usize,
+    draws: usize,
+    target: Rank,
+) -> Option<Hand> {
+    deck.reset();
+    let mut current = held;
+    current.extend(deal(deck, discards)?);
+
+    for _ in 1..draws {
+        let kept = redraw(game, &current, target);
+        let replaced = current.len() - kept.len();
+        current = kept;
+        current.extend(deal(deck, replaced)?);
+    }
+
+    Some(current)
+}
+
+/// Deals the given number of cards, or returns `None` if fewer are left in
+/// the deck.
+fn deal(deck: &mut Deck, count: usize) -> Option<&[Card]> {
+    if count > deck.len() {
+        return None;
+    }
+    deck.deal(count)
+}
+
+/// Returns the cards kept by the simple drawing strategy used for the later
+/// draws of a sampled runout.
+fn redraw(game: Lowball, hand: &Hand, target: Rank) -> Hand {
+    if let Some(r) = game.high_card(hand) {
+        if game.is_at_most(r, target) {
+            return *hand;
+        }
+    }
+
+    let mut kept = Hand::new();
+    for card in hand.iter() {
+        let r = card.rank() as usize;
+        if game.is_at_most(r, target) && kept.rank_mask() & (1 << r) == 0 {
+            kept.insert_unchecked(card);
+        }
+    }
+    kept
+}
+
+fn enumerate_draws(held: Hand, cards: &[Card], draws: usize, distribution: &mut DrawDistribution) {
+    if draws == 0 {
+        distribution.insert(&held);
+        return;
+    }
+    if cards.len() < draws {
+        return;
+    }
+
+    for i in 0..=(cards.len() - draws) {
+        let mut hand = held;
+        hand.insert_unchecked(&cards[i]);
+        enumerate_draws(hand, &cards[(i + 1)..], draws - 1, distribution);
+    }
+}
+
+fn prepare(hand: &Hand, dead: &Hand) -> ([Card; 5], [Card; CARD_COUNT], usize) {
+    assert_eq!(hand.len(), 5, "hand must contain exactly 5 cards");
+
+    let mut cards = [CARDS[0]; 5];
+    for (i, &card) in hand.iter().enumerate() {
+        cards[i] = card;
+    }
+
+    let mut deck = [CARDS[0]; CARD_COUNT];
+    let mut deck_len = 0;
+    for &card in CARDS.iter() {
+        if !hand.contains(&card) && !dead.contains(&card) {
+            deck[deck_len] = card;
+            deck_len += 1;
+        }
+    }
+
+    (cards, deck, deck_len)
+}
+
+fn split(cards: &[Card; 5], discard_bits: usize) -> (Hand, Hand) {
+    let mut held = Hand::new();
+    let mut discard = Hand::new();
+    for (i, card) in cards.iter().enumerate() {
+        if discard_bits & (1 << i) != 0 {
+            discard.insert_unchecked(card);
+        } else {
+            held.insert_unchecked(card);
+        }
+    }
+    (held, discard)
+}
+
+fn empty_outcomes(game: Lowball) -> [DiscardOption; 32] {
+    core::array::from_fn(|_| DiscardOption {
+        discard: Hand::new(),
+        distribution: DrawDistribution::new(game),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+
+    #[test]
+    fn pat_hand() -> Result<(), ParseError> {
+        let hand = "7c 5d 4h 3s 2d".parse()?;
+        let outcomes = exact_draw_outcomes(Lowball::DeuceSeven, &hand, &Hand::new());
+
+        assert_eq!(outcomes[0].discard, Hand::new());
+        assert_eq!(outcomes[0].distribution.total(), 1);
+        assert_eq!(outcomes[0].distribution.made(Rank::Seven), 1);
+        assert_eq!(outcomes[31].discard, hand);
+        assert_eq!(outcomes[31].distribution.total(), 1_533_939);
+        Ok(())
+    }
+
+    #[test]
+    fn one_card_draw() -> Result<(), ParseError> {
+        let hand = "7c 5d 3h 2s Kd".parse()?;
+        let dead = "4c 6s".parse()?;
+        let outcomes = exact_draw_outcomes(Lowball::DeuceSeven, &hand, &dead);
+
+        // Discarding the king leaves 45 cards, of which the three remaining
+        // fours and the three remaining sixes make a seven, while the eights
+        // and nines make an eight or a nine.
+        let best = best_discard(&outcomes, Rank::Nine);
+        assert_eq!(best.discard, "Kd".parse()?);
+        assert_eq!(best.distribution.total(), 45);
+        assert_eq!(best.distribution.made(Rank::Seven), 6);
+        assert_eq!(best.distribution.made(Rank::Eight), 4);
+        assert_eq!(best.distribution.made(Rank::Nine), 4);
+        assert_eq!(
+            best.distribution.probability_at_most(Rank::Nine),
+            14.0 / 45.0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn full_rank_distribution() -> Result<(), ParseError> {
+        let hand = "7c 5d 3h 2s Kd".parse()?;
+        let dead = "4c 6s".parse()?;
+        let outcomes = exact_draw_outcomes(Lowball::DeuceSeven, &hand, &dead);
+        let best = best_discard(&outcomes, Rank::Nine);
+        let distribution = &best.distribution;
+
+        // The sevens split into 7-5-4-3-2 and the worse 7-6-5-3-2.
+        assert_eq!(distribution.count(&"7c 5d 4h 3s 2d".parse()?), 3);
+        assert_eq!(distribution.count(&"7c 6d 5h 3s 2d".parse()?), 3);
+        assert_eq!(distribution.count(&"7c 6d 5h 4s 3d".parse()?), 0);
+        assert_eq!(
+            distribution.probability_at_least(&"7c 5d 4h 3s 2d".parse()?),
+            3.0 / 45.0
+        );
+
+        // Any of the 12 cards pairing one of the four kept cards.
+        assert_eq!(distribution.category_count(PokerRankCategory::Pair), 12);
+        assert_eq!(
+            distribution.ranks().map(|(_, count)| count).sum::<u64>(),
+            45
+        );
+        assert!(distribution
+            .ranks()
+            .zip(distribution.ranks().skip(1))
+            .all(|(a, b)| a.0 > b.0));
+        Ok(())
+    }
+
+    #[test]
+    fn ace_five_wheel_draw() -> Result<(), ParseError> {
+        let hand = "Ac 2d 3h 4s Kd".parse()?;
+        let outcomes = exact_draw_outcomes(Lowball::AceFive, &hand, &Hand::new());
+        let best = best_discard(&outcomes, Rank::Five);
+
+        assert_eq!(best.discard, "Kd".parse()?);
+        assert_eq!(best.distribution.made(Rank::Five), 4);
+        assert_eq!(best.distribution.made(Rank::Six), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn sampled_triple_draw() -> Result<(), ParseError> {
+        let hand = "8c 6d 4h 3s 3d".parse()?;
+        let dead = Hand::new();
+        let single =
+            sampled_draw_outcomes(Lowball::DeuceSeven, &hand, &dead, 1, Rank::Eight, 500, 7)
+                .unwrap();
+        let triple =
+            sampled_draw_outcomes(Lowball::DeuceSeven, &hand, &dead, 3, Rank::Eight, 500, 7)
+                .unwrap();
+
+        // Drawing one card to 8-6-4-3 is better with more draws remaining.
+        let discard = "3d".parse::<Hand>()?;
+        let single = single.iter().find(|o| o.discard == discard).unwrap();
+        let triple = triple.iter().find(|o| o.discard == discard).unwrap();
+        assert_eq!(triple.distribution.total(), 500);
+        assert!(
+            triple.distribution.probability_at_most(Rank::Eight)
+                > single.distribution.probability_at_most(Rank::Eight)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn no_draws_remaining() -> Result<(), ParseError> {
+        let hand = "8c 6d 4h 3s 3d".parse()?;
+        let outcomes = sampled_draw_outcomes(
+            Lowball::DeuceSeven,
+            &hand,
+            &Hand::new(),
+            0,
+            Rank::Eight,
+            100,
+            7,
+        )
+        .unwrap();
+
+        assert_eq!(outcomes[31].discard, hand);
+        assert!(outcomes.iter().all(|o| o.distribution.total() == 0));
+        assert_eq!(
+            outcomes[0].distribution.probability_at_most(Rank::Eight),
+            0.0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn too_few_cards_left() -> Result<(), ParseError> {
+        // Without a deuce to keep, every draw replaces at least four cards,
+        // which needs more than the 40 cards left in the deck over ten draws.
+        let hand = "8c 6d 4h 3s 3d".parse()?;
+        let dead = "2c 2d 2h 2s Kc Kd Kh".parse()?;
+        assert_eq!(
+            sampled_draw_outcomes(Lowball::DeuceSeven, &hand, &dead, 10, Rank::Two, 100, 7),
+            Err(DrawError::NotEnoughCards)
+        );
+        let outcomes =
+            sampled_draw_outcomes(Lowball::DeuceSeven, &hand, &dead, 2, Rank::Two, 100, 7).unwrap();
+        assert!(outcomes.iter().all(|o| o.distribution.total() == 100));
+        Ok(())
+    }
+}
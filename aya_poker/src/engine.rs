@@ -0,0 +1,936 @@
+//! A deterministic state machine for dealing and betting hands of hold'em
+//! style games.
+//!
+//! A [`HandState`] posts the antes and blinds, deals the hole and board cards
+//! from a deck shuffled by the given seed, validates the actions of the
+//! players through every betting round of a fixed-limit, pot-limit or
+//! no-limit game, and finally resolves the showdown with the evaluator of the
+//! game. Replaying the same actions on a hand created from the same seed
+//! always results in the same hand.
+//!
+//! Players are given in order of position, starting from the first player to
+//! the left of the button, so that the last player holds the button. With
+//! three or more players, the first two players post the small and big
+//! blinds, while heads-up the button posts the small blind.
+//!
+//! Every game of the [`registry`](crate::registry) which deals all of the
+//! hole cards before the first betting round and has no draws is supported,
+//! i.e. Texas hold'em, short-deck hold'em, Manila and the Omaha games.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::engine::{Action, BettingStructure, HandState, TableConfig};
+//! use aya_poker::registry::find_game;
+//!
+//! let game = find_game("nlhe").unwrap();
+//! let config = TableConfig::new(BettingStructure::NoLimit, 5, 10);
+//! let mut hand = HandState::new(game, config, &[1000, 1000], 42).unwrap();
+//!
+//! // Heads-up, the button completes the small blind and the big blind checks.
+//! assert_eq!(hand.to_act(), Some(1));
+//! hand.act(Action::Call).unwrap();
+//! hand.act(Action::Check).unwrap();
+//! assert_eq!(hand.board().len(), 3);
+//!
+//! // The big blind bets the flop and the button folds.
+//! hand.act(Action::Bet(20)).unwrap();
+//! hand.act(Action::Fold).unwrap();
+//! assert!(hand.is_complete());
+//! assert_eq!(hand.seats()[0].stack, 1010);
+//! assert_eq!(hand.seats()[1].stack, 990);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use aya_base::{Deck, Hand};
+
+use crate::{
+    registry::{GameDef, GameRank, HandValue},
+    showdown::{resolve_showdown, Player, Showdown},
+};
+
+/// The maximum number of bets and raises in a betting round of a fixed-limit
+/// game.
+const FIXED_LIMIT_CAP: usize = 4;
+
+/// The limits on the sizes of bets and raises.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum BettingStructure {
+    /// Bets and raises are of a fixed size, equal to the big blind on the
+    /// first half of the streets and twice the big blind afterwards, and
+    /// each betting round is capped at a bet and three raises.
+    FixedLimit,
+    /// Bets and raises may be up to the size of the pot.
+    PotLimit,
+    /// Bets and raises may be up to the whole stack of the player.
+    NoLimit,
+}
+
+impl fmt::Display for BettingStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BettingStructure::FixedLimit => "Fixed Limit",
+            BettingStructure::PotLimit => "Pot Limit",
+            BettingStructure::NoLimit => "No Limit",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The betting structure and forced bets of a hand.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct TableConfig {
+    /// The limits on the sizes of bets and raises.
+    pub structure: BettingStructure,
+    /// The small blind, posted by the first player to the left of the
+    /// button, or by the button heads-up.
+    pub small_blind: u64,
+    /// The big blind, which is also the minimum bet.
+    pub big_blind: u64,
+    /// The ante posted by every player, which does not count towards their
+    /// bets.
+    pub ante: u64,
+}
+
+impl TableConfig {
+    /// Creates a configuration with the given blinds and no ante.
+    pub fn new(structure: BettingStructure, small_blind: u64, big_blind: u64) -> TableConfig {
+        TableConfig {
+            structure,
+            small_blind,
+            big_blind,
+            ante: 0,
+        }
+    }
+
+    /// Returns the configuration with every player posting the given ante.
+    pub fn with_ante(mut self, ante: u64) -> TableConfig {
+        self.ante = ante;
+        self
+    }
+}
+
+/// An action taken by a player in a betting round.
+///
+/// The amounts of bets and raises are the total number of chips the player
+/// has put in during the betting round after the action, including any
+/// blind.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Action {
+    /// Give up the hand.
+    Fold,
+    /// Pass when there is no bet to call.
+    Check,
+    /// Match the current bet, or put in the whole stack if it is smaller.
+    Call,
+    /// Make the first bet of the betting round.
+    Bet(u64),
+    /// Raise the current bet.
+    Raise(u64),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Fold => write!(f, "Fold"),
+            Action::Check => write!(f, "Check"),
+            Action::Call => write!(f, "Call"),
+            Action::Bet(amount) => write!(f, "Bet {}", amount),
+            Action::Raise(amount) => write!(f, "Raise to {}", amount),
+        }
+    }
+}
+
+/// The actions available to the player to act.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct LegalActions {
+    /// Whether the player may check.
+    pub check: bool,
+    /// The number of chips needed to call, if there is a bet to call.
+    pub call: Option<u64>,
+    /// The allowed amounts of a bet, if the player may bet.
+    pub bet: Option<RangeInclusive<u64>>,
+    /// The allowed amounts of a raise, if the player may raise.
+    pub raise: Option<RangeInclusive<u64>>,
+}
+
+/// A step in the progress of a hand.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Event {
+    /// A player posted an ante.
+    Ante { seat: usize, amount: u64 },
+    /// A player posted the small blind.
+    SmallBlind { seat: usize, amount: u64 },
+    /// A player posted the big blind.
+    BigBlind { seat: usize, amount: u64 },
+    /// A new street began after the given cards were dealt to the board.
+    Street { street: usize, cards: Hand },
+    /// A player acted, putting the given number of chips in the pot.
+    Action {
+        seat: usize,
+        action: Action,
+        amount: u64,
+        all_in: bool,
+    },
+}
+
+/// A player at the table.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct Seat {
+    /// The chips the player has behind.
+    pub stack: u64,
+    /// The hole cards of the player.
+    pub hole: Hand,
+    /// The chips the player has put in during the current betting round.
+    pub street_bet: u64,
+    /// The chips the player has put in the pot during the whole hand.
+    pub contribution: u64,
+    /// Whether the player folded.
+    pub folded: bool,
+    acted: bool,
+}
+
+impl Seat {
+    /// Returns `true` if the player has put their whole stack in the pot.
+    pub fn is_all_in(&self) -> bool {
+        !self.folded && self.stack == 0
+    }
+}
+
+/// An error which can be returned when starting or playing a hand.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EngineError {
+    /// The game has draws or deals hole cards after the first betting round.
+    UnsupportedGame,
+    /// There are fewer than two players, or not enough cards to deal to all
+    /// of them.
+    InvalidPlayerCount,
+    /// The big blind is zero or smaller than the small blind.
+    InvalidStakes,
+    /// A player has no chips.
+    EmptyStack,
+    /// The hand is complete, so no more actions can be taken.
+    HandComplete,
+    /// The action is not available to the player to act.
+    IllegalAction,
+    /// The amount of the bet or raise is not allowed.
+    InvalidAmount,
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::UnsupportedGame => write!(f, "game is not supported"),
+            EngineError::InvalidPlayerCount => write!(f, "invalid number of players"),
+            EngineError::InvalidStakes => write!(f, "invalid blinds"),
+            EngineError::EmptyStack => write!(f, "player has no chips"),
+            EngineError::HandComplete => write!(f, "hand is complete"),
+            EngineError::IllegalAction => write!(f, "action is not allowed"),
+            EngineError::InvalidAmount => write!(f, "invalid bet amount"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EngineError {}
+
+/// The state of a hand in progress.
+pub struct HandState {
+    game: &'static GameDef,
+    config: TableConfig,
+    deck: Deck,
+    seats: Vec<Seat>,
+    board: Hand,
+    street: usize,
+    bet: u64,
+    min_raise: u64,
+    bets: usize,
+    to_act: Option<usize>,
+    history: Vec<Event>,
+    result: Option<Showdown>,
+}
+
+impl HandState {
+    /// Starts a hand of the game between players with the given stacks,
+    /// dealing from a deck shuffled by the seed.
+    ///
+    /// The antes and blinds are posted and the cards of the first street are
+    /// dealt, so that the hand is ready for the first action. Players who
+    /// cannot cover their forced bets are all-in.
+    ///
+    /// Returns an error if the game is not supported, if there are fewer
+    /// than two players or too many to deal to, if the blinds are invalid or
+    /// if any player has no chips.
+    pub fn new(
+        game: &'static GameDef,
+        config: TableConfig,
+        stacks: &[u64],
+        seed: u64,
+    ) -> Result<HandState, EngineError> {
+        let (first, rest) = game
+            .streets
+            .split_first()
+            .ok_or(EngineError::UnsupportedGame)?;
+        if first.draw || rest.iter().any(|s| s.draw || s.hole_cards > 0) {
+            return Err(EngineError::UnsupportedGame);
+        }
+        if config.big_blind == 0 || config.small_blind > config.big_blind {
+            return Err(EngineError::InvalidStakes);
+        }
+        let n = stacks.len();
+        if n < 2 || n * game.hole_cards() + game.board_cards() > game.deck.card_count() {
+            return Err(EngineError::InvalidPlayerCount);
+        }
+        if stacks.contains(&0) {
+            return Err(EngineError::EmptyStack);
+        }
+
+        let seats = stacks
+            .iter()
+            .map(|&stack| Seat {
+                stack,
+                hole: Hand::new(),
+                street_bet: 0,
+                contribution: 0,
+                folded: false,
+                acted: false,
+            })
+            .collect();
+        let mut state = HandState {
+            game,
+            config,
+            deck: game.deck.deck(seed),
+            seats,
+            board: Hand::new(),
+            street: 0,
+            bet: config.big_blind,
+            min_raise: config.big_blind,
+            bets: 1,
+            to_act: None,
+            history: Vec::new(),
+            result: None,
+        };
+
+        if config.ante > 0 {
+            for seat in 0..n {
+                let amount = state.post(seat, config.ante, false);
+                state.history.push(Event::Ante { seat, amount });
+            }
+        }
+        let (small_blind, big_blind) = if n == 2 { (1, 0) } else { (0, 1) };
+        if config.small_blind > 0 {
+            let amount = state.post(small_blind, config.small_blind, true);
+            state.history.push(Event::SmallBlind {
+                seat: small_blind,
+                amount,
+            });
+        }
+        let amount = state.post(big_blind, config.big_blind, true);
+        state.history.push(Event::BigBlind {
+            seat: big_blind,
+            amount,
+        });
+
+        state.deal_street();
+        state.advance((big_blind + 1) % n);
+        Ok(state)
+    }
+
+    /// Returns the rules of the game being played.
+    pub fn game(&self) -> &'static GameDef {
+        self.game
+    }
+
+    /// Returns the betting structure and forced bets of the hand.
+    pub fn config(&self) -> &TableConfig {
+        &self.config
+    }
+
+    /// Returns the players, in order of position.
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    /// Returns the cards dealt to the board.
+    pub fn board(&self) -> Hand {
+        self.board
+    }
+
+    /// Returns the index of the current street among the streets of the
+    /// game.
+    pub fn street(&self) -> usize {
+        self.street
+    }
+
+    /// Returns the total number of chips in the pot, including the bets of
+    /// the current betting round.
+    pub fn pot(&self) -> u64 {
+        self.seats.iter().map(|s| s.contribution).sum()
+    }
+
+    /// Returns the amount of the largest bet of the current betting round.
+    pub fn current_bet(&self) -> u64 {
+        self.bet
+    }
+
+    /// Returns the index of the player to act, or `None` if the hand is
+    /// complete.
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    /// Returns `true` if the hand is complete and the pot has been awarded.
+    pub fn is_complete(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// Returns the forced bets, dealt streets and actions of the hand so far.
+    pub fn history(&self) -> &[Event] {
+        &self.history
+    }
+
+    /// Returns how the pot was awarded, once the hand is complete.
+    ///
+    /// If every player but one folded, the remaining player is awarded the
+    /// whole pot without a showdown.
+    pub fn result(&self) -> Option<&Showdown> {
+        self.result.as_ref()
+    }
+
+    /// Returns the actions available to the player to act, or `None` if the
+    /// hand is complete.
+    ///
+    /// Folding is always allowed. A player whose whole stack is not enough
+    /// for the minimum bet or raise may still bet or raise all-in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::engine::{BettingStructure, HandState, TableConfig};
+    /// use aya_poker::registry::find_game;
+    ///
+    /// let game = find_game("plo").unwrap();
+    /// let config = TableConfig::new(BettingStructure::PotLimit, 5, 10);
+    /// let hand = HandState::new(game, config, &[1000, 1000, 1000], 7).unwrap();
+    ///
+    /// // The button may call 10, or raise the pot of 25 after calling.
+    /// let legal = hand.legal_actions().unwrap();
+    /// assert_eq!(legal.call, Some(10));
+    /// assert_eq!(legal.raise, Some(20..=35));
+    /// ```
+    pub fn legal_actions(&self) -> Option<LegalActions> {
+        self.to_act.map(|seat| self.legal_actions_for(seat))
+    }
+
+    /// Takes the action for the player to act.
+    ///
+    /// Once a betting round is complete, the cards of the next street are
+    /// dealt, and once the last betting round is complete, or when no more
+    /// betting is possible, the remaining board is dealt and the pot is
+    /// awarded.
+    ///
+    /// Returns an error if the hand is complete, if the action is not
+    /// available or if the amount of a bet or raise is not allowed, in which
+    /// case the state of the hand is unchanged.
+    pub fn act(&mut self, action: Action) -> Result<(), EngineError> {
+        let seat = self.to_act.ok_or(EngineError::HandComplete)?;
+        let legal = self.legal_actions_for(seat);
+
+        let amount = match action {
+            Action::Fold => {
+                self.seats[seat].folded = true;
+                0
+            }
+            Action::Check => {
+                if !legal.check {
+                    return Err(EngineError::IllegalAction);
+                }
+                0
+            }
+            Action::Call => {
+                let amount = legal.call.ok_or(EngineError::IllegalAction)?;
+                self.post(seat, amount, true)
+            }
+            Action::Bet(to) | Action::Raise(to) => {
+                let allowed = match action {
+                    Action::Bet(_) => legal.bet,
+                    _ => legal.raise,
+                };
+                let allowed = allowed.ok_or(EngineError::IllegalAction)?;
+                if !allowed.contains(&to) {
+                    return Err(EngineError::InvalidAmount);
+                }
+                self.raise_to(seat, to)
+            }
+        };
+
+        let player = &mut self.seats[seat];
+        player.acted = true;
+        self.history.push(Event::Action {
+            seat,
+            action,
+            amount,
+            all_in: amount > 0 && player.stack == 0,
+        });
+        self.advance(seat + 1);
+        Ok(())
+    }
+
+    fn legal_actions_for(&self, seat: usize) -> LegalActions {
+        let player = &self.seats[seat];
+        let check = player.street_bet >= self.bet;
+        let call = (!check).then_some((self.bet - player.street_bet).min(player.stack));
+
+        let all_in = player.street_bet + player.stack;
+        let capped =
+            self.config.structure == BettingStructure::FixedLimit && self.bets >= FIXED_LIMIT_CAP;
+        let sizes = if !player.acted && !capped && all_in > self.bet && self.others_can_act(seat) {
+            let min = self.bet + self.min_raise;
+            let max = match self.config.structure {
+                BettingStructure::FixedLimit => min,
+                BettingStructure::PotLimit => 2 * self.bet - player.street_bet + self.pot(),
+                BettingStructure::NoLimit => all_in,
+            };
+            Some(min.min(all_in)..=max.min(all_in))
+        } else {
+            None
+        };
+
+        let (bet, raise) = if self.bet == 0 {
+            (sizes, None)
+        } else {
+            (None, sizes)
+        };
+        LegalActions {
+            check,
+            call,
+            bet,
+            raise,
+        }
+    }
+
+    /// Moves chips from the stack of the player to the pot, returning the
+    /// number of chips moved.
+    fn post(&mut self, seat: usize, amount: u64, live: bool) -> u64 {
+        let player = &mut self.seats[seat];
+        let amount = amount.min(player.stack);
+        player.stack -= amount;
+        player.contribution += amount;
+        if live {
+            player.street_bet += amount;
+        }
+        amount
+    }
+
+    fn raise_to(&mut self, seat: usize, to: u64) -> u64 {
+        let amount = self.post(seat, to - self.seats[seat].street_bet, true);
+        // Only a full raise reopens the betting for players who have acted.
+        let increase = to - self.bet;
+        if increase >= self.min_raise {
+            self.min_raise = increase;
+            self.bets += 1;
+            for player in self.seats.iter_mut() {
+                player.acted = false;
+            }
+        }
+        self.bet = to;
+        amount
+    }
+
+    fn others_can_act(&self, seat: usize) -> bool {
+        self.seats
+            .iter()
+            .enumerate()
+            .any(|(i, s)| i != seat && !s.folded && s.stack > 0)
+    }
+
+    fn needs_action(&self, seat: usize) -> bool {
+        let player = &self.seats[seat];
+        if player.folded || player.stack == 0 {
+            return false;
+        }
+        player.street_bet < self.bet || (!player.acted && self.others_can_act(seat))
+    }
+
+    /// Finds the next player to act starting from the given seat, dealing
+    /// further streets and awarding the pot as needed.
+    fn advance(&mut self, mut from: usize) {
+        let n = self.seats.len();
+        loop {
+            if self.seats.iter().filter(|s| !s.folded).count() == 1 {
+                self.finish(false);
+                return;
+            }
+            let next = (0..n)
+                .map(|i| (from + i) % n)
+                .find(|&seat| self.needs_action(seat));
+            if next.is_some() {
+                self.to_act = next;
+                return;
+            }
+            if self.street + 1 == self.game.streets.len() {
+                self.finish(true);
+                return;
+            }
+
+            self.street += 1;
+            for player in self.seats.iter_mut() {
+                player.street_bet = 0;
+                player.acted = false;
+            }
+            self.bet = 0;
+            self.bets = 0;
+            self.min_raise = match self.config.structure {
+                BettingStructure::FixedLimit if 2 * self.street >= self.game.streets.len() => {
+                    2 * self.config.big_blind
+                }
+                _ => self.config.big_blind,
+            };
+            self.deal_street();
+            from = 0;
+        }
+    }
+
+    fn deal_street(&mut self) {
+        let street = self.game.streets[self.street];
+        for _ in 0..street.hole_cards {
+            for player in self.seats.iter_mut() {
+                player.hole.extend(self.deck.deal(1).unwrap());
+            }
+        }
+        let cards = self
+            .deck
+            .deal(street.board_cards)
+            .unwrap()
+            .iter()
+            .collect::<Hand>();
+        self.board.extend(cards.iter());
+        self.history.push(Event::Street {
+            street: self.street,
+            cards,
+        });
+    }
+
+    fn finish(&mut self, showdown: bool) {
+        let unranked = GameRank {
+            hi: HandValue(0),
+            lo: None,
+        };
+        let players = self
+            .seats
+            .iter()
+            .map(|s| {
+                let rank = if showdown && !s.folded {
+                    self.game.evaluate(&s.hole, &self.board).unwrap()
+                } else {
+                    unranked
+                };
+                let player = Player::hi_lo(s.contribution, rank.hi, rank.lo);
+                if s.folded {
+                    player.fold()
+                } else {
+                    player
+                }
+            })
+            .collect::<Vec<_>>();
+        let result = resolve_showdown(&players).unwrap();
+        for (player, payout) in self.seats.iter_mut().zip(result.payouts.iter()) {
+            player.stack += payout;
+        }
+        self.to_act = None;
+        self.result = Some(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find_game;
+    use rstest::rstest;
+
+    fn start(game: &str, config: TableConfig, stacks: &[u64], seed: u64) -> HandState {
+        HandState::new(find_game(game).unwrap(), config, stacks, seed).unwrap()
+    }
+
+    fn no_limit(stacks: &[u64]) -> HandState {
+        start(
+            "holdem",
+            TableConfig::new(BettingStructure::NoLimit, 5, 10),
+            stacks,
+            1,
+        )
+    }
+
+    #[test]
+    fn posts_blinds_and_deals() {
+        let hand = no_limit(&[1000, 1000, 1000, 1000]);
+        let seats = hand.seats();
+        assert_eq!(seats[0].street_bet, 5);
+        assert_eq!(seats[1].street_bet, 10);
+        assert!(seats.iter().all(|s| s.hole.len() == 2));
+        assert!(hand.board().is_empty());
+        assert_eq!(hand.to_act(), Some(2));
+        assert_eq!(hand.pot(), 15);
+        assert_eq!(
+            hand.history()[..2],
+            [
+                Event::SmallBlind { seat: 0, amount: 5 },
+                Event::BigBlind {
+                    seat: 1,
+                    amount: 10
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn heads_up_order() {
+        let mut hand = no_limit(&[1000, 1000]);
+        assert_eq!(hand.seats()[1].street_bet, 5);
+        assert_eq!(hand.to_act(), Some(1));
+        assert_eq!(
+            hand.legal_actions(),
+            Some(LegalActions {
+                check: false,
+                call: Some(5),
+                bet: None,
+                raise: Some(20..=1000),
+            })
+        );
+
+        hand.act(Action::Call).unwrap();
+        assert_eq!(hand.to_act(), Some(0));
+        assert_eq!(hand.legal_actions().unwrap().raise, Some(20..=1000));
+        hand.act(Action::Check).unwrap();
+
+        assert_eq!(hand.street(), 1);
+        assert_eq!(hand.board().len(), 3);
+        assert_eq!(hand.to_act(), Some(0));
+        assert_eq!(hand.legal_actions().unwrap().bet, Some(10..=990));
+    }
+
+    #[test]
+    fn minimum_raise() {
+        let mut hand = no_limit(&[1000, 1000, 1000]);
+        hand.act(Action::Raise(30)).unwrap();
+        assert_eq!(hand.act(Action::Raise(40)), Err(EngineError::InvalidAmount));
+        assert_eq!(hand.to_act(), Some(0));
+        hand.act(Action::Raise(50)).unwrap();
+        assert_eq!(hand.legal_actions().unwrap().raise, Some(70..=1000));
+    }
+
+    #[test]
+    fn illegal_actions() {
+        let mut hand = no_limit(&[1000, 1000, 1000]);
+        assert_eq!(hand.act(Action::Check), Err(EngineError::IllegalAction));
+        assert_eq!(hand.act(Action::Bet(20)), Err(EngineError::IllegalAction));
+        assert_eq!(
+            hand.act(Action::Raise(2000)),
+            Err(EngineError::InvalidAmount)
+        );
+        assert!(hand.history().len() == 3);
+
+        hand.act(Action::Fold).unwrap();
+        hand.act(Action::Fold).unwrap();
+        assert_eq!(hand.act(Action::Fold), Err(EngineError::HandComplete));
+    }
+
+    #[test]
+    fn pot_limit_sizes() {
+        let mut hand = start(
+            "holdem",
+            TableConfig::new(BettingStructure::PotLimit, 5, 10),
+            &[1000, 1000, 1000],
+            1,
+        );
+        assert_eq!(hand.legal_actions().unwrap().raise, Some(20..=35));
+        hand.act(Action::Raise(35)).unwrap();
+        // The small blind may call 30 for a pot of 80, then raise by 80.
+        assert_eq!(hand.legal_actions().unwrap().raise, Some(60..=115));
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::Call).unwrap();
+
+        assert_eq!(hand.pot(), 105);
+        assert_eq!(hand.legal_actions().unwrap().bet, Some(10..=105));
+    }
+
+    #[test]
+    fn fixed_limit_sizes_and_cap() {
+        let mut hand = start(
+            "holdem",
+            TableConfig::new(BettingStructure::FixedLimit, 5, 10),
+            &[1000, 1000],
+            1,
+        );
+        assert_eq!(hand.legal_actions().unwrap().raise, Some(20..=20));
+        hand.act(Action::Raise(20)).unwrap();
+        hand.act(Action::Raise(30)).unwrap();
+        hand.act(Action::Raise(40)).unwrap();
+        assert_eq!(hand.legal_actions().unwrap().raise, None);
+        hand.act(Action::Call).unwrap();
+
+        // The flop is played with small bets and the turn with big bets.
+        assert_eq!(hand.legal_actions().unwrap().bet, Some(10..=10));
+        hand.act(Action::Check).unwrap();
+        hand.act(Action::Check).unwrap();
+        assert_eq!(hand.street(), 2);
+        assert_eq!(hand.legal_actions().unwrap().bet, Some(20..=20));
+    }
+
+    #[test]
+    fn incomplete_raise_does_not_reopen_betting() {
+        let mut hand = no_limit(&[1000, 1000, 45]);
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::Raise(30)).unwrap();
+        hand.act(Action::Call).unwrap();
+        assert_eq!(hand.legal_actions().unwrap().raise, Some(45..=45));
+        hand.act(Action::Raise(45)).unwrap();
+        assert!(hand.seats()[2].is_all_in());
+
+        // The short all-in raise of 15 does not reopen the betting.
+        assert_eq!(hand.to_act(), Some(0));
+        let legal = hand.legal_actions().unwrap();
+        assert_eq!(legal.call, Some(15));
+        assert_eq!(legal.raise, None);
+    }
+
+    #[test]
+    fn uncontested_pot() {
+        let mut hand = no_limit(&[1000, 1000, 1000]);
+        hand.act(Action::Raise(30)).unwrap();
+        hand.act(Action::Fold).unwrap();
+        hand.act(Action::Fold).unwrap();
+
+        assert!(hand.is_complete());
+        assert_eq!(hand.to_act(), None);
+        assert!(hand.board().is_empty());
+        let stacks = hand.seats().iter().map(|s| s.stack).collect::<Vec<_>>();
+        assert_eq!(stacks, [995, 990, 1015]);
+    }
+
+    #[test]
+    fn all_in_runs_out_the_board() {
+        let mut hand = no_limit(&[500, 200]);
+        hand.act(Action::Raise(200)).unwrap();
+        hand.act(Action::Call).unwrap();
+
+        assert!(hand.is_complete());
+        assert_eq!(hand.board().len(), 5);
+        let result = hand.result().unwrap();
+        assert_eq!(result.payouts.iter().sum::<u64>(), 400);
+        assert_eq!(hand.seats()[0].stack + hand.seats()[1].stack, 700);
+        let streets = hand
+            .history()
+            .iter()
+            .filter(|e| matches!(e, Event::Street { .. }))
+            .count();
+        assert_eq!(streets, 4);
+    }
+
+    #[test]
+    fn short_blinds_are_all_in() {
+        let mut hand = no_limit(&[8, 1000, 1000]);
+        hand.act(Action::Call).unwrap();
+        assert_eq!(hand.legal_actions().unwrap().call, Some(3));
+        hand.act(Action::Call).unwrap();
+        assert!(hand.seats()[0].is_all_in());
+        hand.act(Action::Check).unwrap();
+
+        assert_eq!(hand.street(), 1);
+        assert_eq!(hand.pot(), 28);
+        assert_eq!(hand.to_act(), Some(1));
+    }
+
+    #[test]
+    fn antes_are_dead() {
+        let hand = start(
+            "short-deck",
+            TableConfig::new(BettingStructure::NoLimit, 0, 10).with_ante(2),
+            &[100, 100, 100],
+            3,
+        );
+        assert_eq!(hand.pot(), 16);
+        assert_eq!(hand.seats()[0].street_bet, 0);
+        assert_eq!(hand.seats()[1].street_bet, 10);
+        assert!(hand
+            .seats()
+            .iter()
+            .flat_map(|s| s.hole.iter())
+            .all(|c| c.rank() >= aya_base::Rank::Six));
+    }
+
+    #[rstest]
+    #[case("holdem", 6)]
+    #[case("short-deck", 6)]
+    #[case("manila", 5)]
+    #[case("omaha", 4)]
+    #[case("omaha-6", 0)]
+    #[case("omaha-hi-lo", 9)]
+    #[case("omaha-5-hi-lo", 4)]
+    fn check_down_to_showdown(#[case] game: &str, #[case] seed: u64) {
+        let def = find_game(game).unwrap();
+        let config = TableConfig::new(BettingStructure::PotLimit, 1, 2);
+        let mut hand = HandState::new(def, config, &[100, 100, 100], seed).unwrap();
+        while let Some(legal) = hand.legal_actions() {
+            let action = if legal.check {
+                Action::Check
+            } else {
+                Action::Call
+            };
+            hand.act(action).unwrap();
+        }
+
+        assert_eq!(hand.board().len(), def.board_cards());
+        assert!(hand
+            .seats()
+            .iter()
+            .all(|s| s.hole.len() == def.hole_cards()));
+        assert_eq!(hand.seats().iter().map(|s| s.stack).sum::<u64>(), 300);
+        assert_eq!(hand.result().unwrap().payouts.iter().sum::<u64>(), 6);
+    }
+
+    #[test]
+    fn replays_deterministically() {
+        let play = |seed| {
+            let mut hand = start(
+                "holdem",
+                TableConfig::new(BettingStructure::NoLimit, 5, 10),
+                &[1000, 1000, 1000],
+                seed,
+            );
+            for action in [Action::Raise(30), Action::Call, Action::Fold] {
+                hand.act(action).unwrap();
+            }
+            while hand.act(Action::Check).is_ok() {}
+            hand
+        };
+
+        let (first, second) = (play(99), play(99));
+        assert_eq!(first.seats(), second.seats());
+        assert_eq!(first.board(), second.board());
+        assert_eq!(first.history(), second.history());
+        assert_eq!(first.result(), second.result());
+        assert_ne!(first.seats(), play(100).seats());
+    }
+
+    #[rstest]
+    #[case("stud", TableConfig::new(BettingStructure::FixedLimit, 5, 10), &[100, 100], EngineError::UnsupportedGame)]
+    #[case("2-7td", TableConfig::new(BettingStructure::FixedLimit, 5, 10), &[100, 100], EngineError::UnsupportedGame)]
+    #[case("holdem", TableConfig::new(BettingStructure::NoLimit, 5, 0), &[100, 100], EngineError::InvalidStakes)]
+    #[case("holdem", TableConfig::new(BettingStructure::NoLimit, 20, 10), &[100, 100], EngineError::InvalidStakes)]
+    #[case("holdem", TableConfig::new(BettingStructure::NoLimit, 5, 10), &[100], EngineError::InvalidPlayerCount)]
+    #[case("omaha-6", TableConfig::new(BettingStructure::PotLimit, 5, 10), &[100; 8], EngineError::InvalidPlayerCount)]
+    #[case("holdem", TableConfig::new(BettingStructure::NoLimit, 5, 10), &[100, 0], EngineError::EmptyStack)]
+    fn invalid_hands(
+        #[case] game: &str,
+        #[case] config: TableConfig,
+        #[case] stacks: &[u64],
+        #[case] expected: EngineError,
+    ) {
+        let result = HandState::new(find_game(game).unwrap(), config, stacks, 0);
+        assert_eq!(result.err(), Some(expected));
+    }
+}
@@ -0,0 +1,1789 @@
+//! Equity calculations for hold'em and Omaha showdowns.
+//!
+//! Equities can either be computed exactly, by enumerating every runout of
+//! the board with [`exact_equity`], or estimated by sampling random runouts
+//! with [`sampled_equity`] when enumeration would take too long.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::equity::exact_equity;
+//!
+//! let holes = ["Ah Ad".parse()?, "Kh Kd".parse()?];
+//! let board = "Ac Kc 2s 7d".parse()?;
+//! let result = exact_equity(&holes, &board, &"".parse()?).unwrap();
+//! assert_eq!(result.runouts, 44);
+//! assert_eq!(result.players[1].win, 1.0 / 44.0);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt, ops::RangeInclusive};
+
+use aya_base::{constants::CARD_COUNT, Card, Hand, Rank, CARDS};
+
+use crate::{
+    combine, deck::Deck, for_each_subhand, omaha_lo_rank, omaha_range::OmahaRange, omaha_rank,
+    poker_rank, range::Range, short_deck_rank, AceFiveHandRank, PokerHandRank,
+};
+
+pub use crate::players::{MAX_PLAYERS, MIN_PLAYERS};
+
+/// The number of runouts sampled between two convergence checks.
+const BATCH_SIZE: u64 = 1_000;
+
+/// A poker variant supported by the equity calculators.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Game {
+    /// Texas hold'em, with two hole cards ranked by [`poker_rank`].
+    Holdem,
+    /// Short-deck hold'em, with two hole cards dealt from a 36-card deck and
+    /// ranked by [`short_deck_rank`].
+    ShortDeck,
+    /// Omaha, with four to six hole cards ranked by [`omaha_rank`].
+    Omaha,
+    /// Omaha Hi/Lo, with four to six hole cards, where half of the pot is
+    /// awarded to the best 8-or-better low ranked by [`omaha_lo_rank`], if
+    /// any.
+    OmahaHiLo,
+}
+
+impl Game {
+    /// Returns the number of hole cards each player may hold.
+    pub fn hole_cards(self) -> RangeInclusive<usize> {
+        match self {
+            Game::Holdem | Game::ShortDeck => 2..=2,
+            Game::Omaha | Game::OmahaHiLo => 4..=6,
+        }
+    }
+
+    /// Returns `true` if the card is part of the deck used by the game.
+    pub fn deck_contains(self, card: &Card) -> bool {
+        self != Game::ShortDeck || card.rank() >= Rank::Six
+    }
+}
+
+/// An error which can be returned when the cards given to an equity
+/// calculation do not describe a valid deal.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EquityError {
+    /// There are fewer than [`MIN_PLAYERS`] or more than [`MAX_PLAYERS`]
+    /// players.
+    InvalidPlayerCount,
+    /// A player holds the wrong number of hole cards for the game.
+    InvalidHoleCards,
+    /// The board holds more cards than can be dealt in the game.
+    InvalidBoard,
+    /// A card was dealt more than once.
+    DuplicateCard,
+    /// A card is not part of the deck used by the game.
+    InvalidCard,
+    /// No hands of the ranges can be dealt together.
+    EmptyRange,
+    /// There are not enough cards left in the deck to deal every player and
+    /// the board.
+    NotEnoughCards,
+    /// The sampling options do not allow any runouts to be sampled.
+    NoSamples,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::InvalidPlayerCount => write!(f, "invalid number of players"),
+            EquityError::InvalidHoleCards => write!(f, "invalid number of hole cards"),
+            EquityError::InvalidBoard => write!(f, "invalid number of board cards"),
+            EquityError::DuplicateCard => write!(f, "card dealt more than once"),
+            EquityError::InvalidCard => write!(f, "card not in the deck"),
+            EquityError::EmptyRange => write!(f, "no hands can be dealt from the ranges"),
+            EquityError::NotEnoughCards => write!(f, "not enough cards left in the deck"),
+            EquityError::NoSamples => write!(f, "no runouts to sample"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EquityError {}
+
+/// The share of the pot a single player can expect to win at showdown.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct PlayerEquity {
+    /// The average fraction of the pot won by the player.
+    pub equity: f64,
+    /// The fraction of runouts in which the player wins the whole pot.
+    pub win: f64,
+    /// The fraction of runouts in which the player wins only part of the pot.
+    pub tie: f64,
+    /// The standard error of the estimated equity, which is zero for exact
+    /// calculations.
+    pub std_error: f64,
+}
+
+impl PlayerEquity {
+    /// Returns the fraction of runouts in which the player wins no part of
+    /// the pot.
+    pub fn lose(&self) -> f64 {
+        1.0 - self.win - self.tie
+    }
+
+    /// Returns the confidence interval of the equity which lies `z` standard
+    /// errors on either side of the estimate, e.g. use `z = 1.96` for a 95%
+    /// confidence interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::equity::{sampled_equity, Game, SamplingOptions};
+    ///
+    /// let holes = ["Ah Ad".parse()?, "Kh Kd".parse()?];
+    /// let options = SamplingOptions::new(42).with_max_samples(10_000);
+    /// let result = sampled_equity(Game::Holdem, &holes, &"".parse()?, &"".parse()?, &options);
+    /// let (low, high) = result.unwrap().players[0].confidence_interval(1.96);
+    /// assert!(low < 0.82 && 0.82 < high);
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        (
+            self.equity - z * self.std_error,
+            self.equity + z * self.std_error,
+        )
+    }
+}
+
+/// The result of an equity calculation.
+#[derive(PartialEq, Clone, Debug)]
+pub struct EquityResult {
+    /// The equity of each player, in the order the players were given.
+    pub players: Vec<PlayerEquity>,
+    /// The number of runouts evaluated.
+    pub runouts: u64,
+}
+
+/// The stopping rules of a sampled equity calculation.
+///
+/// Sampling stops as soon as one of the limits is reached: either the
+/// standard error of every player's equity falls below the target after the
+/// minimum number of samples, the maximum number of samples is reached, or,
+/// with the `std` feature, the time limit runs out.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::equity::SamplingOptions;
+///
+/// let options = SamplingOptions::new(42)
+///     .with_target_std_error(0.001)
+///     .with_max_samples(5_000_000);
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SamplingOptions {
+    seed: u64,
+    target_std_error: f64,
+    min_samples: u64,
+    max_samples: u64,
+    #[cfg(feature = "std")]
+    time_limit: Option<std::time::Duration>,
+}
+
+impl SamplingOptions {
+    /// Creates new sampling options using the given random seed, which
+    /// sample one million runouts without an error target or time limit.
+    pub fn new(seed: u64) -> SamplingOptions {
+        SamplingOptions {
+            seed,
+            target_std_error: 0.0,
+            min_samples: 10_000,
+            max_samples: 1_000_000,
+            #[cfg(feature = "std")]
+            time_limit: None,
+        }
+    }
+
+    /// Stops sampling once the standard error of every player's equity is at
+    /// most `std_error`.
+    pub fn with_target_std_error(mut self, std_error: f64) -> SamplingOptions {
+        self.target_std_error = std_error;
+        self
+    }
+
+    /// Sets the number of runouts sampled before checking the target
+    /// standard error, which guards against stopping on an unreliable early
+    /// estimate.
+    pub fn with_min_samples(mut self, samples: u64) -> SamplingOptions {
+        self.min_samples = samples;
+        self
+    }
+
+    /// Sets the maximum number of runouts sampled.
+    pub fn with_max_samples(mut self, samples: u64) -> SamplingOptions {
+        self.max_samples = samples;
+        self
+    }
+
+    /// Stops sampling once the given time has elapsed.
+    ///
+    /// Note that the result is no longer determined by the seed alone when a
+    /// time limit is set.
+    #[cfg(feature = "std")]
+    pub fn with_time_limit(mut self, time_limit: std::time::Duration) -> SamplingOptions {
+        self.time_limit = Some(time_limit);
+        self
+    }
+}
+
+/// Returns the exact equity of each player in a Texas hold'em showdown.
+///
+/// Every possible completion of `board` is enumerated from the cards that
+/// were not dealt to a player, to the board or marked as `dead`, and each
+/// player's best hand is ranked with [`poker_rank`]. A pot shared by several
+/// players is split evenly among them.
+///
+/// Returns an error if there are not between [`MIN_PLAYERS`] and
+/// [`MAX_PLAYERS`] players, if a player does not hold exactly two hole cards,
+/// if the board holds more than five cards, or if any card was dealt twice.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::equity::exact_equity;
+///
+/// let holes = ["Ah Kd".parse()?, "Ac Kc".parse()?, "As Ks".parse()?];
+/// let board = "Qd Jh Th 2c 3c".parse()?;
+/// let result = exact_equity(&holes, &board, &"".parse()?).unwrap();
+/// assert_eq!(result.runouts, 1);
+/// assert_eq!(result.players[0].tie, 1.0);
+/// assert_eq!(result.players[0].equity, 1.0 / 3.0);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn exact_equity(
+    holes: &[Hand],
+    board: &Hand,
+    dead: &Hand,
+) -> Result<EquityResult, EquityError> {
+    let (deck, deck_len) = prepare(Game::Holdem, holes, board, dead)?;
+
+    let mut tally = Tally::new(holes.len());
+    for_each_subhand(&deck[..deck_len], 5 - board.len(), *board, &mut |runout| {
+        showdown(Game::Holdem, holes, &runout, &mut tally);
+    });
+
+    Ok(tally.result(false))
+}
+
+/// Returns the estimated equity of each player in a showdown of the given
+/// game, computed by sampling random runouts of the board.
+///
+/// The runouts are dealt from the cards of the game's deck that were not
+/// dealt to a player, to the board or marked as `dead`, shuffled according to
+/// the seed of the `options`, so that the result is reproducible. Sampling
+/// continues until one of the stopping rules of the `options` is met, and the
+/// standard error of each estimate is reported alongside it.
+///
+/// Returns an error if there are not between [`MIN_PLAYERS`] and
+/// [`MAX_PLAYERS`] players, if a player holds the wrong number of hole cards
+/// for the game, if the board holds more than five cards, if any card was
+/// dealt twice, if a card is not part of the game's deck, if there are not
+/// enough cards left to complete the board, or if the `options` allow no
+/// samples.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::equity::{sampled_equity, Game, SamplingOptions};
+///
+/// let holes = ["As Ks Jh Td".parse()?, "Qc Qd 8c 7d".parse()?];
+/// let board = "Qh 9s 2c".parse()?;
+/// let options = SamplingOptions::new(42).with_target_std_error(0.005);
+/// let result = sampled_equity(Game::Omaha, &holes, &board, &"".parse()?, &options).unwrap();
+/// assert!(result.players[0].std_error <= 0.005);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn sampled_equity(
+    game: Game,
+    holes: &[Hand],
+    board: &Hand,
+    dead: &Hand,
+    options: &SamplingOptions,
+) -> Result<EquityResult, EquityError> {
+    if options.max_samples == 0 {
+        return Err(EquityError::NoSamples);
+    }
+    let (deck_cards, deck_len) = prepare(game, holes, board, dead)?;
+    let mut deck = Deck::with_seed(deck_cards[..deck_len].iter(), options.seed);
+    let missing = 5 - board.len();
+
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
+
+    let mut tally = Tally::new(holes.len());
+    while tally.runouts < options.max_samples {
+        let batch = BATCH_SIZE.min(options.max_samples - tally.runouts);
+        for _ in 0..batch {
+            deck.reset();
+            let mut runout = *board;
+            runout.extend(deck.deal(missing).unwrap());
+            showdown(game, holes, &runout, &mut tally);
+        }
+
+        if tally.runouts >= options.min_samples && tally.max_std_error() <= options.target_std_error
+        {
+            break;
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(time_limit) = options.time_limit {
+            if start.elapsed() >= time_limit {
+                break;
+            }
+        }
+    }
+
+    Ok(tally.result(true))
+}
+
+/// The equity of a single combination of hole cards in a range.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct ComboEquity {
+    /// The hole cards.
+    pub hole: Hand,
+    /// The frequency of the hole cards in the range after card removal,
+    /// normalized so that the weights of all combinations add up to 1.
+    pub weight: f64,
+    /// The equity of the hole cards against the opposing range.
+    pub equity: PlayerEquity,
+}
+
+/// The result of a range-vs-range equity calculation.
+#[derive(PartialEq, Clone, Debug)]
+pub struct RangeEquityResult {
+    /// The overall equity of each range, in the order the ranges were given.
+    pub players: Vec<PlayerEquity>,
+    /// The equity of each combination of hole cards of each range which can
+    /// be dealt against the opposing range.
+    pub combos: Vec<Vec<ComboEquity>>,
+    /// The number of matchups and runouts evaluated.
+    pub runouts: u64,
+}
+
+/// Returns the exact equity of two hold'em ranges against each other.
+///
+/// Every pair of non-conflicting hole cards from the two ranges is played
+/// out on every possible completion of `board`, weighted by the product of
+/// their weights, with cards in `board` or `dead` removed from the ranges and
+/// the deck. Hand-vs-range equities can be computed by passing a range
+/// holding a single combination, e.g. `"AhKh"`.
+///
+/// The number of evaluations grows with the number of runouts, so this is
+/// best suited for postflop boards, while [`sampled_range_equity`] should be
+/// used preflop.
+///
+/// Returns an error if the board holds more than five cards, if any card was
+/// dealt twice, or if no hands of the two ranges can be dealt together.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{equity::range_equity, range::Range};
+///
+/// let hero = "AhKh".parse::<Range>()?;
+/// let villain = "QQ+, AK".parse::<Range>()?;
+/// let board = "Kd 7h 2h".parse()?;
+/// let result = range_equity(&hero, &villain, &board, &"".parse()?).unwrap();
+/// assert!(result.players[0].equity > 0.7);
+/// // Combos holding the Ah, Kh or Kd cannot be dealt to the villain
+/// assert_eq!(result.combos[1].len(), 3 + 1 + 6 + 6);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn range_equity(
+    hero: &Range,
+    villain: &Range,
+    board: &Hand,
+    dead: &Hand,
+) -> Result<RangeEquityResult, EquityError> {
+    let (deck, deck_len) = prepare_ranges(board, dead)?;
+    let mut dealt = *board;
+    dealt.extend(dead.iter());
+    let matchup = Matchup::new(hero, villain, &dealt)?;
+
+    let mut hero_ranks = Vec::with_capacity(matchup.hero.len());
+    let mut villain_ranks = Vec::with_capacity(matchup.villain.len());
+    let mut outcomes = matchup.outcomes();
+    let mut runouts = 0;
+    for_each_subhand(&deck[..deck_len], 5 - board.len(), *board, &mut |runout| {
+        matchup.rank_combos(&runout, true, &mut hero_ranks);
+        matchup.rank_combos(&runout, false, &mut villain_ranks);
+
+        for (i, &(hole, hero_weight)) in matchup.hero.iter().enumerate() {
+            let hero_rank = match hero_ranks[i] {
+                Some(rank) => rank,
+                None => continue,
+            };
+            for (j, &(other, villain_weight)) in matchup.villain.iter().enumerate() {
+                let villain_rank = match villain_ranks[j] {
+                    Some(rank) if hole.is_disjoint(&other) => rank,
+                    _ => continue,
+                };
+
+                let share = match hero_rank.cmp(&villain_rank) {
+                    Ordering::Greater => 1.0,
+                    Ordering::Equal => 0.5,
+                    Ordering::Less => 0.0,
+                };
+                outcomes[0][i].insert(villain_weight, share);
+                outcomes[1][j].insert(hero_weight, 1.0 - share);
+                runouts += 1;
+            }
+        }
+    });
+
+    Ok(matchup.result(&outcomes, None, runouts))
+}
+
+/// Returns the estimated equity of two hold'em ranges against each other,
+/// computed by sampling random matchups and runouts.
+///
+/// Each sample deals a pair of non-conflicting hole cards from the two
+/// ranges, chosen according to their weights, and a random completion of
+/// `board`, using a random number generator seeded with the seed of the
+/// `options`. Sampling continues until one of the stopping rules of the
+/// `options` is met. Note that the per-combination equities are estimated
+/// from only the samples in which the combination was dealt.
+///
+/// Returns an error if the board holds more than five cards, if any card was
+/// dealt twice, if no hands of the two ranges can be dealt together, or if
+/// the `options` allow no samples.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::equity::{sampled_range_equity, SamplingOptions};
+/// use aya_poker::range::Range;
+///
+/// let hero = "TT+, AQs+, AKo".parse::<Range>()?;
+/// let villain = "22+, A2s+, KTs+, QTs+, JTs, ATo+, KJo+".parse::<Range>()?;
+/// let options = SamplingOptions::new(42).with_target_std_error(0.005);
+/// let result =
+///     sampled_range_equity(&hero, &villain, &"".parse()?, &"".parse()?, &options).unwrap();
+/// assert!(result.players[0].equity > 0.6);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn sampled_range_equity(
+    hero: &Range,
+    villain: &Range,
+    board: &Hand,
+    dead: &Hand,
+    options: &SamplingOptions,
+) -> Result<RangeEquityResult, EquityError> {
+    if options.max_samples == 0 {
+        return Err(EquityError::NoSamples);
+    }
+    let (deck_cards, deck_len) = prepare_ranges(board, dead)?;
+    // The deck is seeded from the combination sampler, so that the two do
+    // not draw from the same stream of random numbers.
+    let mut rng = fastrand::Rng::with_seed(options.seed);
+    let mut deck = Deck::with_seed(deck_cards[..deck_len].iter(), rng.u64(..));
+    let mut dealt = *board;
+    dealt.extend(dead.iter());
+    let matchup = Matchup::new(hero, villain, &dealt)?;
+    let hero_weights = cumulative_weights(&matchup.hero);
+    let villain_weights = cumulative_weights(&matchup.villain);
+    let missing = 5 - board.len();
+
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
+
+    let mut tally = Tally::new(2);
+    let mut outcomes = matchup.outcomes();
+    while tally.runouts < options.max_samples {
+        let batch = BATCH_SIZE.min(options.max_samples - tally.runouts);
+        for _ in 0..batch {
+            let (i, j) = loop {
+                let i = sample_index(&mut rng, &hero_weights);
+                let j = sample_index(&mut rng, &villain_weights);
+                if matchup.hero[i].0.is_disjoint(&matchup.villain[j].0) {
+                    break (i, j);
+                }
+            };
+            let holes = [matchup.hero[i].0, matchup.villain[j].0];
+
+            deck.reset();
+            let mut runout = *board;
+            while runout.len() < board.len() + missing {
+                let card = deck.deal(1).unwrap()[0];
+                if !holes[0].contains(&card) && !holes[1].contains(&card) {
+                    runout.insert_unchecked(&card);
+                }
+            }
+
+            let ranks = holes.map(|hole| poker_rank(&combine(&hole, &runout)));
+            tally.award(&ranks, 1.0);
+            let share = tally.runout_shares[0];
+            tally.finish_runout();
+            outcomes[0][i].insert(1.0, share);
+            outcomes[1][j].insert(1.0, 1.0 - share);
+        }
+
+        if tally.runouts >= options.min_samples && tally.max_std_error() <= options.target_std_error
+        {
+            break;
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(time_limit) = options.time_limit {
+            if start.elapsed() >= time_limit {
+                break;
+            }
+        }
+    }
+
+    Ok(matchup.result(&outcomes, Some(&tally), tally.runouts))
+}
+
+/// The equity of a player in an Omaha showdown.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct OmahaPlayerEquity {
+    /// The overall equity of the player.
+    pub equity: PlayerEquity,
+    /// The fraction of runouts in which the player wins at least part of the
+    /// high half of the pot, or of the whole pot in high-only games.
+    pub high: f64,
+    /// The fraction of runouts in which the player wins at least part of the
+    /// low half of the pot.
+    pub low: f64,
+    /// The fraction of runouts in which the player wins the whole pot.
+    pub scoop: f64,
+    /// The fraction of runouts in which the player wins exactly a quarter of
+    /// the pot, e.g. by splitting the low half with another player.
+    pub quartered: f64,
+}
+
+/// The result of an Omaha equity calculation.
+#[derive(PartialEq, Clone, Debug)]
+pub struct OmahaEquityResult {
+    /// The equity of each player, in the order the ranges were given.
+    pub players: Vec<OmahaPlayerEquity>,
+    /// The number of runouts evaluated.
+    pub runouts: u64,
+}
+
+/// Returns the estimated equity of Omaha ranges against each other,
+/// computed by sampling random hands and runouts.
+///
+/// Each player is dealt `hole_cards` hole cards, which can be 4, 5 or 6,
+/// chosen uniformly from the hands of their range that do not conflict with
+/// the board, the dead cards, or the other players' hands. A player holding
+/// known cards can be given a range consisting of that single hand, e.g.
+/// `"AsAhKsKh"`. If `hi_lo` is `true`, half of the pot is awarded to the best
+/// 8-or-better low, if any. The random number generator is seeded with the
+/// seed of the `options`, and sampling continues until one of the stopping
+/// rules of the `options` is met.
+///
+/// The hands of narrow ranges are listed up front and checked for a deal
+/// compatible with each other, while hands of wide ranges are dealt at
+/// random until one belongs to the range. Each runout deals the narrowest
+/// ranges first and the wider ranges from the cards left over.
+///
+/// Returns an error if there are not between [`MIN_PLAYERS`] and
+/// [`MAX_PLAYERS`] players or not enough cards to deal to all of them, if
+/// `hole_cards` is not between 4 and 6, if the board holds more than five
+/// cards, if any card was dealt twice, if no hands of the ranges can be
+/// dealt together, or if the `options` allow no samples.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::equity::{omaha_equity, SamplingOptions};
+/// use aya_poker::omaha_range::OmahaRange;
+///
+/// let ranges = ["AsAhKsKh".parse::<OmahaRange>()?, "$R:$ds".parse()?];
+/// let options = SamplingOptions::new(42).with_max_samples(20_000);
+/// let result =
+///     omaha_equity(&ranges, 4, false, &"".parse()?, &"".parse()?, &options).unwrap();
+/// assert!(result.players[0].equity.equity > 0.55);
+///
+/// let ranges = ["A2**:$ds".parse::<OmahaRange>()?, "KK**".parse()?];
+/// let result = omaha_equity(&ranges, 4, true, &"".parse()?, &"".parse()?, &options).unwrap();
+/// assert!(result.players[0].quartered > 0.0 && result.players[1].low < 0.1);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn omaha_equity(
+    ranges: &[OmahaRange],
+    hole_cards: usize,
+    hi_lo: bool,
+    board: &Hand,
+    dead: &Hand,
+    options: &SamplingOptions,
+) -> Result<OmahaEquityResult, EquityError> {
+    if ranges.len() < MIN_PLAYERS || ranges.len() > MAX_PLAYERS {
+        return Err(EquityError::InvalidPlayerCount);
+    }
+    if options.max_samples == 0 {
+        return Err(EquityError::NoSamples);
+    }
+    if !Game::Omaha.hole_cards().contains(&hole_cards) {
+        return Err(EquityError::InvalidHoleCards);
+    }
+    let (deck_cards, deck_len) = prepare_ranges(board, dead)?;
+    if ranges.len() * hole_cards + 5 - board.len() > deck_len {
+        return Err(EquityError::NotEnoughCards);
+    }
+
+    let mut rng = fastrand::Rng::with_seed(options.seed);
+    let mut deck = Deck::with_seed(deck_cards[..deck_len].iter(), rng.u64(..));
+    let mut available = deck_cards;
+    let available = &mut available[..deck_len];
+
+    let mut dealt = *board;
+    dealt.extend(dead.iter());
+    let mut samplers = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        samplers.push(HandSampler::new(
+            range, hole_cards, &dealt, available, &mut rng,
+        )?);
+    }
+
+    let mut order = (0..ranges.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| samplers[i].len());
+    let listed = order
+        .iter()
+        .filter_map(|&i| samplers[i].hands())
+        .collect::<Vec<_>>();
+    if !can_deal(&listed, &mut Vec::with_capacity(ranges.len())) {
+        return Err(EquityError::EmptyRange);
+    }
+
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
+
+    let n = ranges.len();
+    let mut tally = Tally::new(n);
+    let mut stats = [[0_u64; 4]; MAX_PLAYERS];
+    let mut holes = [Hand::new(); MAX_PLAYERS];
+    while tally.runouts < options.max_samples {
+        let batch = BATCH_SIZE.min(options.max_samples - tally.runouts);
+        for _ in 0..batch {
+            let mut attempts = 0;
+            while !deal_holes(&samplers, &order, available, &mut rng, &mut holes[..n]) {
+                attempts += 1;
+                if attempts == MAX_DEAL_ATTEMPTS {
+                    return Err(EquityError::EmptyRange);
+                }
+            }
+
+            deck.reset();
+            let mut runout = *board;
+            while runout.len() < 5 {
+                let card = deck.deal(1).unwrap()[0];
+                if !holes[..n].iter().any(|hole| hole.contains(&card)) {
+                    runout.insert_unchecked(&card);
+                }
+            }
+
+            let hi_ranks = rank_holes(&holes[..n], |hole| omaha_rank(hole, &runout));
+            let lo_ranks = rank_holes(&holes[..n], |hole| {
+                if hi_lo {
+                    omaha_lo_rank(hole, &runout).to_lo_8_rank()
+                } else {
+                    AceFiveHandRank(0)
+                }
+            });
+            let best_hi = hi_ranks[..n].iter().max().unwrap();
+            let best_lo = lo_ranks[..n].iter().max().unwrap();
+
+            if best_lo.0 == 0 {
+                tally.award(&hi_ranks[..n], 1.0);
+            } else {
+                tally.award(&hi_ranks[..n], 0.5);
+                tally.award(&lo_ranks[..n], 0.5);
+            }
+            for i in 0..n {
+                let share = tally.runout_shares[i];
+                stats[i][0] += (hi_ranks[i] == *best_hi) as u64;
+                stats[i][1] += (best_lo.0 != 0 && lo_ranks[i] == *best_lo) as u64;
+                stats[i][2] += (share == 1.0) as u64;
+                stats[i][3] += (share == 0.25) as u64;
+            }
+            tally.finish_runout();
+        }
+
+        if tally.runouts >= options.min_samples && tally.max_std_error() <= options.target_std_error
+        {
+            break;
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(time_limit) = options.time_limit {
+            if start.elapsed() >= time_limit {
+                break;
+            }
+        }
+    }
+
+    let runouts = tally.runouts as f64;
+    let players = tally
+        .result(true)
+        .players
+        .into_iter()
+        .zip(stats)
+        .map(
+            |(equity, [high, low, scoop, quartered])| OmahaPlayerEquity {
+                equity,
+                high: high as f64 / runouts,
+                low: low as f64 / runouts,
+                scoop: scoop as f64 / runouts,
+                quartered: quartered as f64 / runouts,
+            },
+        )
+        .collect();
+
+    Ok(OmahaEquityResult {
+        players,
+        runouts: tally.runouts,
+    })
+}
+
+/// Deals a hand from each player's range in the given order, each from the
+/// cards not dealt to the previous players, and returns `false` if a range
+/// has no hand left to deal.
+fn deal_holes(
+    samplers: &[HandSampler],
+    order: &[usize],
+    available: &[Card],
+    rng: &mut fastrand::Rng,
+    holes: &mut [Hand],
+) -> bool {
+    let mut cards = [CARDS[0]; CARD_COUNT];
+    for (k, &player) in order.iter().enumerate() {
+        let dealt = &order[..k];
+        let mut len = 0;
+        for card in available {
+            if dealt.iter().all(|&i| !holes[i].contains(card)) {
+                cards[len] = *card;
+                len += 1;
+            }
+        }
+
+        let is_free = |hole: &Hand| dealt.iter().all(|&i| holes[i].is_disjoint(hole));
+        match samplers[player].sample(&mut cards[..len], is_free, rng) {
+            Some(hole) => holes[player] = hole,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Returns `true` if a hand can be dealt from each of the lists of hands
+/// without any conflicts with each other or with the hands already `dealt`.
+fn can_deal(combos: &[&[Hand]], dealt: &mut Vec<Hand>) -> bool {
+    let (first, rest) = match combos.split_first() {
+        Some(split) => split,
+        None => return true,
+    };
+
+    for hole in first.iter() {
+        if dealt.iter().all(|other| other.is_disjoint(hole)) {
+            dealt.push(*hole);
+            let found = can_deal(rest, dealt);
+            dealt.pop();
+            if found {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// The maximum number of attempts to deal hands from the ranges to all
+/// players before giving up.
+const MAX_DEAL_ATTEMPTS: usize = 10_000;
+
+/// The maximum number of random hands dealt from the remaining cards while
+/// looking for a hand of a wide range.
+const MAX_SAMPLE_ATTEMPTS: usize = 1_000;
+
+/// The number of random hands dealt to estimate the fraction of hands that
+/// belong to an Omaha range.
+const RANGE_SAMPLE_SIZE: usize = 2_000;
+
+/// The number of random hands picked from the list of a narrow range before
+/// falling back to choosing among the hands that can still be dealt.
+const MAX_PICK_ATTEMPTS: usize = 64;
+
+/// Draws random hands from an Omaha range.
+enum HandSampler<'a> {
+    /// Deals random hands until one belongs to the range, used for wide
+    /// ranges.
+    Rejection(&'a OmahaRange, usize),
+    /// Picks a random hand from the list of all hands in the range, used for
+    /// narrow ranges.
+    Enumerated(Vec<Hand>),
+}
+
+impl<'a> HandSampler<'a> {
+    fn new(
+        range: &'a OmahaRange,
+        hole_cards: usize,
+        dealt: &Hand,
+        available: &mut [Card],
+        rng: &mut fastrand::Rng,
+    ) -> Result<HandSampler<'a>, EquityError> {
+        let accepted = (0..RANGE_SAMPLE_SIZE)
+            .filter(|_| range.contains(&deal_hand(available, hole_cards, rng)))
+            .count();
+        if accepted >= RANGE_SAMPLE_SIZE / 100 {
+            return Ok(HandSampler::Rejection(range, hole_cards));
+        }
+
+        let hands = range.hands(hole_cards, dealt);
+        if hands.is_empty() {
+            return Err(EquityError::EmptyRange);
+        }
+        Ok(HandSampler::Enumerated(hands))
+    }
+
+    /// Returns the number of listed hands, or `usize::MAX` for wide ranges.
+    fn len(&self) -> usize {
+        match self {
+            HandSampler::Rejection(..) => usize::MAX,
+            HandSampler::Enumerated(hands) => hands.len(),
+        }
+    }
+
+    /// Returns the listed hands of a narrow range.
+    fn hands(&self) -> Option<&[Hand]> {
+        match self {
+            HandSampler::Rejection(..) => None,
+            HandSampler::Enumerated(hands) => Some(hands),
+        }
+    }
+
+    /// Returns a random hand of the range dealt from the given cards, or
+    /// `None` if none was found. The hands of narrow ranges are instead
+    /// checked with `is_free`.
+    fn sample(
+        &self,
+        cards: &mut [Card],
+        is_free: impl Fn(&Hand) -> bool,
+        rng: &mut fastrand::Rng,
+    ) -> Option<Hand> {
+        match self {
+            HandSampler::Rejection(range, hole_cards) => (0..MAX_SAMPLE_ATTEMPTS)
+                .map(|_| deal_hand(cards, *hole_cards, rng))
+                .find(|hand| range.contains(hand)),
+            HandSampler::Enumerated(hands) => {
+                let picked = (0..MAX_PICK_ATTEMPTS)
+                    .map(|_| hands[rng.usize(..hands.len())])
+                    .find(|hand| is_free(hand));
+                if picked.is_some() {
+                    return picked;
+                }
+
+                let free = hands.iter().filter(|hand| is_free(hand)).count();
+                if free == 0 {
+                    return None;
+                }
+                hands
+                    .iter()
+                    .filter(|hand| is_free(hand))
+                    .nth(rng.usize(..free))
+                    .copied()
+            }
+        }
+    }
+}
+
+/// Deals a random hand of `hole_cards` cards from the given cards.
+fn deal_hand(cards: &mut [Card], hole_cards: usize, rng: &mut fastrand::Rng) -> Hand {
+    let mut hand = Hand::new();
+    for i in 0..hole_cards {
+        cards.swap(i, rng.usize(i..cards.len()));
+        hand.insert_unchecked(&cards[i]);
+    }
+    hand
+}
+
+/// The combinations of hole cards of two ranges after card removal.
+struct Matchup {
+    hero: Vec<(Hand, f64)>,
+    villain: Vec<(Hand, f64)>,
+}
+
+impl Matchup {
+    fn new(hero: &Range, villain: &Range, dealt: &Hand) -> Result<Matchup, EquityError> {
+        let hero = hero.without_blocked(dealt).combos().collect::<Vec<_>>();
+        let villain = villain.without_blocked(dealt).combos().collect::<Vec<_>>();
+
+        let compatible = hero
+            .iter()
+            .any(|(h, _)| villain.iter().any(|(v, _)| h.is_disjoint(v)));
+        if !compatible {
+            return Err(EquityError::EmptyRange);
+        }
+
+        Ok(Matchup { hero, villain })
+    }
+
+    fn outcomes(&self) -> [Vec<Outcomes>; 2] {
+        [
+            alloc::vec![Outcomes::default(); self.hero.len()],
+            alloc::vec![Outcomes::default(); self.villain.len()],
+        ]
+    }
+
+    /// Ranks every combo of one of the ranges which does not conflict with
+    /// the board.
+    fn rank_combos(&self, board: &Hand, hero: bool, ranks: &mut Vec<Option<PokerHandRank>>) {
+        let combos = if hero { &self.hero } else { &self.villain };
+        ranks.clear();
+        ranks.extend(combos.iter().map(|(hole, _)| {
+            if hole.is_disjoint(board) {
+                Some(poker_rank(&combine(hole, board)))
+            } else {
+                None
+            }
+        }));
+    }
+
+    fn result(
+        &self,
+        outcomes: &[Vec<Outcomes>; 2],
+        tally: Option<&Tally>,
+        runouts: u64,
+    ) -> RangeEquityResult {
+        let mut players = Vec::with_capacity(2);
+        let mut combos = Vec::with_capacity(2);
+        for (player, (range, outcomes)) in [&self.hero, &self.villain]
+            .into_iter()
+            .zip(outcomes)
+            .enumerate()
+        {
+            // In exact calculations, each combo's outcomes are weighted by
+            // the opposing range, and need to be weighted by its own weight.
+            let combo_weight = |i: usize| match tally {
+                Some(_) => outcomes[i].weight,
+                None => outcomes[i].weight * range[i].1,
+            };
+
+            let mut total = Outcomes::default();
+            for (i, outcome) in outcomes.iter().enumerate() {
+                total.merge(
+                    outcome,
+                    combo_weight(i) / outcome.weight.max(f64::MIN_POSITIVE),
+                );
+            }
+            let mut equity = total.equity();
+            if let Some(tally) = tally {
+                equity.std_error = tally.std_error(player);
+            }
+            players.push(equity);
+
+            let total_weight = (0..range.len()).map(combo_weight).sum::<f64>();
+            let breakdown = outcomes
+                .iter()
+                .enumerate()
+                .filter(|(_, outcome)| outcome.weight > 0.0)
+                .map(|(i, outcome)| ComboEquity {
+                    hole: range[i].0,
+                    weight: combo_weight(i) / total_weight,
+                    equity: outcome.equity(),
+                })
+                .collect();
+            combos.push(breakdown);
+        }
+
+        RangeEquityResult {
+            players,
+            combos,
+            runouts,
+        }
+    }
+}
+
+/// Weighted totals of the showdown outcomes of a combination of hole cards.
+#[derive(Clone, Copy, Debug, Default)]
+struct Outcomes {
+    weight: f64,
+    wins: f64,
+    ties: f64,
+    shares: f64,
+}
+
+impl Outcomes {
+    fn insert(&mut self, weight: f64, share: f64) {
+        self.weight += weight;
+        if share == 1.0 {
+            self.wins += weight;
+        } else if share > 0.0 {
+            self.ties += weight;
+        }
+        self.shares += weight * share;
+    }
+
+    /// Adds the outcomes of `other`, scaled by `scale`.
+    fn merge(&mut self, other: &Outcomes, scale: f64) {
+        self.weight += scale * other.weight;
+        self.wins += scale * other.wins;
+        self.ties += scale * other.ties;
+        self.shares += scale * other.shares;
+    }
+
+    fn equity(&self) -> PlayerEquity {
+        PlayerEquity {
+            equity: self.shares / self.weight,
+            win: self.wins / self.weight,
+            tie: self.ties / self.weight,
+            std_error: 0.0,
+        }
+    }
+}
+
+fn cumulative_weights(combos: &[(Hand, f64)]) -> Vec<f64> {
+    combos
+        .iter()
+        .scan(0.0, |total, &(_, weight)| {
+            *total += weight;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Returns a random index with probability proportional to its weight.
+fn sample_index(rng: &mut fastrand::Rng, cumulative_weights: &[f64]) -> usize {
+    let total = cumulative_weights[cumulative_weights.len() - 1];
+    let target = rng.f64() * total;
+    cumulative_weights
+        .partition_point(|&w| w <= target)
+        .min(cumulative_weights.len() - 1)
+}
+
+/// Validates the board and dead cards of a range-vs-range calculation and
+/// returns the cards remaining in the deck.
+fn prepare_ranges(board: &Hand, dead: &Hand) -> Result<([Card; CARD_COUNT], usize), EquityError> {
+    if board.len() > 5 {
+        return Err(EquityError::InvalidBoard);
+    }
+    if !board.is_disjoint(dead) {
+        return Err(EquityError::DuplicateCard);
+    }
+
+    let mut deck = [CARDS[0]; CARD_COUNT];
+    let mut deck_len = 0;
+    for card in CARDS.iter() {
+        if !board.contains(card) && !dead.contains(card) {
+            deck[deck_len] = *card;
+            deck_len += 1;
+        }
+    }
+
+    Ok((deck, deck_len))
+}
+
+/// Validates the deal and returns the cards remaining in the deck.
+fn prepare(
+    game: Game,
+    holes: &[Hand],
+    board: &Hand,
+    dead: &Hand,
+) -> Result<([Card; CARD_COUNT], usize), EquityError> {
+    if holes.len() < MIN_PLAYERS || holes.len() > MAX_PLAYERS {
+        return Err(EquityError::InvalidPlayerCount);
+    }
+    if holes
+        .iter()
+        .any(|hole| !game.hole_cards().contains(&hole.len()))
+    {
+        return Err(EquityError::InvalidHoleCards);
+    }
+    if board.len() > 5 {
+        return Err(EquityError::InvalidBoard);
+    }
+    let (deck, deck_len) = remaining_deck(game, holes, board, dead)?;
+    if 5 - board.len() > deck_len {
+        return Err(EquityError::NotEnoughCards);
+    }
+    Ok((deck, deck_len))
+}
+
+/// Checks that no card was dealt twice and that every card is part of the
+/// deck of the game, and returns the cards remaining in the deck.
+pub(crate) fn remaining_deck(
+    game: Game,
+    holes: &[Hand],
+    board: &Hand,
+    dead: &Hand,
+) -> Result<([Card; CARD_COUNT], usize), EquityError> {
+    // A hand cannot hold every dealt card of a large multiway pot, so they
+    // are tracked by their index instead.
+    let mut dealt = 0_u64;
+    for card in [board]
+        .into_iter()
+        .chain(holes)
+        .chain([dead])
+        .flat_map(Hand::iter)
+    {
+        let bit = 1 << card.idx();
+        if dealt & bit != 0 {
+            return Err(EquityError::DuplicateCard);
+        }
+        dealt |= bit;
+    }
+    if ![board]
+        .into_iter()
+        .chain(holes)
+        .chain([dead])
+        .all(|hand| hand.iter().all(|c| game.deck_contains(c)))
+    {
+        return Err(EquityError::InvalidCard);
+    }
+
+    let mut deck = [CARDS[0]; CARD_COUNT];
+    let mut deck_len = 0;
+    for card in CARDS.iter() {
+        if game.deck_contains(card) && dealt & 1 << card.idx() == 0 {
+            deck[deck_len] = *card;
+            deck_len += 1;
+        }
+    }
+
+    Ok((deck, deck_len))
+}
+
+/// Awards the pot of a complete board to the best hands.
+fn showdown(game: Game, holes: &[Hand], board: &Hand, tally: &mut Tally) {
+    let n = holes.len();
+    match game {
+        Game::Holdem => {
+            let ranks = rank_holes(holes, |hole| poker_rank(&combine(hole, board)));
+            tally.award(&ranks[..n], 1.0);
+        }
+        Game::ShortDeck => {
+            let ranks = rank_holes(holes, |hole| short_deck_rank(&combine(hole, board)));
+            tally.award(&ranks[..n], 1.0);
+        }
+        Game::Omaha => {
+            let ranks = rank_holes(holes, |hole| omaha_rank(hole, board));
+            tally.award(&ranks[..n], 1.0);
+        }
+        Game::OmahaHiLo => {
+            let hi_ranks = rank_holes(holes, |hole| omaha_rank(hole, board));
+            let lo_ranks = rank_holes(holes, |hole| omaha_lo_rank(hole, board).to_lo_8_rank());
+            if lo_ranks[..n].iter().all(|r| r.0 == 0) {
+                tally.award(&hi_ranks[..n], 1.0);
+            } else {
+                tally.award(&hi_ranks[..n], 0.5);
+                tally.award(&lo_ranks[..n], 0.5);
+            }
+        }
+    }
+    tally.finish_runout();
+}
+
+fn rank_holes<R: Copy>(holes: &[Hand], rank_fn: impl Fn(&Hand) -> R) -> [R; MAX_PLAYERS] {
+    let mut ranks = [rank_fn(&holes[0]); MAX_PLAYERS];
+    for (rank, hole) in ranks.iter_mut().zip(holes).skip(1) {
+        *rank = rank_fn(hole);
+    }
+    ranks
+}
+
+/// Running totals of the showdown outcomes for each player.
+struct Tally {
+    players: usize,
+    runout_shares: [f64; MAX_PLAYERS],
+    wins: [u64; MAX_PLAYERS],
+    ties: [u64; MAX_PLAYERS],
+    shares: [f64; MAX_PLAYERS],
+    squared_shares: [f64; MAX_PLAYERS],
+    runouts: u64,
+}
+
+impl Tally {
+    fn new(players: usize) -> Tally {
+        Tally {
+            players,
+            runout_shares: [0.0; MAX_PLAYERS],
+            wins: [0; MAX_PLAYERS],
+            ties: [0; MAX_PLAYERS],
+            shares: [0.0; MAX_PLAYERS],
+            squared_shares: [0.0; MAX_PLAYERS],
+            runouts: 0,
+        }
+    }
+
+    /// Splits a pot of the current runout evenly among the players with the
+    /// best rank.
+    fn award<R: Ord>(&mut self, ranks: &[R], pot: f64) {
+        let best_rank = match ranks.iter().max() {
+            Some(rank) => rank,
+            None => return,
+        };
+        let winners = ranks.iter().filter(|&r| r == best_rank).count();
+        for (share, rank) in self.runout_shares.iter_mut().zip(ranks) {
+            if rank == best_rank {
+                *share += pot / winners as f64;
+            }
+        }
+    }
+
+    /// Records the shares of the pot awarded in the current runout.
+    fn finish_runout(&mut self) {
+        self.runouts += 1;
+        for i in 0..self.players {
+            let share = self.runout_shares[i];
+            if share == 1.0 {
+                self.wins[i] += 1;
+            } else if share > 0.0 {
+                self.ties[i] += 1;
+            }
+            self.shares[i] += share;
+            self.squared_shares[i] += share * share;
+            self.runout_shares[i] = 0.0;
+        }
+    }
+
+    /// Returns the standard error of the mean share of the pot won by the
+    /// player.
+    fn std_error(&self, player: usize) -> f64 {
+        if self.runouts < 2 {
+            return f64::INFINITY;
+        }
+
+        let n = self.runouts as f64;
+        let mean = self.shares[player] / n;
+        let variance = (self.squared_shares[player] / n - mean * mean) * n / (n - 1.0);
+        sqrt(variance.max(0.0) / n)
+    }
+
+    fn max_std_error(&self) -> f64 {
+        (0..self.players)
+            .map(|i| self.std_error(i))
+            .fold(0.0, f64::max)
+    }
+
+    fn result(&self, sampled: bool) -> EquityResult {
+        let runouts = self.runouts as f64;
+        let players = (0..self.players)
+            .map(|i| PlayerEquity {
+                equity: self.shares[i] / runouts,
+                win: self.wins[i] as f64 / runouts,
+                tie: self.ties[i] as f64 / runouts,
+                std_error: if sampled { self.std_error(i) } else { 0.0 },
+            })
+            .collect();
+
+        EquityResult {
+            players,
+            runouts: self.runouts,
+        }
+    }
+}
+
+/// Computes the square root of a non-negative number with Newton's method,
+/// since `f64::sqrt` is not available in `no_std` builds.
+fn sqrt(x: f64) -> f64 {
+    if x <= 0.0 || x.is_infinite() {
+        return x;
+    }
+
+    let mut guess = if x < 1.0 { 1.0 } else { x };
+    loop {
+        let next = 0.5 * (guess + x / guess);
+        if next >= guess {
+            return guess;
+        }
+        guess = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    fn parse_holes(holes: &[&str]) -> Result<Vec<Hand>, ParseError> {
+        holes.iter().map(|h| h.parse()).collect()
+    }
+
+    #[rstest]
+    #[case::turn(&["Ah Ad", "Kh Kd"], "Ac Kc 2s 7d", "", 44, [43, 1])]
+    #[case::dead_card(&["Ah Ad", "Kh Kd"], "Ac Kc 2s 7d", "Ks 3h", 42, [42, 0])]
+    #[case::river(&["Ah Ad", "Kh Kd"], "Ac Kc 2s 7d Ks", "", 1, [0, 1])]
+    #[case::chopped(&["Ah Kd", "Ac Kc"], "Qs Jh Th 2c", "", 44, [0, 0])]
+    fn heads_up_wins(
+        #[case] holes: &[&str],
+        #[case] board: &str,
+        #[case] dead: &str,
+        #[case] runouts: u64,
+        #[case] wins: [u64; 2],
+    ) -> Result<(), ParseError> {
+        let holes = parse_holes(holes)?;
+        let result = exact_equity(&holes, &board.parse()?, &dead.parse()?).unwrap();
+
+        assert_eq!(result.runouts, runouts);
+        for (player, wins) in result.players.iter().zip(wins) {
+            assert_eq!(player.win, wins as f64 / runouts as f64);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn multiway_equity_sums_to_one() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Kh", "Qs Qd", "Jc Tc", "7s 6s"])?;
+        let result = exact_equity(&holes, &"Qh Th 5s".parse()?, &"".parse()?).unwrap();
+
+        assert_eq!(result.runouts, 820);
+        let total = result.players.iter().map(|p| p.equity).sum::<f64>();
+        assert!((total - 1.0).abs() < 1e-9);
+        for player in result.players.iter() {
+            assert!(player.equity >= player.win);
+            assert!(player.equity <= player.win + player.tie);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn three_way_split() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Kd", "Ac Kc", "Kh 5d"])?;
+        let result = exact_equity(&holes, &"As Ks 2d 2h 2s".parse()?, &"".parse()?).unwrap();
+
+        assert_eq!(result.players[0].equity, 0.5);
+        assert_eq!(result.players[1].equity, 0.5);
+        assert_eq!(result.players[2].lose(), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn preflop_runouts() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Ad", "7c 2d"])?;
+        let result = exact_equity(&holes, &"".parse()?, &"".parse()?).unwrap();
+
+        assert_eq!(result.runouts, 1_712_304);
+        assert!(result.players[0].equity > 0.85 && result.players[0].equity < 0.9);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::one_player(&["Ah Ad"], "", "", EquityError::InvalidPlayerCount)]
+    #[case::too_many_players(
+        &["2c 2d", "3c 3d", "4c 4d", "5c 5d", "6c 6d", "7c 7d", "8c 8d", "9c 9d", "Tc Td", "Jc Jd", "Qc Qd"],
+        "",
+        "",
+        EquityError::InvalidPlayerCount
+    )]
+    #[case::three_hole_cards(&["Ah Ad Ac", "Kh Kd"], "", "", EquityError::InvalidHoleCards)]
+    #[case::six_board_cards(&["Ah Ad", "Kh Kd"], "2c 3c 4c 5c 6c 7c", "", EquityError::InvalidBoard)]
+    #[case::shared_hole_card(&["Ah Ad", "Ah Kd"], "", "", EquityError::DuplicateCard)]
+    #[case::dead_board_card(&["Ah Ad", "Kh Kd"], "2c 3c 4c", "3c", EquityError::DuplicateCard)]
+    fn invalid_deals(
+        #[case] holes: &[&str],
+        #[case] board: &str,
+        #[case] dead: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let holes = parse_holes(holes)?;
+        let result = exact_equity(&holes, &board.parse()?, &dead.parse()?);
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(0.25)]
+    #[case(2.0)]
+    #[case(1e-12)]
+    #[case(12345.678)]
+    fn newton_sqrt(#[case] x: f64) {
+        assert!((sqrt(x) - x.sqrt()).abs() <= 1e-12 * x.sqrt().max(1.0));
+    }
+
+    #[test]
+    fn sampled_matches_exact() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Kh", "Qs Qd", "Jc Tc"])?;
+        let board = "Qh Th 5s".parse()?;
+        let exact = exact_equity(&holes, &board, &"".parse()?).unwrap();
+
+        let options = SamplingOptions::new(7).with_max_samples(50_000);
+        let sampled = sampled_equity(Game::Holdem, &holes, &board, &"".parse()?, &options).unwrap();
+        assert_eq!(sampled.runouts, 50_000);
+        for (exact, sampled) in exact.players.iter().zip(sampled.players.iter()) {
+            let (low, high) = sampled.confidence_interval(4.0);
+            assert!(low < exact.equity && exact.equity < high);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn six_way_river() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Kh", "Qs Qd", "6c 6d", "3c 3d", "4c 4d", "5c 5d"])?;
+        let board = "Kd 7h 2h 9s Ts".parse()?;
+        let result = exact_equity(&holes, &board, &"".parse()?).unwrap();
+        assert_eq!(result.runouts, 1);
+        assert_eq!(result.players[0].equity, 1.0);
+
+        let options = SamplingOptions::new(0).with_max_samples(10);
+        let sampled = sampled_equity(Game::Holdem, &holes, &board, &"".parse()?, &options).unwrap();
+        assert_eq!(sampled.players, result.players);
+        Ok(())
+    }
+
+    #[test]
+    fn four_way_omaha() -> Result<(), ParseError> {
+        let holes = parse_holes(&["As Ks Jh Td", "Qc Qd 8c 7d", "9h 8h 7s 6s", "Ac Ad 2c 3c"])?;
+        let options = SamplingOptions::new(1).with_max_samples(2_000);
+        let result = sampled_equity(Game::Omaha, &holes, &"".parse()?, &"".parse()?, &options);
+        let total = result
+            .unwrap()
+            .players
+            .iter()
+            .map(|p| p.equity)
+            .sum::<f64>();
+        assert!((total - 1.0).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn not_enough_cards_for_runout() {
+        let holes = CARDS.chunks(6).take(8).map(|cards| cards.iter().collect());
+        let holes = holes.collect::<Vec<Hand>>();
+        let options = SamplingOptions::new(0).with_max_samples(10);
+        let result = sampled_equity(Game::Omaha, &holes, &Hand::new(), &Hand::new(), &options);
+        assert_eq!(result, Err(EquityError::NotEnoughCards));
+    }
+
+    #[test]
+    fn no_samples() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Ad", "Kh Kd"])?;
+        let options = SamplingOptions::new(0).with_max_samples(0);
+        let result = sampled_equity(Game::Holdem, &holes, &"".parse()?, &"".parse()?, &options);
+        assert_eq!(result, Err(EquityError::NoSamples));
+
+        let (hero, villain) = ("AA".parse()?, "KK".parse()?);
+        let result = sampled_range_equity(&hero, &villain, &Hand::new(), &Hand::new(), &options);
+        assert_eq!(result, Err(EquityError::NoSamples));
+        Ok(())
+    }
+
+    #[test]
+    fn sampled_is_deterministic() -> Result<(), ParseError> {
+        let holes = parse_holes(&["As Ks Jh Td", "Qc Qd 8c 7d", "9h 8h 7s 6s"])?;
+        let options = SamplingOptions::new(42).with_max_samples(5_000);
+        let first = sampled_equity(Game::Omaha, &holes, &"".parse()?, &"".parse()?, &options);
+        let second = sampled_equity(Game::Omaha, &holes, &"".parse()?, &"".parse()?, &options);
+        assert_eq!(first, second);
+        Ok(())
+    }
+
+    #[test]
+    fn sampled_stops_at_target() -> Result<(), ParseError> {
+        let holes = parse_holes(&["Ah Ad", "Kh Kd"])?;
+        let options = SamplingOptions::new(42)
+            .with_target_std_error(0.01)
+            .with_min_samples(1_000);
+        let result =
+            sampled_equity(Game::Holdem, &holes, &"".parse()?, &"".parse()?, &options).unwrap();
+
+        assert!(result.runouts < 10_000);
+        assert!(result.players.iter().all(|p| p.std_error <= 0.01));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::short_deck_flush_over_full_house(
+        Game::ShortDeck,
+        &["Ah Kh", "9c 9d"],
+        "Qh Jh 6h 9s 6c",
+        [1.0, 0.0]
+    )]
+    #[case::omaha_two_hole_cards(Game::Omaha, &["Ah 2c 3d 4s", "Qc Qd Jc Jd"], "Th 9h 8h 7h Qs", [0.0, 1.0])]
+    #[case::hi_lo_split(Game::OmahaHiLo, &["Ah 2d Kc Kd", "Qc Qd Jc Jd"], "3h 4s 8d Qh 9c", [0.5, 0.5])]
+    #[case::hi_lo_no_low(Game::OmahaHiLo, &["Ah 2d Kc Kd", "Qc Qd Jc Jd"], "Th 4s 9d Qh 9c", [0.0, 1.0])]
+    #[case::hi_lo_scoop(Game::OmahaHiLo, &["Ah 2d 5c 5d", "Qc Qd Jc Jd"], "3h 4s 5h Kh 9c", [1.0, 0.0])]
+    fn complete_board_shares(
+        #[case] game: Game,
+        #[case] holes: &[&str],
+        #[case] board: &str,
+        #[case] expected: [f64; 2],
+    ) -> Result<(), ParseError> {
+        let holes = parse_holes(holes)?;
+        let options = SamplingOptions::new(0).with_max_samples(10);
+        let result = sampled_equity(game, &holes, &board.parse()?, &"".parse()?, &options).unwrap();
+
+        for (player, expected) in result.players.iter().zip(expected) {
+            assert_eq!(player.equity, expected);
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::short_deck_deuce(Game::ShortDeck, &["Ah Kh", "2c 9d"], EquityError::InvalidCard)]
+    #[case::omaha_two_cards(Game::Omaha, &["Ah Kh", "Qc Qd Jc Jd"], EquityError::InvalidHoleCards)]
+    #[case::holdem_four_cards(Game::Holdem, &["Ah Kh", "Qc Qd Jc Jd"], EquityError::InvalidHoleCards)]
+    fn invalid_game_deals(
+        #[case] game: Game,
+        #[case] holes: &[&str],
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let holes = parse_holes(holes)?;
+        let options = SamplingOptions::new(0);
+        let result = sampled_equity(game, &holes, &"".parse()?, &"".parse()?, &options);
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn single_combo_ranges_match_exact() -> Result<(), ParseError> {
+        let hero = "AhAd".parse::<Range>()?;
+        let villain = "KhKd".parse::<Range>()?;
+        let board = "Ac Kc 2s".parse()?;
+        let result = range_equity(&hero, &villain, &board, &"".parse()?).unwrap();
+
+        let holes = parse_holes(&["Ah Ad", "Kh Kd"])?;
+        let exact = exact_equity(&holes, &board, &"".parse()?).unwrap();
+        for (range, exact) in result.players.iter().zip(exact.players.iter()) {
+            assert!((range.equity - exact.equity).abs() < 1e-12);
+            assert!((range.win - exact.win).abs() < 1e-12);
+            assert!((range.tie - exact.tie).abs() < 1e-12);
+        }
+        assert_eq!(result.runouts, exact.runouts);
+        Ok(())
+    }
+
+    #[test]
+    fn range_equity_averages_matchups() -> Result<(), ParseError> {
+        let hero = "AA, KhQh".parse::<Range>()?;
+        let villain = "KK:0.5, 76s".parse::<Range>()?;
+        let board = "Kc 7h 2h 5d".parse()?;
+        let result = range_equity(&hero, &villain, &board, &"".parse()?).unwrap();
+
+        let mut total_weight = 0.0;
+        let mut total_equity = 0.0;
+        for (h, wh) in hero.without_blocked(&board).combos() {
+            for (v, wv) in villain.without_blocked(&board).combos() {
+                if h.is_disjoint(&v) {
+                    let exact = exact_equity(&[h, v], &board, &"".parse()?).unwrap();
+                    total_weight += wh * wv;
+                    total_equity += wh * wv * exact.players[0].equity;
+                }
+            }
+        }
+        assert!((result.players[0].equity - total_equity / total_weight).abs() < 1e-12);
+        assert!((result.players[0].equity + result.players[1].equity - 1.0).abs() < 1e-12);
+
+        for combos in result.combos.iter() {
+            let total = combos.iter().map(|c| c.weight).sum::<f64>();
+            assert!((total - 1.0).abs() < 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn range_card_removal() -> Result<(), ParseError> {
+        let hero = "AsAh".parse::<Range>()?;
+        let villain = "AA, KK".parse::<Range>()?;
+        let board = "Kd 7h 2c".parse()?;
+        let result = range_equity(&hero, &villain, &board, &"7c".parse()?).unwrap();
+
+        let villain_combos = &result.combos[1];
+        assert_eq!(villain_combos.len(), 1 + 3);
+        assert_eq!(villain_combos[0].hole, "Kc Kh".parse()?);
+        assert_eq!(villain_combos[0].weight, 0.25);
+        let hero_hole = "As Ah".parse()?;
+        assert!(villain_combos
+            .iter()
+            .all(|c| c.hole.is_disjoint(&hero_hole)));
+        Ok(())
+    }
+
+    #[test]
+    fn sampled_range_matches_exact() -> Result<(), ParseError> {
+        let hero = "TT+, AK".parse::<Range>()?;
+        let villain = "99-66, KQs, 87s".parse::<Range>()?;
+        let board = "Ks 8h 6d 2c".parse()?;
+        let exact = range_equity(&hero, &villain, &board, &"".parse()?).unwrap();
+
+        let options = SamplingOptions::new(3).with_max_samples(50_000);
+        let sampled =
+            sampled_range_equity(&hero, &villain, &board, &"".parse()?, &options).unwrap();
+        assert_eq!(sampled.runouts, 50_000);
+        for (exact, sampled) in exact.players.iter().zip(sampled.players.iter()) {
+            let (low, high) = sampled.confidence_interval(4.0);
+            assert!(low < exact.equity && exact.equity < high);
+        }
+
+        let again = sampled_range_equity(&hero, &villain, &board, &"".parse()?, &options).unwrap();
+        assert_eq!(sampled, again);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::conflicting("AhKh", "AhKh", "", "", EquityError::EmptyRange)]
+    #[case::blocked("AhKh", "QQ", "Qh Qd", "Qc", EquityError::EmptyRange)]
+    #[case::empty("", "QQ", "", "", EquityError::EmptyRange)]
+    #[case::board_dead_overlap("AhKh", "QQ", "2c 3c 4c", "2c", EquityError::DuplicateCard)]
+    #[case::six_board_cards("AhKh", "QQ", "2c 3c 4c 5c 6c 7c", "", EquityError::InvalidBoard)]
+    fn invalid_ranges(
+        #[case] hero: &str,
+        #[case] villain: &str,
+        #[case] board: &str,
+        #[case] dead: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let hero = hero.parse::<Range>()?;
+        let villain = villain.parse::<Range>()?;
+        let (board, dead) = (board.parse()?, dead.parse()?);
+
+        let result = range_equity(&hero, &villain, &board, &dead);
+        assert_eq!(result, Err(expected));
+        let options = SamplingOptions::new(0);
+        let result = sampled_range_equity(&hero, &villain, &board, &dead, &options);
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+
+    fn parse_omaha_ranges(ranges: &[&str]) -> Result<Vec<OmahaRange>, ParseError> {
+        ranges.iter().map(|r| r.parse()).collect()
+    }
+
+    #[test]
+    fn omaha_known_hands_match_sampled_equity() -> Result<(), ParseError> {
+        let ranges = parse_omaha_ranges(&["AsAhKsKh", "JdTd9c8c"])?;
+        let board = "Qs 7d 2c".parse()?;
+        let options = SamplingOptions::new(5).with_max_samples(20_000);
+        let result = omaha_equity(&ranges, 4, false, &board, &"".parse()?, &options).unwrap();
+
+        let holes = parse_holes(&["As Ah Ks Kh", "Jd Td 9c 8c"])?;
+        let exact = exact_omaha_equity(&holes, &board);
+        let (low, high) = result.players[0].equity.confidence_interval(4.0);
+        assert!(low < exact && exact < high);
+        assert_eq!(result.players[0].low, 0.0);
+        Ok(())
+    }
+
+    /// Computes the exact Omaha equity of the first of two hands on the flop.
+    fn exact_omaha_equity(holes: &[Hand], board: &Hand) -> f64 {
+        let deck = CARDS
+            .iter()
+            .filter(|c| !board.contains(c) && holes.iter().all(|h| !h.contains(c)))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut tally = Tally::new(2);
+        for_each_subhand(&deck, 5 - board.len(), *board, &mut |runout| {
+            showdown(Game::Omaha, holes, &runout, &mut tally);
+        });
+        tally.result(false).players[0].equity
+    }
+
+    #[rstest]
+    #[case::split_low(
+        ["AsKs2sKd", "Ah2h9c9d"],
+        "4c 5d 8h Kc Qd",
+        [(0.75, 1.0, 1.0, 0.0, 0.0), (0.25, 0.0, 1.0, 0.0, 1.0)]
+    )]
+    #[case::no_low(
+        ["AsKs2sKd", "Ah2h9c9d"],
+        "Kc Qd 9h 8c 2d",
+        [(1.0, 1.0, 0.0, 1.0, 0.0), (0.0, 0.0, 0.0, 0.0, 0.0)]
+    )]
+    #[case::scoop(
+        ["As2s3sKd", "Ah9h9c9d"],
+        "4s 5d 8s Kc Qs",
+        [(1.0, 1.0, 1.0, 1.0, 0.0), (0.0, 0.0, 0.0, 0.0, 0.0)]
+    )]
+    fn omaha_hi_lo_statistics(
+        #[case] ranges: [&str; 2],
+        #[case] board: &str,
+        #[case] expected: [(f64, f64, f64, f64, f64); 2],
+    ) -> Result<(), ParseError> {
+        let ranges = parse_omaha_ranges(&ranges)?;
+        let options = SamplingOptions::new(0).with_max_samples(10);
+        let result =
+            omaha_equity(&ranges, 4, true, &board.parse()?, &"".parse()?, &options).unwrap();
+
+        for (player, expected) in result.players.iter().zip(expected) {
+            let actual = (
+                player.equity.equity,
+                player.high,
+                player.low,
+                player.scoop,
+                player.quartered,
+            );
+            assert_eq!(actual, expected);
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(4)]
+    #[case(5)]
+    #[case(6)]
+    fn omaha_multiway_ranges(#[case] hole_cards: usize) -> Result<(), ParseError> {
+        let ranges = parse_omaha_ranges(&["AA:$ds", "*", "$R, KK"])?;
+        let options = SamplingOptions::new(11).with_max_samples(2_000);
+        let result = omaha_equity(
+            &ranges,
+            hole_cards,
+            true,
+            &"".parse()?,
+            &"".parse()?,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(result.runouts, 2_000);
+        let total = result.players.iter().map(|p| p.equity.equity).sum::<f64>();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let again = omaha_equity(
+            &ranges,
+            hole_cards,
+            true,
+            &"".parse()?,
+            &"".parse()?,
+            &options,
+        )
+        .unwrap();
+        assert_eq!(result, again);
+        Ok(())
+    }
+
+    // Hands dealt at random rarely belong to every range at once, or rarely
+    // fit together at a full table.
+    #[rstest]
+    #[case::rare_pairs(&["KK**", "QQ**", "JJ**", "TT**"])]
+    #[case::full_table(&["*"; 10])]
+    fn rarely_compatible_omaha_ranges(#[case] ranges: &[&str]) -> Result<(), ParseError> {
+        let ranges = parse_omaha_ranges(ranges)?;
+        let options = SamplingOptions::new(3).with_max_samples(500);
+        let result = omaha_equity(&ranges, 4, false, &"".parse()?, &"".parse()?, &options).unwrap();
+        assert_eq!(result.runouts, 500);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::three_hole_cards(&["AA", "KK"], 3, "", EquityError::InvalidHoleCards)]
+    #[case::seven_hole_cards(&["AA", "KK"], 7, "", EquityError::InvalidHoleCards)]
+    #[case::one_player(&["AA"], 4, "", EquityError::InvalidPlayerCount)]
+    #[case::not_enough_cards(&["*"; 9], 6, "", EquityError::NotEnoughCards)]
+    #[case::conflicting(&["AAAA", "AA"], 4, "", EquityError::EmptyRange)]
+    #[case::blocked(&["AsAh**", "KK"], 4, "As", EquityError::EmptyRange)]
+    fn invalid_omaha_deals(
+        #[case] ranges: &[&str],
+        #[case] hole_cards: usize,
+        #[case] dead: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let ranges = parse_omaha_ranges(ranges)?;
+        let options = SamplingOptions::new(0).with_max_samples(100);
+        let result = omaha_equity(
+            &ranges,
+            hole_cards,
+            false,
+            &"".parse()?,
+            &dead.parse()?,
+            &options,
+        );
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+}
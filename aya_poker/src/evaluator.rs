@@ -0,0 +1,575 @@
+//! A common interface to the hand evaluators of every poker variant.
+//!
+//! Every variant is represented by a unit struct implementing [`Variant`],
+//! which describes the numbers of hole and board cards and the deck of the
+//! game, and either [`Evaluator`], if the pot is awarded to the best hand
+//! under a single ranking, or [`SplitEvaluator`], if it is split between
+//! the best high and low hands. This allows code such as showdowns and
+//! simulations to be written once for all of the variants. The equity and
+//! nuts calculations do not use these traits, and only support the variants
+//! of [`Game`](crate::equity::Game).
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::base::{Hand, ParseError};
+//! use aya_poker::evaluator::{Evaluator, Holdem, Omaha};
+//!
+//! /// Returns the index of the player with the best hand.
+//! fn winner<E: Evaluator>(evaluator: &E, holes: &[&str], board: &str) -> Result<usize, ParseError> {
+//!     let board = board.parse()?;
+//!     let mut best = None;
+//!     for (i, hole) in holes.iter().enumerate() {
+//!         let rank = evaluator.rank(&hole.parse()?, &board);
+//!         if best.map_or(true, |(_, best_rank)| rank > best_rank) {
+//!             best = Some((i, rank));
+//!         }
+//!     }
+//!     Ok(best.unwrap().0)
+//! }
+//!
+//! let board = "Ah 9h 7h 4c 2s";
+//! assert_eq!(winner(&Holdem, &["Kh 3h", "Ac Ad"], board)?, 0);
+//! assert_eq!(winner(&Omaha, &["Kh 3c Qd Jd", "Ac Ad 8s 8d"], board)?, 1);
+//! # Ok::<(), ParseError>(())
+//! ```
+
+use core::{fmt, hash::Hash, ops::RangeInclusive};
+
+use aya_base::{Card, Deck, Hand, Rank, CARDS};
+
+use crate::{
+    ace_five_rank, baduci_rank, badugi_rank, combine, deuce_seven_rank, deuces_wild_rank,
+    manila_rank, omaha_lo_rank, omaha_rank, poker_rank, short_deck_rank, AceFiveHandRank,
+    BaduciHandRank, BadugiHandRank, BadugiRankCategory, DeuceSevenHandRank, PokerHandRank,
+    PokerRankCategory, ShortDeckHandRank,
+};
+
+/// The numbers of cards dealt and the deck used in a poker variant.
+pub trait Variant {
+    /// The numbers of hole cards a player may hold.
+    const HOLE_CARDS: RangeInclusive<usize>;
+
+    /// The numbers of cards the board may hold.
+    const BOARD_CARDS: RangeInclusive<usize>;
+
+    /// Returns `true` if the card is part of the deck used by the variant.
+    fn deck_contains(card: &Card) -> bool {
+        let _ = card;
+        true
+    }
+
+    /// Returns a shuffled deck of all of the cards used by the variant.
+    fn deck(seed: u64) -> Deck {
+        Deck::with_seed(CARDS.iter().filter(|c| Self::deck_contains(c)), seed)
+    }
+}
+
+/// A hand evaluator for a poker variant in which the pot is awarded to the
+/// best hand under a single ranking.
+pub trait Evaluator: Variant {
+    /// The strength ranking of a hand, where greater ranks are better.
+    type Rank: Ord + Copy + fmt::Debug + Hash;
+
+    /// The hand-ranking category of a hand, e.g. a straight or a flush.
+    type Category: Eq + Copy + fmt::Debug + fmt::Display + Hash;
+
+    /// Returns the rank of the best hand that can be made from the hole and
+    /// board cards.
+    fn rank(&self, hole: &Hand, board: &Hand) -> Self::Rank;
+
+    /// Returns the hand-ranking category of the rank.
+    fn category(&self, rank: Self::Rank) -> Self::Category;
+}
+
+/// A hand evaluator for a poker variant in which the pot is split between
+/// the best high hand and the best low hand.
+pub trait SplitEvaluator: Variant {
+    /// The strength ranking of the high half of a hand, where greater ranks
+    /// are better.
+    type HiRank: Ord + Copy + fmt::Debug + Hash;
+
+    /// The strength ranking of the low half of a hand, where greater ranks
+    /// are better.
+    type LoRank: Ord + Copy + fmt::Debug + Hash;
+
+    /// Returns the ranks of the best high hand and of the best qualifying low
+    /// hand, if any, that can be made from the hole and board cards.
+    fn rank(&self, hole: &Hand, board: &Hand) -> (Self::HiRank, Option<Self::LoRank>);
+}
+
+/// Texas hold'em, ranked by [`poker_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Holdem;
+
+impl Variant for Holdem {
+    const HOLE_CARDS: RangeInclusive<usize> = 2..=2;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=5;
+}
+
+impl Evaluator for Holdem {
+    type Rank = PokerHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> PokerHandRank {
+        poker_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: PokerHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Short-deck hold'em, played with a 36-card deck of sixes through aces and
+/// ranked by [`short_deck_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct ShortDeckHoldem;
+
+impl Variant for ShortDeckHoldem {
+    const HOLE_CARDS: RangeInclusive<usize> = 2..=2;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=5;
+
+    fn deck_contains(card: &Card) -> bool {
+        card.rank() >= Rank::Six
+    }
+}
+
+impl Evaluator for ShortDeckHoldem {
+    type Rank = ShortDeckHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> ShortDeckHandRank {
+        short_deck_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: ShortDeckHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Omaha, with four to six hole cards, ranked by [`omaha_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Omaha;
+
+impl Variant for Omaha {
+    const HOLE_CARDS: RangeInclusive<usize> = 4..=6;
+    const BOARD_CARDS: RangeInclusive<usize> = 3..=5;
+}
+
+impl Evaluator for Omaha {
+    type Rank = PokerHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> PokerHandRank {
+        omaha_rank(hole, board)
+    }
+
+    fn category(&self, rank: PokerHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Omaha Hi/Lo, with four to six hole cards, ranked by [`omaha_rank`] and
+/// by the 8-or-better [`omaha_lo_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct OmahaHiLo;
+
+impl Variant for OmahaHiLo {
+    const HOLE_CARDS: RangeInclusive<usize> = 4..=6;
+    const BOARD_CARDS: RangeInclusive<usize> = 3..=5;
+}
+
+impl SplitEvaluator for OmahaHiLo {
+    type HiRank = PokerHandRank;
+    type LoRank = AceFiveHandRank;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> (PokerHandRank, Option<AceFiveHandRank>) {
+        let lo = omaha_lo_rank(hole, board).to_lo_8_rank();
+        (omaha_rank(hole, board), Some(lo).filter(|lo| lo.0 != 0))
+    }
+}
+
+/// Manila, played with a 32-card deck of sevens through aces and ranked by
+/// [`manila_rank`], with hands of too few cards ranked as `None`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Manila;
+
+impl Variant for Manila {
+    const HOLE_CARDS: RangeInclusive<usize> = 2..=2;
+    const BOARD_CARDS: RangeInclusive<usize> = 3..=5;
+
+    fn deck_contains(card: &Card) -> bool {
+        card.rank() >= Rank::Seven
+    }
+}
+
+impl Evaluator for Manila {
+    type Rank = Option<ShortDeckHandRank>;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> Option<ShortDeckHandRank> {
+        manila_rank(hole, board)
+    }
+
+    fn category(&self, rank: Option<ShortDeckHandRank>) -> PokerRankCategory {
+        rank.map_or(PokerRankCategory::Ineligible, |rank| rank.rank_category())
+    }
+}
+
+/// Seven-card stud, from third street to the river, ranked by
+/// [`poker_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Stud;
+
+impl Variant for Stud {
+    const HOLE_CARDS: RangeInclusive<usize> = 3..=7;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for Stud {
+    type Rank = PokerHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> PokerHandRank {
+        poker_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: PokerHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Seven-card stud Hi/Lo, ranked by [`poker_rank`] and by the 8-or-better
+/// [`ace_five_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct StudHiLo;
+
+impl Variant for StudHiLo {
+    const HOLE_CARDS: RangeInclusive<usize> = 3..=7;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl SplitEvaluator for StudHiLo {
+    type HiRank = PokerHandRank;
+    type LoRank = AceFiveHandRank;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> (PokerHandRank, Option<AceFiveHandRank>) {
+        let hand = combine(hole, board);
+        let lo = ace_five_rank(&hand).to_lo_8_rank();
+        (poker_rank(&hand), Some(lo).filter(|lo| lo.0 != 0))
+    }
+}
+
+/// Razz, i.e. seven-card stud ranked by [`ace_five_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Razz;
+
+impl Variant for Razz {
+    const HOLE_CARDS: RangeInclusive<usize> = 3..=7;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for Razz {
+    type Rank = AceFiveHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> AceFiveHandRank {
+        ace_five_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: AceFiveHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Five-card draw, ranked by [`poker_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct FiveCardDraw;
+
+impl Variant for FiveCardDraw {
+    const HOLE_CARDS: RangeInclusive<usize> = 5..=5;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for FiveCardDraw {
+    type Rank = PokerHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> PokerHandRank {
+        poker_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: PokerHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Deuce-seven lowball draw, ranked by [`deuce_seven_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct DeuceSeven;
+
+impl Variant for DeuceSeven {
+    const HOLE_CARDS: RangeInclusive<usize> = 5..=5;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for DeuceSeven {
+    type Rank = DeuceSevenHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> DeuceSevenHandRank {
+        deuce_seven_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: DeuceSevenHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Five-card draw with deuces wild, ranked by [`deuces_wild_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct DeucesWild;
+
+impl Variant for DeucesWild {
+    const HOLE_CARDS: RangeInclusive<usize> = 5..=5;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for DeucesWild {
+    type Rank = PokerHandRank;
+    type Category = PokerRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> PokerHandRank {
+        deuces_wild_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: PokerHandRank) -> PokerRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Badugi, ranked by [`badugi_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Badugi;
+
+impl Variant for Badugi {
+    const HOLE_CARDS: RangeInclusive<usize> = 4..=4;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for Badugi {
+    type Rank = BadugiHandRank;
+    type Category = BadugiRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> BadugiHandRank {
+        badugi_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: BadugiHandRank) -> BadugiRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Baduci, i.e. Badugi with aces playing high, ranked by [`baduci_rank`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Baduci;
+
+impl Variant for Baduci {
+    const HOLE_CARDS: RangeInclusive<usize> = 4..=4;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl Evaluator for Baduci {
+    type Rank = BaduciHandRank;
+    type Category = BadugiRankCategory;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> BaduciHandRank {
+        baduci_rank(&combine(hole, board))
+    }
+
+    fn category(&self, rank: BaduciHandRank) -> BadugiRankCategory {
+        rank.rank_category()
+    }
+}
+
+/// Badeucy, with five cards split between the best Baduci and deuce-seven
+/// lowball hands, as in [`badeucy_rank`](crate::badeucy_rank).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Badeucy;
+
+impl Variant for Badeucy {
+    const HOLE_CARDS: RangeInclusive<usize> = 5..=5;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl SplitEvaluator for Badeucy {
+    type HiRank = BaduciHandRank;
+    type LoRank = DeuceSevenHandRank;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> (BaduciHandRank, Option<DeuceSevenHandRank>) {
+        let hand = combine(hole, board);
+        (baduci_rank(&hand), Some(deuce_seven_rank(&hand)))
+    }
+}
+
+/// Badacey, with five cards split between the best Badugi and ace-five
+/// lowball hands, as in [`badacey_rank`](crate::badacey_rank).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Badacey;
+
+impl Variant for Badacey {
+    const HOLE_CARDS: RangeInclusive<usize> = 5..=5;
+    const BOARD_CARDS: RangeInclusive<usize> = 0..=0;
+}
+
+impl SplitEvaluator for Badacey {
+    type HiRank = BadugiHandRank;
+    type LoRank = AceFiveHandRank;
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> (BadugiHandRank, Option<AceFiveHandRank>) {
+        let hand = combine(hole, board);
+        (badugi_rank(&hand), Some(ace_five_rank(&hand)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        badacey_rank, badeucy_rank,
+        base::ParseError,
+        showdown::{resolve_showdown, Player},
+    };
+    use rstest::rstest;
+
+    fn check_counts<V: Variant>(hole: usize, board: usize) -> bool {
+        V::HOLE_CARDS.contains(&hole) && V::BOARD_CARDS.contains(&board)
+    }
+
+    #[test]
+    fn card_counts() {
+        assert!(check_counts::<Holdem>(2, 0));
+        assert!(!check_counts::<Holdem>(4, 3));
+        assert!(check_counts::<Omaha>(6, 5));
+        assert!(!check_counts::<Omaha>(4, 0));
+        assert!(check_counts::<Stud>(7, 0));
+        assert!(check_counts::<Badugi>(4, 0));
+        assert!(!check_counts::<Badacey>(4, 0));
+    }
+
+    #[test]
+    fn deck_sizes() {
+        assert_eq!(Holdem::deck(0).len(), 52);
+        assert_eq!(ShortDeckHoldem::deck(0).len(), 36);
+        assert_eq!(Manila::deck(0).len(), 32);
+        assert_eq!(Badugi::deck(0).len(), 52);
+    }
+
+    fn category_of<E: Evaluator>(
+        evaluator: E,
+        hole: &str,
+        board: &str,
+    ) -> Result<String, ParseError> {
+        let rank = evaluator.rank(&hole.parse()?, &board.parse()?);
+        Ok(evaluator.category(rank).to_string())
+    }
+
+    #[rstest]
+    #[case::holdem(category_of(Holdem, "Ah Kh", "Qh Jh Th 2c 3d"), "Royal Flush")]
+    #[case::short_deck(category_of(ShortDeckHoldem, "Ah Kh", "Qh 6h 7h Ac Ad"), "Flush")]
+    #[case::omaha(category_of(Omaha, "Ah Kd 2c 3c", "Qh Jh Th 9h 4d"), "Straight")]
+    #[case::manila(category_of(Manila, "Ah 9d", "Ts 8h 7h Kh Kc"), "Straight")]
+    #[case::manila_too_few_cards(category_of(Manila, "Ah 9d", "Ts 8h"), "Ineligible")]
+    #[case::stud(category_of(Stud, "9c 9d 9h 2c 2s 5d 7c", ""), "Full House")]
+    #[case::razz(category_of(Razz, "Ac 2d 3h 4s 6c Kd Kc", ""), "High Card")]
+    #[case::five_card_draw(category_of(FiveCardDraw, "Ah Ad Ac Kd Ks", ""), "Full House")]
+    #[case::deuce_seven(category_of(DeuceSeven, "2c 3d 4h 5s 6c", ""), "Straight")]
+    #[case::deuces_wild(category_of(DeucesWild, "2c 2d Ah As Kd", ""), "Four of a Kind")]
+    #[case::badugi(category_of(Badugi, "Ac 2d 3h 4s", ""), "Four Cards")]
+    #[case::baduci(category_of(Baduci, "Ac 2c 3h 4s", ""), "Three Cards")]
+    fn categories(
+        #[case] category: Result<String, ParseError>,
+        #[case] expected: &str,
+    ) -> Result<(), ParseError> {
+        assert_eq!(category?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Ah 7d", "Qh 9h 4h Tc 2d")]
+    #[case("Kc Kd", "Ks Kh 2c")]
+    #[case("7s 2c", "")]
+    fn evaluators_match_rank_functions(
+        #[case] hole: &str,
+        #[case] board: &str,
+    ) -> Result<(), ParseError> {
+        let (hole, board) = (hole.parse()?, board.parse()?);
+        assert_eq!(
+            Holdem.rank(&hole, &board),
+            poker_rank(&combine(&hole, &board))
+        );
+        assert_eq!(
+            Stud.rank(&hole, &board),
+            poker_rank(&combine(&hole, &board))
+        );
+        assert_eq!(
+            Razz.rank(&hole, &board),
+            ace_five_rank(&combine(&hole, &board))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn split_evaluators() -> Result<(), ParseError> {
+        let hand = "Kc 2d 3h 4s 7d".parse()?;
+        let empty = Hand::new();
+        let badeucy = badeucy_rank(&hand);
+        assert_eq!(
+            Badeucy.rank(&hand, &empty),
+            (badeucy.baduci, Some(badeucy.deuce_seven))
+        );
+        let badacey = badacey_rank(&hand);
+        assert_eq!(
+            Badacey.rank(&hand, &empty),
+            (badacey.badugi, Some(badacey.ace_five))
+        );
+
+        let board = "Ks 8d 7c 3h 2s".parse()?;
+        let (_, lo) = OmahaHiLo.rank(&"Ah 4d Tc 9d".parse()?, &board);
+        assert!(lo.is_some());
+        let (_, lo) = OmahaHiLo.rank(&"Kh Kd Tc 9d".parse()?, &board);
+        assert_eq!(lo, None);
+
+        let (hi, lo) = StudHiLo.rank(&"Ac 2d 3h 4s 8c 9c 9d".parse()?, &empty);
+        assert_eq!(hi.rank_category(), PokerRankCategory::Pair);
+        assert!(lo.is_some());
+        let (_, lo) = StudHiLo.rank(&"Ac 2d 3h 4s 9c 9d 9h".parse()?, &empty);
+        assert_eq!(lo, None);
+        Ok(())
+    }
+
+    fn generic_payouts<E: SplitEvaluator>(
+        evaluator: E,
+        holes: &[&str],
+        board: &str,
+    ) -> Result<Vec<u64>, ParseError> {
+        let board = board.parse()?;
+        let mut players = Vec::new();
+        for hole in holes {
+            let (hi, lo) = evaluator.rank(&hole.parse()?, &board);
+            players.push(Player::hi_lo(100, hi, lo));
+        }
+        Ok(resolve_showdown(&players).unwrap().payouts)
+    }
+
+    #[test]
+    fn generic_showdowns() -> Result<(), ParseError> {
+        let payouts =
+            generic_payouts(OmahaHiLo, &["Kc Kd Qh Jh", "Ah 4d Tc 9d"], "Ks 8d 7c 3h 2s")?;
+        assert_eq!(payouts, [100, 100]);
+
+        let payouts = generic_payouts(Badacey, &["Ac 2d 3h 4s 5c", "Kc Qd Jh Ts 9s"], "")?;
+        assert_eq!(payouts, [200, 0]);
+        Ok(())
+    }
+}
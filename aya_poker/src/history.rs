@@ -0,0 +1,1561 @@
+//! Parsing and export of hand histories in the PokerStars text format.
+//!
+//! A [`HandHistory`] holds the stakes, seats, cards and actions of a single
+//! hand of hold'em, Manila, Omaha or stud, and is parsed from and written back
+//! to the text format used by PokerStars and many other sites. Chat, table and
+//! connection messages are ignored when parsing, as is most of the summary,
+//! which is regenerated from the actions on export.
+//!
+//! Amounts are given in chips, or in cents when the stakes are in a
+//! currency. Hands played with the [`engine`] can be exported
+//! with [`HandHistory::from_engine`], and the showdown of any hand can be
+//! re-evaluated and checked against the collected pots with
+//! [`HandHistory::verify_showdown`].
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::history::HandHistory;
+//!
+//! let text = "\
+//! PokerStars Hand #1001:  Hold'em No Limit ($0.01/$0.02 USD) - 2020/01/01 12:00:00 ET
+//! Table 'Alpha' 6-max Seat #2 is the button
+//! Seat 1: Alice ($2 in chips)
+//! Seat 2: Bob ($2 in chips)
+//! Bob: posts small blind $0.01
+//! Alice: posts big blind $0.02
+//! *** HOLE CARDS ***
+//! Bob: raises $0.04 to $0.06
+//! Alice: folds
+//! Uncalled bet ($0.04) returned to Bob
+//! Bob collected $0.04 from pot
+//! Bob: doesn't show hand
+//! ";
+//!
+//! let hand: HandHistory = text.parse().unwrap();
+//! assert_eq!(hand.game.id, "holdem");
+//! assert_eq!(hand.stakes, (1, 2));
+//! assert_eq!(hand.contributions(), [2, 2]);
+//! assert_eq!(hand.collected(), [0, 4]);
+//! assert!(hand.verify_showdown().is_ok());
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use core::str::FromStr;
+
+use aya_base::{Card, Hand};
+
+use crate::{
+    engine::{self, BettingStructure, HandState},
+    registry::{find_game, GameDef, HandValue},
+    showdown::{resolve_showdown, Player, Showdown},
+};
+
+/// The names of the supported games in hand histories and the ids of their
+/// rules, with longer names first so that they are matched first.
+const GAME_NAMES: [(&str, &str); 11] = [
+    ("5 Card Omaha Hi/Lo", "omaha-5-hi-lo"),
+    ("7 Card Stud Hi/Lo", "stud-hi-lo"),
+    ("5 Card Omaha", "omaha-5"),
+    ("6 Card Omaha", "omaha-6"),
+    ("Omaha Hi/Lo", "omaha-hi-lo"),
+    ("7 Card Stud", "stud"),
+    ("6+ Hold'em", "short-deck"),
+    ("Hold'em", "holdem"),
+    ("Manila", "manila"),
+    ("Omaha", "omaha"),
+    ("Razz", "razz"),
+];
+
+pub(crate) const CURRENCIES: [(char, &str); 3] = [('$', "USD"), ('€', "EUR"), ('£', "GBP")];
+
+const COMMUNITY_STREETS: [&str; 4] = ["HOLE CARDS", "FLOP", "TURN", "RIVER"];
+
+const MANILA_STREETS: [&str; 5] = [
+    "FIRST CARD",
+    "SECOND CARD",
+    "THIRD CARD",
+    "FOURTH CARD",
+    "FIFTH CARD",
+];
+
+const STUD_STREETS: [&str; 5] = [
+    "3rd STREET",
+    "4th STREET",
+    "5th STREET",
+    "6th STREET",
+    "7th STREET",
+];
+
+/// The tournament a hand was played in.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Tournament {
+    /// The tournament id.
+    pub id: u64,
+    /// The buy-in, as written in the hand history.
+    pub buy_in: String,
+    /// The blind level, as written in the hand history.
+    pub level: String,
+}
+
+/// A player seated at the table.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct SeatInfo {
+    /// The number of the seat.
+    pub number: usize,
+    /// The name of the player.
+    pub name: String,
+    /// The chips of the player at the start of the hand.
+    pub stack: u64,
+}
+
+/// The kind of an action or event in a hand history.
+///
+/// The amounts of calls and bets, and the `by` amounts of raises, are the
+/// chips added by the action, while the amounts of completions and the `to`
+/// amounts of raises are the total bets of the player on the street.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub enum ActionKind {
+    /// Posts an ante.
+    PostAnte(u64),
+    /// Posts the small blind.
+    PostSmallBlind(u64),
+    /// Posts the big blind.
+    PostBigBlind(u64),
+    /// Brings in the betting in a stud game.
+    BringIn(u64),
+    /// Receives the given cards.
+    Dealt(Vec<Card>),
+    /// Folds.
+    Fold,
+    /// Checks.
+    Check,
+    /// Calls.
+    Call(u64),
+    /// Bets.
+    Bet(u64),
+    /// Raises.
+    Raise { by: u64, to: u64 },
+    /// Completes the bring-in to a full bet in a stud game.
+    Complete(u64),
+    /// Takes back the part of a bet no one called.
+    UncalledBet(u64),
+    /// Shows the given cards at showdown.
+    Show {
+        cards: Vec<Card>,
+        description: Option<String>,
+    },
+    /// Mucks at showdown.
+    Muck,
+    /// Collects chips from the pot.
+    Collect(u64),
+}
+
+/// An action or event in a hand history.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct HistoryAction {
+    /// The index of the street among the streets of the game.
+    pub street: usize,
+    /// The index of the player in [`HandHistory::seats`].
+    pub seat: usize,
+    /// The kind of the action.
+    pub kind: ActionKind,
+    /// Whether the action put the player all-in.
+    pub all_in: bool,
+}
+
+/// A hand parsed from or exported to a hand history.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct HandHistory {
+    /// The hand id.
+    pub id: u64,
+    /// The tournament the hand was played in, if any.
+    pub tournament: Option<Tournament>,
+    /// The rules of the game.
+    pub game: &'static GameDef,
+    /// The betting structure.
+    pub structure: BettingStructure,
+    /// The blinds in no-limit and pot-limit games, or the small and big bets
+    /// in fixed-limit games.
+    pub stakes: (u64, u64),
+    /// The currency symbol, if the hand was played for money, in which case
+    /// the amounts are in cents.
+    pub currency: Option<char>,
+    /// The date and time, as written in the hand history.
+    pub date: String,
+    /// The name of the table.
+    pub table: String,
+    /// The maximum number of players at the table.
+    pub max_seats: usize,
+    /// The number of the seat with the button, if any.
+    pub button: Option<usize>,
+    /// The players at the table.
+    pub seats: Vec<SeatInfo>,
+    /// The board cards, in the order they were dealt.
+    pub board: Vec<Card>,
+    /// The actions and events of the hand, in order.
+    pub actions: Vec<HistoryAction>,
+    /// The chips taken from the pot by the site.
+    pub rake: u64,
+}
+
+/// An error which can be returned when parsing or verifying a hand history.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HistoryError {
+    /// The hand history does not start with a hand header.
+    MissingHeader,
+    /// The hand header is malformed.
+    InvalidHeader,
+    /// The game is not supported.
+    UnsupportedGame,
+    /// The table line is malformed.
+    InvalidTable,
+    /// A seat line is malformed.
+    InvalidSeat,
+    /// An action or street line is malformed.
+    InvalidLine,
+    /// An amount is malformed.
+    InvalidAmount,
+    /// A card is malformed.
+    InvalidCard,
+    /// A line refers to a player who is not seated at the table.
+    UnknownPlayer,
+    /// The cards shown at showdown cannot be evaluated, or no player is
+    /// left in the hand.
+    InvalidShowdown,
+    /// The players who collected the pot are not the winners of the
+    /// showdown.
+    ShowdownMismatch,
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::MissingHeader => write!(f, "missing hand header"),
+            HistoryError::InvalidHeader => write!(f, "invalid hand header"),
+            HistoryError::UnsupportedGame => write!(f, "game is not supported"),
+            HistoryError::InvalidTable => write!(f, "invalid table line"),
+            HistoryError::InvalidSeat => write!(f, "invalid seat line"),
+            HistoryError::InvalidLine => write!(f, "invalid line"),
+            HistoryError::InvalidAmount => write!(f, "invalid amount"),
+            HistoryError::InvalidCard => write!(f, "invalid card"),
+            HistoryError::UnknownPlayer => write!(f, "unknown player"),
+            HistoryError::InvalidShowdown => write!(f, "invalid showdown"),
+            HistoryError::ShowdownMismatch => write!(f, "pot was not collected by the winners"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HistoryError {}
+
+/// Parses every hand in a text containing any number of hand histories.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::history::parse_histories;
+///
+/// let text = "\
+/// PokerStars Hand #1:  Hold'em No Limit (5/10) - 2020/01/01 12:00:00 ET
+/// Table 'Alpha' 2-max Seat #2 is the button
+/// Seat 1: Alice (1000 in chips)
+/// Seat 2: Bob (1000 in chips)
+/// Bob: posts small blind 5
+/// Alice: posts big blind 10
+/// *** HOLE CARDS ***
+/// Bob: folds
+/// Alice collected 10 from pot
+///
+/// PokerStars Hand #2:  Hold'em No Limit (5/10) - 2020/01/01 12:01:00 ET
+/// Table 'Alpha' 2-max Seat #1 is the button
+/// Seat 1: Alice (1005 in chips)
+/// Seat 2: Bob (995 in chips)
+/// Alice: posts small blind 5
+/// Bob: posts big blind 10
+/// *** HOLE CARDS ***
+/// Alice: folds
+/// Bob collected 10 from pot
+/// ";
+///
+/// let hands = parse_histories(text).unwrap();
+/// assert_eq!(hands.len(), 2);
+/// assert_eq!(hands[1].seats[0].stack, 1005);
+/// ```
+pub fn parse_histories(text: &str) -> Result<Vec<HandHistory>, HistoryError> {
+    let mut hands = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if is_header(line.trim()) {
+            if let Some(start) = start {
+                hands.push(text[start..offset].parse()?);
+            }
+            start = Some(offset);
+        }
+        offset += line.len();
+    }
+
+    match start {
+        Some(start) => hands.push(text[start..].parse()?),
+        None if text.trim().is_empty() => {}
+        None => return Err(HistoryError::MissingHeader),
+    }
+    Ok(hands)
+}
+
+impl HandHistory {
+    /// Converts a hand played with the [`engine`] to a hand
+    /// history.
+    ///
+    /// The players are named `Player 1`, `Player 2` and so on, in order of
+    /// position, and the hole cards of every player are included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::engine::{Action, BettingStructure, HandState, TableConfig};
+    /// use aya_poker::history::HandHistory;
+    /// use aya_poker::registry::find_game;
+    ///
+    /// let config = TableConfig::new(BettingStructure::NoLimit, 5, 10);
+    /// let mut hand = HandState::new(find_game("holdem").unwrap(), config, &[500, 500], 3).unwrap();
+    /// hand.act(Action::Raise(500)).unwrap();
+    /// hand.act(Action::Call).unwrap();
+    ///
+    /// let history = HandHistory::from_engine(&hand, 1);
+    /// let text = history.to_string();
+    /// assert!(text.contains("Player 2: raises 490 to 500 and is all-in"));
+    /// assert_eq!(text.parse::<HandHistory>().unwrap(), history);
+    /// ```
+    pub fn from_engine(hand: &HandState, id: u64) -> HandHistory {
+        let config = hand.config();
+        let n = hand.seats().len();
+        let payouts = match hand.result() {
+            Some(result) => result.payouts.clone(),
+            None => alloc::vec![0; n],
+        };
+        let stakes = match config.structure {
+            BettingStructure::FixedLimit => (config.big_blind, 2 * config.big_blind),
+            _ => (config.small_blind, config.big_blind),
+        };
+        let seats = hand
+            .seats()
+            .iter()
+            .zip(payouts.iter())
+            .enumerate()
+            .map(|(i, (seat, payout))| SeatInfo {
+                number: i + 1,
+                name: format!("Player {}", i + 1),
+                stack: seat.stack + seat.contribution - payout,
+            })
+            .collect();
+        let mut history = HandHistory {
+            id,
+            tournament: None,
+            game: hand.game(),
+            structure: config.structure,
+            stakes,
+            currency: None,
+            date: String::new(),
+            table: "aya".to_string(),
+            max_seats: n,
+            button: Some(n),
+            seats,
+            board: Vec::new(),
+            actions: Vec::new(),
+            rake: 0,
+        };
+
+        let mut street = 0;
+        let mut current_bet = config.big_blind;
+        for event in hand.history() {
+            let (seat, kind, all_in) = match *event {
+                engine::Event::Ante { seat, amount } => (seat, ActionKind::PostAnte(amount), false),
+                engine::Event::SmallBlind { seat, amount } => {
+                    (seat, ActionKind::PostSmallBlind(amount), false)
+                }
+                engine::Event::BigBlind { seat, amount } => {
+                    (seat, ActionKind::PostBigBlind(amount), false)
+                }
+                engine::Event::Street {
+                    street: index,
+                    cards,
+                } => {
+                    street = index;
+                    history.board.extend(cards.iter());
+                    if index == 0 {
+                        for (seat, player) in hand.seats().iter().enumerate() {
+                            history.push(street, seat, ActionKind::Dealt(cards_of(&player.hole)));
+                        }
+                    } else {
+                        current_bet = 0;
+                    }
+                    continue;
+                }
+                engine::Event::Action {
+                    seat,
+                    action,
+                    amount,
+                    all_in,
+                } => {
+                    let kind = match action {
+                        engine::Action::Fold => ActionKind::Fold,
+                        engine::Action::Check => ActionKind::Check,
+                        engine::Action::Call => ActionKind::Call(amount),
+                        engine::Action::Bet(to) => {
+                            current_bet = to;
+                            ActionKind::Bet(amount)
+                        }
+                        engine::Action::Raise(to) => {
+                            let by = to - current_bet;
+                            current_bet = to;
+                            ActionKind::Raise { by, to }
+                        }
+                    };
+                    (seat, kind, all_in)
+                }
+            };
+            history.actions.push(HistoryAction {
+                street,
+                seat,
+                kind,
+                all_in,
+            });
+        }
+
+        if hand.is_complete() {
+            let contributions = hand
+                .seats()
+                .iter()
+                .map(|s| s.contribution)
+                .collect::<Vec<_>>();
+            let mut returned = alloc::vec![0; n];
+            if let Some((top, uncalled)) = uncalled_bet(&contributions) {
+                returned[top] = uncalled;
+                history.push(street, top, ActionKind::UncalledBet(uncalled));
+            }
+            let live = hand.seats().iter().filter(|s| !s.folded).count();
+            if live > 1 {
+                for (seat, player) in hand.seats().iter().enumerate() {
+                    if !player.folded {
+                        let cards = cards_of(&player.hole);
+                        history.push(
+                            street,
+                            seat,
+                            ActionKind::Show {
+                                cards,
+                                description: None,
+                            },
+                        );
+                    }
+                }
+            }
+            for (seat, (payout, returned)) in payouts.iter().zip(returned.iter()).enumerate() {
+                if payout > returned {
+                    history.push(street, seat, ActionKind::Collect(payout - returned));
+                }
+            }
+        }
+        history
+    }
+
+    /// Returns the chips every player put in the pot, excluding uncalled
+    /// bets returned to them.
+    pub fn contributions(&self) -> Vec<u64> {
+        let mut totals = alloc::vec![0; self.seats.len()];
+        let mut street_bets = alloc::vec![0; self.seats.len()];
+        let mut street = 0;
+        for action in self.actions.iter() {
+            if action.street != street {
+                street = action.street;
+                street_bets.iter_mut().for_each(|bet| *bet = 0);
+            }
+            let (total, street_bet) = (&mut totals[action.seat], &mut street_bets[action.seat]);
+            match action.kind {
+                ActionKind::PostAnte(amount) => *total += amount,
+                ActionKind::PostSmallBlind(amount)
+                | ActionKind::PostBigBlind(amount)
+                | ActionKind::BringIn(amount)
+                | ActionKind::Call(amount)
+                | ActionKind::Bet(amount) => {
+                    *total += amount;
+                    *street_bet += amount;
+                }
+                ActionKind::Raise { to, .. } | ActionKind::Complete(to) => {
+                    *total += to.saturating_sub(*street_bet);
+                    *street_bet = to;
+                }
+                ActionKind::UncalledBet(amount) => *total = total.saturating_sub(amount),
+                _ => {}
+            }
+        }
+        totals
+    }
+
+    /// Returns the chips every player collected from the pot.
+    pub fn collected(&self) -> Vec<u64> {
+        let mut collected = alloc::vec![0; self.seats.len()];
+        for action in self.actions.iter() {
+            if let ActionKind::Collect(amount) = action.kind {
+                collected[action.seat] += amount;
+            }
+        }
+        collected
+    }
+
+    /// Returns the total number of chips in the pot, before the rake.
+    pub fn pot(&self) -> u64 {
+        self.contributions().iter().sum()
+    }
+
+    /// Re-evaluates the showdown from the shown cards and the board, and
+    /// checks that the players who collected chips are the winners of the
+    /// pots.
+    ///
+    /// Players who folded or mucked are not eligible to win, and a hand
+    /// which ended without a showdown is awarded to the last remaining
+    /// player. Since the rake is taken from the pots, only whether each
+    /// player won any chips is compared, rather than the amounts.
+    ///
+    /// Returns the recomputed showdown, with the players in the order of
+    /// [`HandHistory::seats`], or an error if the shown hands cannot be
+    /// evaluated or the pot was collected by other players.
+    pub fn verify_showdown(&self) -> Result<Showdown, HistoryError> {
+        let n = self.seats.len();
+        let contributions = self.contributions();
+        let mut folded = alloc::vec![false; n];
+        let mut shown = alloc::vec![None; n];
+        for action in self.actions.iter() {
+            match &action.kind {
+                ActionKind::Fold | ActionKind::Muck => folded[action.seat] = true,
+                ActionKind::Show { cards, .. } => shown[action.seat] = Some(cards),
+                _ => {}
+            }
+        }
+        for seat in 0..n {
+            folded[seat] |= contributions[seat] == 0;
+        }
+        let live = folded.iter().filter(|&&f| !f).count();
+
+        let board = self.board.iter().collect::<Hand>();
+        let mut players = Vec::with_capacity(n);
+        for seat in 0..n {
+            let player = match shown[seat] {
+                Some(cards) if live > 1 && !folded[seat] => {
+                    let hole = cards.iter().collect::<Hand>();
+                    let rank = self
+                        .game
+                        .evaluate(&hole, &board)
+                        .map_err(|_| HistoryError::InvalidShowdown)?;
+                    Player::hi_lo(contributions[seat], rank.hi, rank.lo)
+                }
+                _ => {
+                    let player = Player::hi_lo(contributions[seat], HandValue(0), None);
+                    if folded[seat] || live > 1 {
+                        player.fold()
+                    } else {
+                        player
+                    }
+                }
+            };
+            players.push(player);
+        }
+
+        let showdown = resolve_showdown(&players).map_err(|_| HistoryError::InvalidShowdown)?;
+        let collected = self.collected();
+        if (0..n).any(|seat| (showdown.payouts[seat] > 0) != (collected[seat] > 0)) {
+            return Err(HistoryError::ShowdownMismatch);
+        }
+        Ok(showdown)
+    }
+
+    fn push(&mut self, street: usize, seat: usize, kind: ActionKind) {
+        self.actions.push(HistoryAction {
+            street,
+            seat,
+            kind,
+            all_in: false,
+        });
+    }
+
+    fn is_stud(&self) -> bool {
+        self.game.board_cards() == 0
+    }
+
+    fn is_manila(&self) -> bool {
+        self.game.id == "manila"
+    }
+
+    /// Returns the names of the street markers of the game.
+    fn street_names(&self) -> &'static [&'static str] {
+        if self.is_stud() {
+            &STUD_STREETS
+        } else if self.is_manila() {
+            &MANILA_STREETS
+        } else {
+            &COMMUNITY_STREETS
+        }
+    }
+
+    fn amount(&self, amount: u64) -> Amount {
+        Amount(amount, self.currency)
+    }
+
+    /// Returns the index of the player whose name starts the line, followed
+    /// by a space or a colon, and the rest of the line.
+    fn split_player<'a>(&self, line: &'a str) -> Option<(usize, &'a str)> {
+        self.seats
+            .iter()
+            .enumerate()
+            .filter(|(_, seat)| {
+                line.strip_prefix(seat.name.as_str())
+                    .map_or(false, |rest| rest.starts_with(' ') || rest.starts_with(':'))
+            })
+            .max_by_key(|(_, seat)| seat.name.len())
+            .map(|(i, seat)| (i, &line[seat.name.len()..]))
+    }
+
+    fn find_player(&self, name: &str) -> Result<usize, HistoryError> {
+        self.seats
+            .iter()
+            .position(|seat| seat.name == name)
+            .ok_or(HistoryError::UnknownPlayer)
+    }
+
+    fn parse_line(&mut self, line: &str, street: usize) -> Result<(), HistoryError> {
+        if let Some(rest) = line.strip_prefix("Dealt to ") {
+            let i = rest.find(" [").ok_or(HistoryError::InvalidLine)?;
+            let (name, cards) = rest.split_at(i);
+            let seat = self.find_player(name)?;
+            let cards = brackets(cards).pop().ok_or(HistoryError::InvalidLine)?;
+            let kind = ActionKind::Dealt(parse_cards(cards)?);
+            self.push(street, seat, kind);
+        } else if let Some(rest) = line.strip_prefix("Uncalled bet (") {
+            let (amount, name) = rest
+                .split_once(") returned to ")
+                .ok_or(HistoryError::InvalidLine)?;
+            let seat = self.find_player(name)?;
+            let kind = ActionKind::UncalledBet(self.parse_amount(amount)?);
+            self.push(street, seat, kind);
+        } else if let Some((seat, rest)) = self.split_player(line) {
+            if let Some(rest) = rest.strip_prefix(" collected ") {
+                let amount = rest.split(' ').next().ok_or(HistoryError::InvalidLine)?;
+                let kind = ActionKind::Collect(self.parse_amount(amount)?);
+                self.push(street, seat, kind);
+            } else if let Some(rest) = rest.strip_prefix(": ") {
+                let (rest, all_in) = match rest.strip_suffix(" and is all-in") {
+                    Some(rest) => (rest, true),
+                    None => (rest, false),
+                };
+                if let Some(kind) = self.parse_action(rest)? {
+                    self.actions.push(HistoryAction {
+                        street,
+                        seat,
+                        kind,
+                        all_in,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_action(&self, action: &str) -> Result<Option<ActionKind>, HistoryError> {
+        let (verb, rest) = action.split_once(' ').unwrap_or((action, ""));
+        let kind = match verb {
+            "folds" => ActionKind::Fold,
+            "checks" => ActionKind::Check,
+            "calls" => ActionKind::Call(self.parse_amount(rest)?),
+            "bets" => ActionKind::Bet(self.parse_amount(rest)?),
+            "raises" => {
+                let (by, to) = rest.split_once(" to ").ok_or(HistoryError::InvalidLine)?;
+                ActionKind::Raise {
+                    by: self.parse_amount(by)?,
+                    to: self.parse_amount(to)?,
+                }
+            }
+            "posts" => {
+                if let Some(amount) = rest.strip_prefix("small blind ") {
+                    ActionKind::PostSmallBlind(self.parse_amount(amount)?)
+                } else if let Some(amount) = rest.strip_prefix("big blind ") {
+                    ActionKind::PostBigBlind(self.parse_amount(amount)?)
+                } else if let Some(amount) = rest.strip_prefix("the ante ") {
+                    ActionKind::PostAnte(self.parse_amount(amount)?)
+                } else {
+                    return Err(HistoryError::InvalidLine);
+                }
+            }
+            "brings" => {
+                let amount = rest
+                    .strip_prefix("in for ")
+                    .ok_or(HistoryError::InvalidLine)?;
+                ActionKind::BringIn(self.parse_amount(amount)?)
+            }
+            "completes" => {
+                let amount = rest
+                    .strip_prefix("it to ")
+                    .ok_or(HistoryError::InvalidLine)?;
+                ActionKind::Complete(self.parse_amount(amount)?)
+            }
+            "shows" => {
+                let (cards, description) = rest
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.split_once(']'))
+                    .ok_or(HistoryError::InvalidLine)?;
+                let description = description
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|d| d.strip_suffix(')'))
+                    .map(String::from);
+                ActionKind::Show {
+                    cards: parse_cards(cards)?,
+                    description,
+                }
+            }
+            "mucks" => ActionKind::Muck,
+            _ => return Ok(None),
+        };
+        Ok(Some(kind))
+    }
+
+    fn parse_amount(&self, amount: &str) -> Result<u64, HistoryError> {
+        let amount = amount.trim();
+        let amount = match self.currency {
+            Some(symbol) => amount.strip_prefix(symbol).unwrap_or(amount),
+            None => amount,
+        };
+        let (whole, cents) = match amount.split_once('.') {
+            Some((whole, cents)) => (whole, Some(cents)),
+            None => (amount, None),
+        };
+        let whole = whole
+            .parse::<u64>()
+            .map_err(|_| HistoryError::InvalidAmount)?;
+        match (self.currency, cents) {
+            (None, None) => Ok(whole),
+            (Some(_), None) => Ok(100 * whole),
+            (Some(_), Some(cents)) if cents.len() <= 2 => {
+                let value = cents
+                    .parse::<u64>()
+                    .map_err(|_| HistoryError::InvalidAmount)?;
+                let value = if cents.len() == 1 { 10 * value } else { value };
+                Ok(100 * whole + value)
+            }
+            _ => Err(HistoryError::InvalidAmount),
+        }
+    }
+
+    fn street_marker(&self, street: usize) -> Result<String, HistoryError> {
+        let name = self
+            .street_names()
+            .get(street)
+            .ok_or(HistoryError::InvalidLine)?;
+        let dealt = self.game.streets[..street]
+            .iter()
+            .map(|s| s.board_cards)
+            .sum::<usize>();
+        let new = self.game.streets[street].board_cards;
+        let marker = match (self.board.get(..dealt), self.board.get(dealt..dealt + new)) {
+            _ if new == 0 => format!("{} ***", name),
+            (Some([]), Some(new)) => format!("{} *** [{}]", name, Cards(new)),
+            (Some(old), Some(new)) => format!("{} *** [{}] [{}]", name, Cards(old), Cards(new)),
+            _ => format!("{} ***", name),
+        };
+        Ok(marker)
+    }
+}
+
+impl FromStr for HandHistory {
+    type Err = HistoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header = lines.next().ok_or(HistoryError::MissingHeader)?;
+        let mut hand = parse_header(header)?;
+        let table = lines.next().ok_or(HistoryError::InvalidTable)?;
+        parse_table(&mut hand, table)?;
+
+        let mut street = 0;
+        let mut started = false;
+        let mut summary = false;
+        for line in lines {
+            if let Some(marker) = line.strip_prefix("*** ") {
+                let (name, cards) = marker.split_once(" ***").ok_or(HistoryError::InvalidLine)?;
+                started = true;
+                match name {
+                    "SHOW DOWN" => {}
+                    "SUMMARY" => summary = true,
+                    _ => {
+                        street = hand
+                            .street_names()
+                            .iter()
+                            .position(|&m| m == name)
+                            .ok_or(HistoryError::InvalidLine)?;
+                        if let Some(cards) = brackets(cards).pop() {
+                            hand.board.extend(parse_cards(cards)?);
+                        }
+                    }
+                }
+            } else if summary {
+                if let Some((_, rake)) = line.split_once("| Rake ") {
+                    hand.rake = hand.parse_amount(rake)?;
+                }
+            } else if !started && line.starts_with("Seat ") {
+                hand.seats.push(parse_seat(&hand, line)?);
+            } else {
+                hand.parse_line(line, street)?;
+            }
+        }
+        Ok(hand)
+    }
+}
+
+impl fmt::Display for HandHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = GAME_NAMES
+            .iter()
+            .find(|(_, id)| *id == self.game.id)
+            .map_or(self.game.name, |(name, _)| name);
+        let structure = match self.structure {
+            BettingStructure::FixedLimit => "Limit",
+            BettingStructure::PotLimit => "Pot Limit",
+            BettingStructure::NoLimit => "No Limit",
+        };
+        let (small, big) = (self.amount(self.stakes.0), self.amount(self.stakes.1));
+
+        write!(f, "PokerStars Hand #{}: ", self.id)?;
+        match &self.tournament {
+            Some(t) => write!(
+                f,
+                "Tournament #{}, {} {} {} - Level {} ({}/{})",
+                t.id, t.buy_in, game, structure, t.level, small, big
+            )?,
+            None => {
+                write!(f, " {} {} ({}/{}", game, structure, small, big)?;
+                if let Some((_, code)) = CURRENCIES.iter().find(|(c, _)| Some(*c) == self.currency)
+                {
+                    write!(f, " {}", code)?;
+                }
+                write!(f, ")")?;
+            }
+        }
+        if !self.date.is_empty() {
+            write!(f, " - {}", self.date)?;
+        }
+        writeln!(f)?;
+
+        write!(f, "Table '{}' {}-max", self.table, self.max_seats)?;
+        if let Some(button) = self.button {
+            write!(f, " Seat #{} is the button", button)?;
+        }
+        writeln!(f)?;
+        for seat in self.seats.iter() {
+            let stack = self.amount(seat.stack);
+            writeln!(
+                f,
+                "Seat {}: {} ({} in chips)",
+                seat.number, seat.name, stack
+            )?;
+        }
+
+        let mut known = alloc::vec![Vec::new(); self.seats.len()];
+        let mut street = None;
+        let mut showdown = false;
+        for action in self.actions.iter() {
+            let is_post = matches!(
+                action.kind,
+                ActionKind::PostAnte(_)
+                    | ActionKind::PostSmallBlind(_)
+                    | ActionKind::PostBigBlind(_)
+            );
+            if !(is_post && street.is_none()) {
+                while street.map_or(true, |s| s < action.street) {
+                    let next = street.map_or(0, |s| s + 1);
+                    let marker = self.street_marker(next).map_err(|_| fmt::Error)?;
+                    writeln!(f, "*** {}", marker)?;
+                    street = Some(next);
+                }
+            }
+            if matches!(action.kind, ActionKind::Show { .. } | ActionKind::Muck) && !showdown {
+                writeln!(f, "*** SHOW DOWN ***")?;
+                showdown = true;
+            }
+
+            let name = &self.seats[action.seat].name;
+            match &action.kind {
+                ActionKind::Dealt(cards) => {
+                    let previous = &mut known[action.seat];
+                    if previous.is_empty() {
+                        writeln!(f, "Dealt to {} [{}]", name, Cards(cards))?;
+                    } else {
+                        writeln!(
+                            f,
+                            "Dealt to {} [{}] [{}]",
+                            name,
+                            Cards(previous),
+                            Cards(cards)
+                        )?;
+                    }
+                    previous.extend(cards.iter().copied());
+                    continue;
+                }
+                ActionKind::UncalledBet(amount) => {
+                    let amount = self.amount(*amount);
+                    writeln!(f, "Uncalled bet ({}) returned to {}", amount, name)?;
+                    continue;
+                }
+                ActionKind::Collect(amount) => {
+                    let amount = self.amount(*amount);
+                    writeln!(f, "{} collected {} from pot", name, amount)?;
+                    continue;
+                }
+                _ => {}
+            }
+
+            write!(f, "{}: ", name)?;
+            match &action.kind {
+                ActionKind::PostAnte(amount) => {
+                    write!(f, "posts the ante {}", self.amount(*amount))?
+                }
+                ActionKind::PostSmallBlind(amount) => {
+                    write!(f, "posts small blind {}", self.amount(*amount))?
+                }
+                ActionKind::PostBigBlind(amount) => {
+                    write!(f, "posts big blind {}", self.amount(*amount))?
+                }
+                ActionKind::BringIn(amount) => write!(f, "brings in for {}", self.amount(*amount))?,
+                ActionKind::Fold => write!(f, "folds")?,
+                ActionKind::Check => write!(f, "checks")?,
+                ActionKind::Call(amount) => write!(f, "calls {}", self.amount(*amount))?,
+                ActionKind::Bet(amount) => write!(f, "bets {}", self.amount(*amount))?,
+                ActionKind::Raise { by, to } => {
+                    write!(f, "raises {} to {}", self.amount(*by), self.amount(*to))?
+                }
+                ActionKind::Complete(to) => write!(f, "completes it to {}", self.amount(*to))?,
+                ActionKind::Show { cards, description } => {
+                    write!(f, "shows [{}]", Cards(cards))?;
+                    if let Some(description) = description {
+                        write!(f, " ({})", description)?;
+                    }
+                }
+                ActionKind::Muck => write!(f, "mucks hand")?,
+                ActionKind::Dealt(_) | ActionKind::UncalledBet(_) | ActionKind::Collect(_) => {}
+            }
+            if action.all_in {
+                write!(f, " and is all-in")?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "*** SUMMARY ***")?;
+        writeln!(
+            f,
+            "Total pot {} | Rake {}",
+            self.amount(self.pot()),
+            self.amount(self.rake)
+        )?;
+        if !self.board.is_empty() {
+            writeln!(f, "Board [{}]", Cards(&self.board))?;
+        }
+        let collected = self.collected();
+        for (i, seat) in self.seats.iter().enumerate() {
+            write!(f, "Seat {}: {}", seat.number, seat.name)?;
+            if self.button == Some(seat.number) {
+                write!(f, " (button)")?;
+            }
+            for action in self.actions.iter().filter(|a| a.seat == i) {
+                match action.kind {
+                    ActionKind::PostSmallBlind(_) => write!(f, " (small blind)")?,
+                    ActionKind::PostBigBlind(_) => write!(f, " (big blind)")?,
+                    _ => {}
+                }
+            }
+            for action in self.actions.iter().filter(|a| a.seat == i) {
+                match &action.kind {
+                    ActionKind::Fold if self.is_stud() => {
+                        let street = STUD_STREETS[action.street.min(4)];
+                        write!(f, " folded on the {} Street", &street[..3])?;
+                    }
+                    ActionKind::Fold if action.street == 0 && !self.is_manila() => {
+                        write!(f, " folded before Flop")?
+                    }
+                    ActionKind::Fold => {
+                        let names = self.street_names();
+                        let street = names[action.street.min(names.len() - 1)];
+                        write!(f, " folded on the {}", title_case(street))?;
+                    }
+                    ActionKind::Show { cards, .. } if collected[i] > 0 => write!(
+                        f,
+                        " showed [{}] and won ({})",
+                        Cards(cards),
+                        self.amount(collected[i])
+                    )?,
+                    ActionKind::Show { cards, .. } => {
+                        write!(f, " showed [{}] and lost", Cards(cards))?
+                    }
+                    ActionKind::Muck => write!(f, " mucked")?,
+                    _ => continue,
+                }
+                break;
+            }
+            let summarized = self.actions.iter().any(|a| {
+                a.seat == i
+                    && matches!(
+                        a.kind,
+                        ActionKind::Fold | ActionKind::Show { .. } | ActionKind::Muck
+                    )
+            });
+            if !summarized && collected[i] > 0 {
+                write!(f, " collected ({})", self.amount(collected[i]))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An amount of chips, or of cents if there is a currency symbol.
+struct Amount(u64, Option<char>);
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            None => write!(f, "{}", self.0),
+            Some(symbol) if self.0 % 100 == 0 => write!(f, "{}{}", symbol, self.0 / 100),
+            Some(symbol) => write!(f, "{}{}.{:02}", symbol, self.0 / 100, self.0 % 100),
+        }
+    }
+}
+
+/// A list of cards separated by spaces.
+struct Cards<'a>(&'a [Card]);
+
+impl fmt::Display for Cards<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, card) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:?}", card)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_header(line: &str) -> bool {
+    line.starts_with("PokerStars Hand #") || line.starts_with("PokerStars Game #")
+}
+
+fn parse_header(line: &str) -> Result<HandHistory, HistoryError> {
+    let rest = line
+        .strip_prefix("PokerStars Hand #")
+        .or_else(|| line.strip_prefix("PokerStars Game #"))
+        .ok_or(HistoryError::MissingHeader)?;
+    let (id, rest) = rest.split_once(':').ok_or(HistoryError::InvalidHeader)?;
+    let id = id.parse().map_err(|_| HistoryError::InvalidHeader)?;
+    let (head, date) = match rest.rfind(") - ") {
+        Some(i) => (&rest[..=i], rest[i + 4..].trim()),
+        None => (rest, ""),
+    };
+
+    let head = head.trim();
+    let open = head.rfind('(').ok_or(HistoryError::InvalidHeader)?;
+    let stakes = head[open + 1..]
+        .strip_suffix(')')
+        .ok_or(HistoryError::InvalidHeader)?;
+    let mut head = head[..open].trim_end();
+    let mut level = "";
+    if let Some(i) = head.rfind(" - Level ") {
+        level = &head[i + 9..];
+        head = &head[..i];
+    }
+
+    let (head, structure) = if let Some(head) = head.strip_suffix(" No Limit") {
+        (head, BettingStructure::NoLimit)
+    } else if let Some(head) = head.strip_suffix(" Pot Limit") {
+        (head, BettingStructure::PotLimit)
+    } else if let Some(head) = head.strip_suffix(" Limit") {
+        (head, BettingStructure::FixedLimit)
+    } else {
+        return Err(HistoryError::UnsupportedGame);
+    };
+    let (name, id_in_registry) = GAME_NAMES
+        .iter()
+        .find(|(name, _)| head.ends_with(name))
+        .ok_or(HistoryError::UnsupportedGame)?;
+    let game = find_game(id_in_registry).ok_or(HistoryError::UnsupportedGame)?;
+
+    let prefix = head[..head.len() - name.len()].trim();
+    let tournament = match prefix.strip_prefix("Tournament #") {
+        Some(rest) => {
+            let (tid, buy_in) = rest.split_once(',').ok_or(HistoryError::InvalidHeader)?;
+            Some(Tournament {
+                id: tid.parse().map_err(|_| HistoryError::InvalidHeader)?,
+                buy_in: buy_in.trim().to_string(),
+                level: level.to_string(),
+            })
+        }
+        None if prefix.is_empty() => None,
+        None => return Err(HistoryError::InvalidHeader),
+    };
+
+    let stakes = stakes.split(' ').next().unwrap_or("");
+    let currency = stakes
+        .chars()
+        .next()
+        .filter(|c| CURRENCIES.iter().any(|(symbol, _)| symbol == c));
+    let mut hand = HandHistory {
+        id,
+        tournament,
+        game,
+        structure,
+        stakes: (0, 0),
+        currency,
+        date: date.to_string(),
+        table: String::new(),
+        max_seats: 0,
+        button: None,
+        seats: Vec::new(),
+        board: Vec::new(),
+        actions: Vec::new(),
+        rake: 0,
+    };
+    let (small, big) = stakes.split_once('/').ok_or(HistoryError::InvalidHeader)?;
+    hand.stakes = (hand.parse_amount(small)?, hand.parse_amount(big)?);
+    Ok(hand)
+}
+
+fn parse_table(hand: &mut HandHistory, line: &str) -> Result<(), HistoryError> {
+    let rest = line
+        .strip_prefix("Table '")
+        .ok_or(HistoryError::InvalidTable)?;
+    let (name, rest) = rest.rsplit_once("' ").ok_or(HistoryError::InvalidTable)?;
+    let max_seats = rest
+        .split(' ')
+        .next()
+        .and_then(|max| max.strip_suffix("-max"))
+        .and_then(|max| max.parse().ok())
+        .ok_or(HistoryError::InvalidTable)?;
+    let button = match rest.split_once("Seat #") {
+        Some((_, button)) => {
+            let button = button.split(' ').next().unwrap_or("");
+            Some(button.parse().map_err(|_| HistoryError::InvalidTable)?)
+        }
+        None => None,
+    };
+
+    hand.table = name.to_string();
+    hand.max_seats = max_seats;
+    hand.button = button;
+    Ok(())
+}
+
+fn parse_seat(hand: &HandHistory, line: &str) -> Result<SeatInfo, HistoryError> {
+    let (number, rest) = line["Seat ".len()..]
+        .split_once(": ")
+        .ok_or(HistoryError::InvalidSeat)?;
+    let number = number.parse().map_err(|_| HistoryError::InvalidSeat)?;
+    let end = rest.rfind(" in chips").ok_or(HistoryError::InvalidSeat)?;
+    let (name, stack) = rest[..end]
+        .rsplit_once(" (")
+        .ok_or(HistoryError::InvalidSeat)?;
+    Ok(SeatInfo {
+        number,
+        name: name.to_string(),
+        stack: hand.parse_amount(stack)?,
+    })
+}
+
+/// Returns the contents of every pair of square brackets in the text.
+fn brackets(text: &str) -> Vec<&str> {
+    text.split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']').map(|(inside, _)| inside))
+        .collect()
+}
+
+pub(crate) fn parse_cards(text: &str) -> Result<Vec<Card>, HistoryError> {
+    text.split_whitespace()
+        .map(|card| card.parse().map_err(|_| HistoryError::InvalidCard))
+        .collect()
+}
+
+fn cards_of(hand: &Hand) -> Vec<Card> {
+    hand.iter().copied().collect()
+}
+
+/// Returns the player whose bet was not called and the uncalled amount, if
+/// any.
+pub(crate) fn uncalled_bet(contributions: &[u64]) -> Option<(usize, u64)> {
+    let (top, &max) = contributions.iter().enumerate().max_by_key(|(_, &c)| c)?;
+    let second = contributions
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != top)
+        .map(|(_, &c)| c)
+        .max()
+        .unwrap_or(0);
+    (max > second).then_some((top, max - second))
+}
+
+fn title_case(street: &str) -> String {
+    let words: Vec<_> = street
+        .split(' ')
+        .map(|word| {
+            let mut word = word.to_ascii_lowercase();
+            word[..1].make_ascii_uppercase();
+            word
+        })
+        .collect();
+    words.join(" ")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::engine::{Action, TableConfig};
+    use rstest::rstest;
+
+    pub(crate) const HOLDEM: &str = "\
+PokerStars Hand #208437101355:  Hold'em No Limit ($0.01/$0.02 USD) - 2020/01/01 12:00:00 ET
+Table 'Alpha' 6-max Seat #1 is the button
+Seat 1: Alice ($2 in chips)
+Seat 2: Bob ($2.15 in chips)
+Seat 3: Carol ($1.98 in chips)
+Bob: posts small blind $0.01
+Carol: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to Alice [Ah Kd]
+Alice: raises $0.04 to $0.06
+Bob: folds
+Carol: calls $0.04
+*** FLOP *** [2c 7d Jh]
+Carol: checks
+Alice: bets $0.08
+Carol: calls $0.08
+*** TURN *** [2c 7d Jh] [Qs]
+Carol: checks
+Alice: checks
+*** RIVER *** [2c 7d Jh Qs] [3h]
+Carol: bets $0.10
+Alice: calls $0.10
+*** SHOW DOWN ***
+Carol: shows [7c 7h] (three of a kind, Sevens)
+Alice: mucks hand
+Carol collected $0.48 from pot
+*** SUMMARY ***
+Total pot $0.49 | Rake $0.01
+Board [2c 7d Jh Qs 3h]
+Seat 1: Alice (button) mucked
+Seat 2: Bob (small blind) folded before Flop
+Seat 3: Carol (big blind) showed [7c 7h] and won ($0.48)
+";
+
+    pub(crate) const STUD: &str = "\
+PokerStars Hand #208437200001:  7 Card Stud Limit ($0.04/$0.08 USD) - 2020/01/01 12:05:00 ET
+Table 'Beta' 8-max
+Seat 1: Dave ($1 in chips)
+Seat 2: Erin ($1.20 in chips)
+Dave: posts the ante $0.01
+Erin: posts the ante $0.01
+*** 3rd STREET ***
+Dealt to Dave [4c 5c 9d]
+Dealt to Erin [Kd]
+Erin: brings in for $0.02
+Dave: completes it to $0.04
+Erin: calls $0.02
+*** 4th STREET ***
+Dealt to Dave [4c 5c 9d] [Th]
+Dealt to Erin [Kd] [Ks]
+Erin: bets $0.04
+Dave: calls $0.04
+*** 5th STREET ***
+Dealt to Dave [4c 5c 9d Th] [6c]
+Dealt to Erin [Kd Ks] [2h]
+Erin: bets $0.08
+Dave: raises $0.08 to $0.16
+Erin: calls $0.08
+*** 6th STREET ***
+Dealt to Dave [4c 5c 9d Th 6c] [7s]
+Dealt to Erin [Kd Ks 2h] [3d]
+Erin: checks
+Dave: bets $0.08
+Erin: calls $0.08
+*** 7th STREET ***
+Dealt to Dave [4c 5c 9d Th 6c 7s] [8s]
+Erin: checks
+Dave: checks
+*** SHOW DOWN ***
+Erin: shows [Qc Jd Kd Ks 2h 3d 8h] (a pair of Kings)
+Dave: shows [4c 5c 9d Th 6c 7s 8s] (a straight, Six to Ten)
+Dave collected $0.63 from pot
+*** SUMMARY ***
+Total pot $0.66 | Rake $0.03
+Seat 1: Dave showed [4c 5c 9d Th 6c 7s 8s] and won ($0.63)
+Seat 2: Erin showed [Qc Jd Kd Ks 2h 3d 8h] and lost
+";
+
+    pub(crate) const OMAHA_HI_LO: &str = "\
+PokerStars Hand #3:  Omaha Hi/Lo Pot Limit ($0.05/$0.10 USD) - 2020/01/01 12:10:00 ET
+Table 'Gamma' 6-max Seat #3 is the button
+Seat 1: Ann ($10 in chips)
+Seat 2: Ben ($10 in chips)
+Seat 3: Cat ($10 in chips)
+Ann: posts small blind $0.05
+Ben: posts big blind $0.10
+*** HOLE CARDS ***
+Cat: calls $0.10
+Ann: calls $0.05
+Ben: checks
+*** FLOP *** [Ks 8d 7c]
+Ann: checks
+Ben: checks
+Cat: checks
+*** TURN *** [Ks 8d 7c] [3h]
+Ann: checks
+Ben: checks
+Cat: checks
+*** RIVER *** [Ks 8d 7c 3h] [2s]
+Ann: checks
+Ben: checks
+Cat: checks
+*** SHOW DOWN ***
+Ann: shows [Ah 4d Tc 9d] (HI: high card King; LO: 7,4,3,2,A)
+Ben: shows [Kh Kd Qc Jd] (HI: three of a kind, Kings)
+Cat: shows [6h 5d Qs Qd] (HI: a pair of Queens; LO: 7,6,5,3,2)
+Ben collected $0.15 from pot
+Ann collected $0.14 from pot
+*** SUMMARY ***
+Total pot $0.30 | Rake $0.01
+Board [Ks 8d 7c 3h 2s]
+Seat 1: Ann (small blind) showed [Ah 4d Tc 9d] and won ($0.14)
+Seat 2: Ben (big blind) showed [Kh Kd Qc Jd] and won ($0.15)
+Seat 3: Cat (button) showed [6h 5d Qs Qd] and lost
+";
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn parses_holdem() {
+        let hand: HandHistory = HOLDEM.parse().unwrap();
+        assert_eq!(hand.id, 208437101355);
+        assert_eq!(hand.game.id, "holdem");
+        assert_eq!(hand.structure, BettingStructure::NoLimit);
+        assert_eq!(hand.stakes, (1, 2));
+        assert_eq!(hand.currency, Some('$'));
+        assert_eq!(hand.date, "2020/01/01 12:00:00 ET");
+        assert_eq!(hand.table, "Alpha");
+        assert_eq!(hand.max_seats, 6);
+        assert_eq!(hand.button, Some(1));
+        assert_eq!(hand.seats[1].name, "Bob");
+        assert_eq!(hand.seats[1].stack, 215);
+        assert_eq!(hand.board, cards("2c 7d Jh Qs 3h"));
+        assert_eq!(hand.rake, 1);
+        assert_eq!(
+            hand.actions[3],
+            HistoryAction {
+                street: 0,
+                seat: 0,
+                kind: ActionKind::Raise { by: 4, to: 6 },
+                all_in: false,
+            }
+        );
+        assert_eq!(hand.contributions(), [24, 1, 24]);
+        assert_eq!(hand.pot(), 49);
+        assert_eq!(hand.collected(), [0, 0, 48]);
+    }
+
+    #[test]
+    fn parses_stud() {
+        let hand: HandHistory = STUD.parse().unwrap();
+        assert_eq!(hand.game.id, "stud");
+        assert_eq!(hand.structure, BettingStructure::FixedLimit);
+        assert_eq!(hand.button, None);
+        assert!(hand.board.is_empty());
+        assert_eq!(hand.actions[3].kind, ActionKind::Dealt(cards("Kd")));
+        assert_eq!(hand.actions[4].kind, ActionKind::BringIn(2));
+        assert_eq!(hand.actions[5].kind, ActionKind::Complete(4));
+        assert_eq!(hand.actions[7].street, 1);
+        assert_eq!(hand.actions[7].kind, ActionKind::Dealt(cards("Th")));
+        assert_eq!(hand.contributions(), [33, 33]);
+    }
+
+    #[rstest]
+    #[case(HOLDEM)]
+    #[case(STUD)]
+    #[case(OMAHA_HI_LO)]
+    fn exports_in_same_format(#[case] text: &str) {
+        let hand: HandHistory = text.parse().unwrap();
+        assert_eq!(hand.to_string(), text);
+    }
+
+    #[test]
+    fn verifies_showdowns() {
+        let hand: HandHistory = HOLDEM.parse().unwrap();
+        assert_eq!(hand.verify_showdown().unwrap().payouts, [0, 0, 49]);
+
+        let hand: HandHistory = STUD.parse().unwrap();
+        assert_eq!(hand.verify_showdown().unwrap().payouts, [66, 0]);
+
+        let hand: HandHistory = OMAHA_HI_LO.parse().unwrap();
+        let showdown = hand.verify_showdown().unwrap();
+        assert_eq!(showdown.pots[0].hi_winners, [1]);
+        assert_eq!(showdown.pots[0].lo_winners, [0]);
+        assert_eq!(showdown.payouts, [15, 15, 0]);
+    }
+
+    #[test]
+    fn detects_wrong_winners() {
+        let mut hand: HandHistory = OMAHA_HI_LO.parse().unwrap();
+        let collect = hand.actions.len() - 1;
+        hand.actions[collect].seat = 2;
+        assert_eq!(hand.verify_showdown(), Err(HistoryError::ShowdownMismatch));
+    }
+
+    #[test]
+    fn parses_tournaments_and_ignores_chatter() {
+        let text = "\
+PokerStars Hand #77: Tournament #123, $1+$0.10 USD Hold'em No Limit - Level II (15/30) - 2020/01/01 13:00:00 ET
+Table '123 1' 9-max Seat #2 is the button
+Seat 1: Fred (1500 in chips)
+Seat 2: Gina (1500 in chips) is sitting out
+Seat 3: Fred Jr (1470 in chips)
+Fred Jr: posts small blind 15
+Fred: posts big blind 30
+*** HOLE CARDS ***
+Fred said, \"gl\"
+Gina: is sitting out
+Fred Jr: raises 1440 to 1470 and is all-in
+Fred: folds
+Uncalled bet (1440) returned to Fred Jr
+Fred Jr collected 60 from pot
+Fred Jr: doesn't show hand
+";
+        let hand: HandHistory = text.parse().unwrap();
+        let tournament = hand.tournament.as_ref().unwrap();
+        assert_eq!(tournament.id, 123);
+        assert_eq!(tournament.buy_in, "$1+$0.10 USD");
+        assert_eq!(tournament.level, "II");
+        assert_eq!(hand.currency, None);
+        assert_eq!(hand.stakes, (15, 30));
+        assert_eq!(hand.table, "123 1");
+        assert_eq!(hand.actions.len(), 6);
+        assert!(hand.actions[2].all_in);
+        assert_eq!(hand.contributions(), [30, 0, 30]);
+        assert!(hand.verify_showdown().is_ok());
+
+        let exported = hand.to_string();
+        assert!(exported.starts_with(
+            "PokerStars Hand #77: Tournament #123, $1+$0.10 USD Hold'em No Limit - Level II (15/30)"
+        ));
+        assert_eq!(exported.parse::<HandHistory>().unwrap(), hand);
+    }
+
+    #[test]
+    fn parses_many_hands() {
+        let text = [HOLDEM, STUD, OMAHA_HI_LO].join("\n\n");
+        let hands = parse_histories(&text).unwrap();
+        assert_eq!(hands.len(), 3);
+        assert_eq!(hands[2].id, 3);
+        assert_eq!(parse_histories("").unwrap(), []);
+    }
+
+    #[rstest]
+    #[case("Table 'Alpha' 6-max", HistoryError::MissingHeader)]
+    #[case(
+        "PokerStars Hand #1:  Badugi Limit ($0.01/$0.02 USD)",
+        HistoryError::UnsupportedGame
+    )]
+    #[case(
+        "PokerStars Hand #x:  Hold'em No Limit ($0.01/$0.02 USD)",
+        HistoryError::InvalidHeader
+    )]
+    #[case(
+        "PokerStars Hand #1:  Hold'em No Limit ($0.01/$0.02 USD)\nTable Alpha",
+        HistoryError::InvalidTable
+    )]
+    #[case(
+        "PokerStars Hand #1:  Hold'em No Limit (1/2)\nTable 'A' 2-max\nSeat 1: Al (1.5 in chips)",
+        HistoryError::InvalidAmount
+    )]
+    #[case(
+        "PokerStars Hand #1:  Hold'em No Limit (1/2)\nTable 'A' 2-max\nSeat 1: Al (100 in chips)\n\
+         *** HOLE CARDS ***\nDealt to Bo [Ah Kd]",
+        HistoryError::UnknownPlayer
+    )]
+    #[case(
+        "PokerStars Hand #1:  Hold'em No Limit (1/2)\nTable 'A' 2-max\nSeat 1: Al (100 in chips)\n\
+         *** HOLE CARDS ***\nDealt to Al [Ah Kx]",
+        HistoryError::InvalidCard
+    )]
+    fn invalid_histories(#[case] text: &str, #[case] expected: HistoryError) {
+        assert_eq!(text.parse::<HandHistory>(), Err(expected));
+    }
+
+    #[rstest]
+    #[case("holdem", &[Action::Raise(30), Action::Fold, Action::Fold])]
+    #[case("holdem", &[Action::Call, Action::Call, Action::Check])]
+    #[case("holdem", &[Action::Raise(1000), Action::Call, Action::Fold])]
+    #[case("manila", &[Action::Call, Action::Call, Action::Check])]
+    #[case("manila", &[Action::Raise(30), Action::Call, Action::Fold])]
+    fn converts_engine_hands(#[case] id: &str, #[case] actions: &[Action]) {
+        let config = TableConfig::new(BettingStructure::NoLimit, 5, 10);
+        let game = find_game(id).unwrap();
+        let mut hand = HandState::new(game, config, &[1000, 1000, 1000], 11).unwrap();
+        for &action in actions {
+            hand.act(action).unwrap();
+        }
+        while let Some(legal) = hand.legal_actions() {
+            let action = if legal.check {
+                Action::Check
+            } else {
+                Action::Call
+            };
+            hand.act(action).unwrap();
+        }
+
+        let history = HandHistory::from_engine(&hand, 5);
+        assert_eq!(history.seats[2].stack, 1000);
+        let showdown = history.verify_showdown().unwrap();
+        assert_eq!(showdown.payouts.iter().sum::<u64>(), history.pot());
+        let winners = |payouts: &[u64]| payouts.iter().map(|&p| p > 0).collect::<Vec<_>>();
+        assert_eq!(
+            winners(&showdown.payouts),
+            winners(&hand.result().unwrap().payouts)
+        );
+
+        let parsed = history.to_string().parse::<HandHistory>().unwrap();
+        assert_eq!(parsed, history);
+    }
+
+    #[test]
+    fn manila_streets() {
+        let config = TableConfig::new(BettingStructure::NoLimit, 5, 10);
+        let game = find_game("manila").unwrap();
+        let mut hand = HandState::new(game, config, &[1000, 1000], 7).unwrap();
+        hand.act(Action::Call).unwrap();
+        hand.act(Action::Check).unwrap();
+        hand.act(Action::Bet(20)).unwrap();
+        hand.act(Action::Fold).unwrap();
+
+        let history = HandHistory::from_engine(&hand, 1);
+        let text = history.to_string();
+        assert!(text.contains(" Manila No Limit (5/10)"));
+        let first = format!(
+            "*** FIRST CARD *** [{}]\nDealt to",
+            Cards(&history.board[..1])
+        );
+        assert!(text.contains(&first));
+        assert!(text.contains("*** SECOND CARD ***"));
+        assert!(text.contains("folded on the Second Card"));
+        assert_eq!(text.parse::<HandHistory>().unwrap(), history);
+    }
+}
@@ -0,0 +1,449 @@
+//! Classification of made hold'em and Omaha hands relative to the board.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::holding::{classify_holding, Holding, Kicker};
+//!
+//! let board = "Ks 9d 4c".parse()?;
+//! let holding = classify_holding(&"Ah Kd".parse()?, &board).unwrap();
+//! assert_eq!(holding, Holding::TopPair(Kicker::Top));
+//! assert_eq!(holding.to_string(), "Top Pair, Top Kicker");
+//!
+//! let holding = classify_holding(&"4h 4d Qc Js".parse()?, &board).unwrap();
+//! assert_eq!(holding, Holding::BottomSet);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use aya_base::{constants::RANK_COUNT, Card, Hand, CARDS};
+
+use crate::{
+    equity::EquityError, exact_hole_rank, for_each_subhand, insert_cards, poker_rank,
+    PokerHandRank, PokerRankCategory,
+};
+
+/// The strength of the kicker accompanying a pair, relative to the best
+/// kicker possible on the board.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub enum Kicker {
+    /// A kicker beaten by at least three other kickers.
+    Weak,
+    /// A kicker beaten by one or two other kickers.
+    Good,
+    /// The best possible kicker.
+    Top,
+}
+
+/// A made hand classified relative to the board, as used in hand reading and
+/// HUD statistics.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Holding {
+    /// No pair or better.
+    HighCard,
+    /// Only the pairs on the board, without a pair made with the hole cards.
+    BoardPair,
+    /// A pocket pair ranked lower than the highest card on the board.
+    Underpair,
+    /// A pocket pair ranked higher than every card on the board.
+    Overpair,
+    /// A hole card paired with the highest card on the board.
+    TopPair(Kicker),
+    /// A hole card paired with a card on the board which is neither the
+    /// highest nor the lowest.
+    MiddlePair,
+    /// A hole card paired with the lowest card on the board.
+    BottomPair,
+    /// Both hole cards paired with the two highest cards on the board.
+    TopTwoPair,
+    /// Both hole cards paired with cards on the board, other than the two
+    /// highest.
+    TwoPair,
+    /// A pocket pair matching the lowest card on the board.
+    BottomSet,
+    /// A pocket pair matching a card on the board which is neither the
+    /// highest nor the lowest.
+    MiddleSet,
+    /// A pocket pair matching the highest card on the board.
+    TopSet,
+    /// A hole card matching a pair on the board.
+    Trips,
+    /// Three of a kind on the board, without help from the hole cards.
+    BoardTrips,
+    /// A straight made with the hole cards.
+    Straight {
+        /// The number of higher straights an opponent could hold, i.e. 0 for
+        /// the nut straight.
+        nut_rank: usize,
+    },
+    /// A flush made with the hole cards.
+    Flush {
+        /// The number of higher cards of the suit an opponent could hold,
+        /// i.e. 0 for the nut flush.
+        nut_rank: usize,
+    },
+    /// A full house made with the hole cards.
+    FullHouse,
+    /// Four of a kind made with the hole cards.
+    FourOfAKind,
+    /// A straight flush made with the hole cards.
+    StraightFlush,
+    /// A complete board which is the best hand, so that the hole cards do not
+    /// play.
+    PlayingTheBoard,
+}
+
+/// Classifies the made hand of the hole cards relative to the board.
+///
+/// Two hole cards are classified as a Texas hold'em hand ranked with
+/// [`poker_rank`], while four to six hole cards are classified as an Omaha
+/// hand, using the two hole cards which make the best hand with exactly three
+/// board cards. The nut ranks of straights and flushes account for the hole
+/// cards, which cannot be held by an opponent, and only compare hands of the
+/// same category.
+///
+/// Returns an error if there are not two or four to six hole cards, if the
+/// board does not hold between three and five cards, or if any card was dealt
+/// twice.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::holding::{classify_holding, Holding};
+///
+/// let board = "Qh 9h 4h Tc 2d".parse()?;
+/// let holding = classify_holding(&"Kh 3h".parse()?, &board).unwrap();
+/// assert_eq!(holding, Holding::Flush { nut_rank: 1 });
+/// assert_eq!(holding.to_string(), "Second-Nut Flush");
+///
+/// let holding = classify_holding(&"Kc Jd 8s 8c".parse()?, &board).unwrap();
+/// assert_eq!(holding, Holding::Straight { nut_rank: 0 });
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn classify_holding(hole: &Hand, board: &Hand) -> Result<Holding, EquityError> {
+    let is_holdem = match hole.len() {
+        2 => true,
+        4..=6 => false,
+        _ => return Err(EquityError::InvalidHoleCards),
+    };
+    if board.len() < 3 || board.len() > 5 {
+        return Err(EquityError::InvalidBoard);
+    }
+    if !hole.is_disjoint(board) {
+        return Err(EquityError::DuplicateCard);
+    }
+
+    let rank_fn = |hole: &Hand| {
+        if is_holdem {
+            let mut hand = *hole;
+            hand.extend(board.iter());
+            poker_rank(&hand)
+        } else {
+            exact_hole_rank(hole, board, 2)
+        }
+    };
+
+    let (used, rank) = if is_holdem {
+        (*hole, rank_fn(hole))
+    } else {
+        let mut buffer = [CARDS[0]; 7];
+        let cards = insert_cards(hole, &mut buffer);
+        let mut best = (Hand::new(), PokerHandRank(0));
+        for_each_subhand(cards, 2, Hand::new(), &mut |pair| {
+            let rank = rank_fn(&pair);
+            if rank > best.1 {
+                best = (pair, rank);
+            }
+        });
+        best
+    };
+
+    if is_holdem && board.len() == 5 && rank == poker_rank(board) {
+        return Ok(Holding::PlayingTheBoard);
+    }
+
+    let holding = match rank.rank_category() {
+        PokerRankCategory::HighCard => Holding::HighCard,
+        PokerRankCategory::Pair | PokerRankCategory::TwoPair => classify_pairs(&used, board),
+        PokerRankCategory::ThreeOfAKind => classify_trips(&used, board),
+        PokerRankCategory::Straight => Holding::Straight {
+            nut_rank: better_straights(hole, board, rank, rank_fn),
+        },
+        PokerRankCategory::Flush => Holding::Flush {
+            nut_rank: better_flush_cards(hole, &used, board),
+        },
+        PokerRankCategory::FullHouse => Holding::FullHouse,
+        PokerRankCategory::FourOfAKind => Holding::FourOfAKind,
+        _ => Holding::StraightFlush,
+    };
+
+    Ok(holding)
+}
+
+/// Returns the ranks of the hole cards, highest first.
+fn hole_ranks(used: &Hand) -> (usize, usize) {
+    let mut cards = used.iter();
+    let (first, second) = (cards.next().unwrap(), cards.next().unwrap());
+    let (first, second) = (first.rank() as usize, second.rank() as usize);
+    (first.max(second), first.min(second))
+}
+
+/// Returns the distinct ranks on the board, highest first.
+fn board_ranks(board: &Hand) -> Vec<usize> {
+    let mask = board.rank_mask();
+    (0..RANK_COUNT)
+        .rev()
+        .filter(|&r| mask & (1 << r) != 0)
+        .collect()
+}
+
+fn board_count(board: &Hand, rank: usize) -> usize {
+    board.iter().filter(|c| c.rank() as usize == rank).count()
+}
+
+fn classify_pairs(used: &Hand, board: &Hand) -> Holding {
+    let (high, low) = hole_ranks(used);
+    let ranks = board_ranks(board);
+    let position = |rank: usize| ranks.iter().position(|&r| r == rank);
+
+    if high == low {
+        return if high > ranks[0] {
+            Holding::Overpair
+        } else {
+            Holding::Underpair
+        };
+    }
+
+    match (position(high), position(low)) {
+        (Some(0), Some(1)) => Holding::TopTwoPair,
+        (Some(_), Some(_)) => Holding::TwoPair,
+        (Some(i), None) => pair_holding(i, &ranks, high, low),
+        (None, Some(i)) => pair_holding(i, &ranks, low, high),
+        (None, None) => Holding::BoardPair,
+    }
+}
+
+fn pair_holding(position: usize, ranks: &[usize], paired: usize, kicker: usize) -> Holding {
+    if position == 0 {
+        let better_kickers = (kicker + 1..RANK_COUNT)
+            .filter(|r| *r != paired && !ranks.contains(r))
+            .count();
+        let kicker = match better_kickers {
+            0 => Kicker::Top,
+            1 | 2 => Kicker::Good,
+            _ => Kicker::Weak,
+        };
+        Holding::TopPair(kicker)
+    } else if position == ranks.len() - 1 {
+        Holding::BottomPair
+    } else {
+        Holding::MiddlePair
+    }
+}
+
+fn classify_trips(used: &Hand, board: &Hand) -> Holding {
+    let (high, low) = hole_ranks(used);
+    let ranks = board_ranks(board);
+
+    if high == low && board_count(board, high) == 1 {
+        match ranks.iter().position(|&r| r == high) {
+            Some(0) => Holding::TopSet,
+            Some(i) if i == ranks.len() - 1 => Holding::BottomSet,
+            _ => Holding::MiddleSet,
+        }
+    } else if board_count(board, high) == 2 || board_count(board, low) == 2 {
+        Holding::Trips
+    } else {
+        Holding::BoardTrips
+    }
+}
+
+/// Returns the number of distinct straights higher than `rank` which an
+/// opponent could make with two of the unseen cards.
+fn better_straights(
+    hole: &Hand,
+    board: &Hand,
+    rank: PokerHandRank,
+    rank_fn: impl Fn(&Hand) -> PokerHandRank,
+) -> usize {
+    let unseen = CARDS
+        .iter()
+        .filter(|c| !hole.contains(c) && !board.contains(c))
+        .copied()
+        .collect::<Vec<Card>>();
+
+    let mut better = Vec::new();
+    for_each_subhand(&unseen, 2, Hand::new(), &mut |opponent| {
+        let opponent_rank = rank_fn(&opponent);
+        if opponent_rank > rank && opponent_rank.rank_category() == PokerRankCategory::Straight {
+            better.push(opponent_rank);
+        }
+    });
+
+    better.sort_unstable();
+    better.dedup();
+    better.len()
+}
+
+/// Returns the number of unseen cards of the flush suit ranked higher than
+/// the highest hole card of the suit.
+fn better_flush_cards(hole: &Hand, used: &Hand, board: &Hand) -> usize {
+    let suit = used
+        .iter()
+        .map(|c| c.suit())
+        .find(|&suit| board.suit_mask(suit).count_ones() + used.suit_mask(suit).count_ones() >= 5)
+        .unwrap();
+
+    let highest = 15 - used.suit_mask(suit).leading_zeros();
+    let unseen = !(board.suit_mask(suit) | hole.suit_mask(suit)) & 0x1fff;
+    (unseen >> (highest + 1)).count_ones() as usize
+}
+
+impl fmt::Display for Kicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kicker::Weak => write!(f, "Weak Kicker"),
+            Kicker::Good => write!(f, "Good Kicker"),
+            Kicker::Top => write!(f, "Top Kicker"),
+        }
+    }
+}
+
+impl fmt::Display for Holding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn nut_prefix(f: &mut fmt::Formatter<'_>, nut_rank: usize) -> fmt::Result {
+            match nut_rank {
+                0 => write!(f, "Nut "),
+                1 => write!(f, "Second-Nut "),
+                2 => write!(f, "Third-Nut "),
+                n => write!(f, "{}th-Nut ", n + 1),
+            }
+        }
+
+        match self {
+            Holding::HighCard => write!(f, "High Card"),
+            Holding::BoardPair => write!(f, "Pair on the Board"),
+            Holding::Underpair => write!(f, "Underpair"),
+            Holding::Overpair => write!(f, "Overpair"),
+            Holding::TopPair(kicker) => write!(f, "Top Pair, {}", kicker),
+            Holding::MiddlePair => write!(f, "Middle Pair"),
+            Holding::BottomPair => write!(f, "Bottom Pair"),
+            Holding::TopTwoPair => write!(f, "Top Two Pair"),
+            Holding::TwoPair => write!(f, "Two Pair"),
+            Holding::BottomSet => write!(f, "Bottom Set"),
+            Holding::MiddleSet => write!(f, "Middle Set"),
+            Holding::TopSet => write!(f, "Top Set"),
+            Holding::Trips => write!(f, "Trips"),
+            Holding::BoardTrips => write!(f, "Trips on the Board"),
+            Holding::Straight { nut_rank } => {
+                nut_prefix(f, *nut_rank)?;
+                write!(f, "Straight")
+            }
+            Holding::Flush { nut_rank } => {
+                nut_prefix(f, *nut_rank)?;
+                write!(f, "Flush")
+            }
+            Holding::FullHouse => write!(f, "Full House"),
+            Holding::FourOfAKind => write!(f, "Four of a Kind"),
+            Holding::StraightFlush => write!(f, "Straight Flush"),
+            Holding::PlayingTheBoard => write!(f, "Playing the Board"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::high_card("Ah Qd", "Ks 9d 4c", Holding::HighCard)]
+    #[case::board_pair("Ah Qd", "Ks 9d 9c", Holding::BoardPair)]
+    #[case::overpair("Ah Ad", "Ks 9d 4c", Holding::Overpair)]
+    #[case::overpair_on_paired_board("Ah Ad", "Ks Kd 4c", Holding::Overpair)]
+    #[case::underpair("Th Td", "Ks 9d 4c", Holding::Underpair)]
+    #[case::top_pair_top_kicker("Kh Ad", "Ks 9d 4c", Holding::TopPair(Kicker::Top))]
+    #[case::top_pair_king_kicker("Ah Kd", "As 9d 4c", Holding::TopPair(Kicker::Top))]
+    #[case::top_pair_good_kicker("Kh Qd", "Ks 9d 4c", Holding::TopPair(Kicker::Good))]
+    #[case::top_pair_weak_kicker("Kh Td", "Ks 9d 4c", Holding::TopPair(Kicker::Weak))]
+    #[case::middle_pair("9h Ad", "Ks 9d 4c", Holding::MiddlePair)]
+    #[case::bottom_pair("4h Ad", "Ks 9d 4c", Holding::BottomPair)]
+    #[case::turn_middle_pair("4h Ad", "Ks 9d 4c 2s", Holding::MiddlePair)]
+    #[case::top_two_pair("Kh 9h", "Ks 9d 4c", Holding::TopTwoPair)]
+    #[case::two_pair("Kh 4h", "Ks 9d 4c", Holding::TwoPair)]
+    #[case::top_set("Kh Kd", "Ks 9d 4c", Holding::TopSet)]
+    #[case::middle_set("9h 9c", "Ks 9d 4c 2s", Holding::MiddleSet)]
+    #[case::bottom_set("4h 4d", "Ks 9d 4c", Holding::BottomSet)]
+    #[case::trips("Kh Qd", "Ks Kd 4c", Holding::Trips)]
+    #[case::board_trips("Ah Qd", "Ks Kd Kc", Holding::BoardTrips)]
+    #[case::nut_straight("Jh Td", "Qs 9d 8c", Holding::Straight { nut_rank: 0 })]
+    #[case::second_nut_straight("Th 7d", "Js 9d 8c", Holding::Straight { nut_rank: 1 })]
+    #[case::nut_flush("Ah 3h", "Qh 9h 4h", Holding::Flush { nut_rank: 0 })]
+    #[case::nut_flush_with_ace_on_board("Kh 3h", "Ah 9h 4h", Holding::Flush { nut_rank: 0 })]
+    #[case::third_nut_flush("Jh Th", "Qh 9h 4h", Holding::Flush { nut_rank: 2 })]
+    #[case::full_house("Kh 9h", "Ks 9d 9c", Holding::FullHouse)]
+    #[case::quads("9h 9s", "Ks 9d 9c", Holding::FourOfAKind)]
+    #[case::straight_flush("Jh Th", "Qh 9h 8h", Holding::StraightFlush)]
+    #[case::playing_the_board("2c 3d", "As Ks Qh Jd Tc", Holding::PlayingTheBoard)]
+    #[case::higher_than_board("Ac 3d", "Ks Qs Jh Td 9c", Holding::Straight { nut_rank: 0 })]
+    fn holdem_holdings(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] expected: Holding,
+    ) -> Result<(), ParseError> {
+        assert_eq!(
+            classify_holding(&hole.parse()?, &board.parse()?),
+            Ok(expected)
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    // Omaha hands must use exactly two hole cards, so a single heart does not
+    // make a flush.
+    #[case::no_flush("Ah Kd Kc 2s", "Qh 9h 4h 7h", Holding::Overpair)]
+    #[case::set("Ks Kd 7c 2s", "Kh 9h 4c", Holding::TopSet)]
+    #[case::nut_flush("Ah 2h Kd Kc", "Qh 9h 4c 7h", Holding::Flush { nut_rank: 0 })]
+    #[case::five_card_straight("Jh Td 3c 3s 2d", "Qs 9d 8c", Holding::Straight { nut_rank: 0 })]
+    #[case::six_card_two_pair("Kh 9c 3c 3s 2d 2h", "Ks 9d 4c", Holding::TopTwoPair)]
+    fn omaha_holdings(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] expected: Holding,
+    ) -> Result<(), ParseError> {
+        assert_eq!(
+            classify_holding(&hole.parse()?, &board.parse()?),
+            Ok(expected)
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Holding::TopPair(Kicker::Good), "Top Pair, Good Kicker")]
+    #[case(Holding::BoardPair, "Pair on the Board")]
+    #[case(Holding::Straight { nut_rank: 0 }, "Nut Straight")]
+    #[case(Holding::Straight { nut_rank: 1 }, "Second-Nut Straight")]
+    #[case(Holding::Flush { nut_rank: 4 }, "5th-Nut Flush")]
+    fn display(#[case] holding: Holding, #[case] expected: &str) {
+        assert_eq!(holding.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::three_hole_cards("Ah Kd Qc", "Ks 9d 4c", EquityError::InvalidHoleCards)]
+    #[case::preflop("Ah Kd", "", EquityError::InvalidBoard)]
+    #[case::duplicate("Ah Kd", "Ah 9d 4c", EquityError::DuplicateCard)]
+    fn invalid_hands(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        assert_eq!(
+            classify_holding(&hole.parse()?, &board.parse()?),
+            Err(expected)
+        );
+        Ok(())
+    }
+}
@@ -6,7 +6,56 @@
 //!
 //! - Can be used to rank hands from standard poker, ace-to-five lowball,
 //!   deuce-to-seven lowball, six-or-better (short-deck), Omaha, Omaha Hi/Lo,
-//!   Badugi or Baduci.
+//!   Badugi, Baduci, Badeucy or Badacey.
+//! - Supports hold'em-family variants which must use exactly a given number
+//!   of hole cards, Manila, and double-board showdowns.
+//! - Can rank standard poker hands with wild cards, i.e. with jokers, the
+//!   bug, or deuces wild.
+//! - Provides video poker paytables and an exact solver for the expected
+//!   value of every hold.
+//! - Analyzes the outcomes of every discard option in single and triple
+//!   draw lowball games.
+//! - Computes exact hold'em equities for 2 to 10 players by enumerating every
+//!   runout of the board, or estimates hold'em, short-deck, Omaha and Omaha
+//!   Hi/Lo equities by reproducible Monte Carlo sampling with confidence
+//!   intervals.
+//! - Computes range-vs-range hold'em equities, exactly or by sampling, with
+//!   card removal between the ranges and per-combo equity breakdowns.
+//! - Parses, prints and combines weighted hold'em hand ranges in
+//!   PokerStove-style notation.
+//! - Parses Omaha ranges in ProPokerTools-style notation and estimates 4-, 5-
+//!   and 6-card Omaha Hi and Hi/Lo equities with scoop and quartering
+//!   statistics.
+//! - Computes hold'em hand strength, positive and negative potential, EHS and
+//!   EHS² against weighted ranges, exactly or by sampling runouts.
+//! - Lists the outs of hold'em hands by the resulting hand category, tells
+//!   clean outs apart, and classifies flush, straight, backdoor and overcard
+//!   draws.
+//! - Classifies made hold'em and Omaha hands relative to the board, e.g. as
+//!   an overpair, top pair with a good kicker, bottom set or second-nut
+//!   flush.
+//! - Computes the nuts and the ranked best holdings on hold'em, short-deck
+//!   and Omaha boards with dead cards, and how many hands beat a given
+//!   holding.
+//! - Analyzes board textures, e.g. suits, pairing, connectedness and
+//!   possible straights and flushes, and groups boards into texture
+//!   categories.
+//! - Resolves showdowns ranked by any of the evaluators into main and side
+//!   pots, with split pots, odd chips awarded by position and hi/lo
+//!   quartering.
+//! - Exposes every variant through common `Evaluator` and `SplitEvaluator`
+//!   traits, so that code such as showdowns and simulations can be written
+//!   once for all variants.
+//! - Looks up the rules of every variant at runtime by name, e.g. `nlhe`,
+//!   `plo8` or `2-7td`, and evaluates hands through them.
+//! - Deals and plays hands of hold'em and Omaha games with fixed-limit,
+//!   pot-limit or no-limit betting, deterministically from a seed.
+//! - Parses and exports hand histories in the PokerStars text format, and
+//!   verifies their showdowns by re-evaluating the shown hands.
+//! - Optionally reads and writes hand histories in the Open Hand History JSON
+//!   format.
+//! - Optionally looks up build-time generated preflop hold'em equities of the
+//!   169 starting hand classes heads-up and against up to 9 random hands.
 //! - Can evaluate hands with 0 to 7 cards, with the missing cards counting as
 //!   the worst possible kickers, allowing for use in stud poker games.
 //! - Uses compile-time generated perfect hash function lookup tables for
@@ -20,7 +69,15 @@
 //!
 //! - `std`: By default, `aya_poker` is a `!#[no_std]` crate, but can be
 //!   compiled with the `std` feature in order to allow the initialization of
-//!   `Deck`s with system-generated random seeds.
+//!   `Deck`s with system-generated random seeds and of time limits for
+//!   sampled equity calculations.
+//! - `preflop-tables`: Generate tables of preflop hold'em equities for all
+//!   169 starting hand classes at build time, which makes the build
+//!   considerably slower, and enable the `preflop` module for looking them
+//!   up.
+//! - `ohh`: Use [`serde_json`](https://crates.io/crates/serde_json) to enable
+//!   the `ohh` module for reading and writing hand histories in the Open Hand
+//!   History JSON format.
 //! - `colored`: Use [`colored`](https://crates.io/crates/colored) to display
 //!   cards and hands in color.
 //! - `colored-4color`: Same as `colored`, but using a four-color deck.
@@ -112,18 +169,43 @@
 //! }
 //! ```
 
-#![cfg_attr(not(any(std, test)), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 use quickdiv::DivisorU64;
 
 mod ace_five;
+mod badeucy;
 mod baduci;
 mod badugi;
 mod deuce_seven;
 mod display;
+mod double_board;
+pub mod draw;
+pub mod engine;
+pub mod equity;
+pub mod evaluator;
+pub mod history;
+pub mod holding;
+pub mod nuts;
+#[cfg(feature = "ohh")]
+pub mod ohh;
 mod omaha;
+pub mod omaha_range;
+pub mod outs;
+mod players;
+#[cfg(feature = "preflop-tables")]
+pub mod preflop;
+pub mod range;
+pub mod registry;
 mod short_deck;
+pub mod showdown;
 mod standard;
+pub mod strength;
+pub mod texture;
+pub mod video_poker;
+mod wild;
 
 /// Basic types for playing card games.
 pub mod base {
@@ -136,12 +218,15 @@ pub mod deck {
 }
 
 pub use ace_five::{ace_five_rank, AceFiveHandRank};
+pub use badeucy::{badacey_rank, badeucy_rank, BadaceyHandRank, BadeucyHandRank, SplitResult};
 pub use baduci::{baduci_rank, BaduciHandRank};
 pub use badugi::{badugi_rank, BadugiHandRank};
 pub use deuce_seven::{deuce_seven_rank, DeuceSevenHandRank};
-pub use omaha::{omaha_lo_rank, omaha_rank};
+pub use double_board::double_board_shares;
+pub use omaha::{exact_hole_rank, manila_rank, omaha_lo_rank, omaha_rank};
 pub use short_deck::{short_deck_rank, ShortDeckHandRank};
 pub use standard::{poker_rank, PokerHandRank};
+pub use wild::{bug_rank, deuces_wild_rank, joker_rank, JokerHand};
 
 struct MiniPhf {
     buckets_len: DivisorU64,
@@ -202,6 +287,9 @@ pub enum PokerRankCategory {
     /// The sequence A-K-Q-J-T all of the same suit, i.e. an ace-high
     /// straight flush.
     RoyalFlush,
+    /// Five cards of the same rank, which can only be made with the help of
+    /// wild cards.
+    FiveOfAKind,
 }
 
 impl core::fmt::Display for PokerRankCategory {
@@ -218,6 +306,7 @@ impl core::fmt::Display for PokerRankCategory {
             PokerRankCategory::FourOfAKind => write!(f, "Four of a Kind"),
             PokerRankCategory::StraightFlush => write!(f, "Straight Flush"),
             PokerRankCategory::RoyalFlush => write!(f, "Royal Flush"),
+            PokerRankCategory::FiveOfAKind => write!(f, "Five of a Kind"),
         }
     }
 }
@@ -247,6 +336,13 @@ impl core::fmt::Display for BadugiRankCategory {
     }
 }
 
+/// Returns the hand made of the hole and the board cards.
+fn combine(hole: &base::Hand, board: &base::Hand) -> base::Hand {
+    let mut hand = *hole;
+    hand.extend(board.iter());
+    hand
+}
+
 fn insert_cards<'a>(hand: &base::Hand, dest: &'a mut [base::Card]) -> &'a [base::Card] {
     let n = hand.len();
     for (i, &card) in hand.iter().enumerate() {
@@ -254,3 +350,22 @@ fn insert_cards<'a>(hand: &base::Hand, dest: &'a mut [base::Card]) -> &'a [base:
     }
     &dest[..n]
 }
+
+/// Calls `f` with each hand made by adding `k` of the given cards to `hand`.
+fn for_each_subhand(
+    cards: &[base::Card],
+    k: usize,
+    hand: base::Hand,
+    f: &mut impl FnMut(base::Hand),
+) {
+    if k == 0 {
+        f(hand);
+        return;
+    }
+
+    for i in 0..=(cards.len() - k) {
+        let mut subhand = hand;
+        subhand.insert_unchecked(&cards[i]);
+        for_each_subhand(&cards[(i + 1)..], k - 1, subhand, f);
+    }
+}
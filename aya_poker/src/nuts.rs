@@ -0,0 +1,361 @@
+//! Computation of the nuts and the strongest possible holdings on a board.
+//!
+//! A holding is the pair of hole cards a player uses to make their hand: any
+//! two hole cards in hold'em, or the two hole cards played with exactly three
+//! board cards in Omaha. Holdings which make hands of the same rank are
+//! grouped together, so that the second nuts are the best hands which lose
+//! to the nuts, rather than another combo of the nut hand.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::{base::Hand, equity::Game, nuts::nut_hands, PokerRankCategory};
+//!
+//! let board = "Qh Jh Th 4c 4s".parse()?;
+//! let hands = nut_hands(Game::Holdem, &board, &"".parse()?, 4).unwrap();
+//!
+//! assert_eq!(hands[0].category, PokerRankCategory::RoyalFlush);
+//! assert_eq!(hands[0].holdings, ["Ah Kh".parse::<Hand>()?]);
+//! assert_eq!(hands[1].category, PokerRankCategory::StraightFlush);
+//! assert_eq!(hands[3].category, PokerRankCategory::FourOfAKind);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use aya_base::{Card, Hand, CARDS};
+
+use crate::{
+    equity::{EquityError, Game},
+    exact_hole_rank, for_each_subhand, omaha_rank, poker_rank, short_deck_rank, PokerRankCategory,
+};
+
+/// The holdings which make a hand of the same rank on a board.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct NutHand {
+    /// The hand-ranking category of the hand.
+    pub category: PokerRankCategory,
+    /// The two-card holdings which make the hand.
+    pub holdings: Vec<Hand>,
+}
+
+/// Returns the `count` best hands which can be made on the board, best
+/// first, each with all of the holdings which make it.
+///
+/// Omaha Hi/Lo boards are ranked by their high hands. Holdings including
+/// any of the dead cards are excluded, so that fewer than `count` hands are
+/// returned only if fewer distinct hands can be made.
+///
+/// Returns an error if the board does not hold between three and five cards,
+/// if any card was dealt twice, or if a card is not part of the deck used by
+/// the game.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{equity::Game, nuts::nut_hands, PokerRankCategory};
+///
+/// let board = "Ks Qs 7d 2c".parse()?;
+/// let hands = nut_hands(Game::Omaha, &board, &"".parse()?, 2).unwrap();
+/// assert_eq!(hands[0].category, PokerRankCategory::ThreeOfAKind);
+/// assert_eq!(hands[0].holdings.len(), 3);
+/// assert!(hands[1].holdings.contains(&"Qc Qd".parse()?));
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn nut_hands(
+    game: Game,
+    board: &Hand,
+    dead: &Hand,
+    count: usize,
+) -> Result<Vec<NutHand>, EquityError> {
+    validate(game, board, dead)?;
+
+    let mut holdings = ranked_holdings(game, board, dead);
+    holdings.sort_unstable_by_key(|&(rank, _, _)| Reverse(rank));
+
+    let mut hands: Vec<NutHand> = Vec::with_capacity(count);
+    let mut last_rank = None;
+    for (rank, category, holding) in holdings {
+        if last_rank != Some(rank) {
+            if hands.len() == count {
+                break;
+            }
+            hands.push(NutHand {
+                category,
+                holdings: Vec::new(),
+            });
+            last_rank = Some(rank);
+        }
+        hands.last_mut().unwrap().holdings.push(holding);
+    }
+
+    Ok(hands)
+}
+
+/// Returns the best hand which can be made on the board with all of the
+/// holdings which make it.
+///
+/// Returns an error if the board does not hold between three and five cards,
+/// if any card was dealt twice, or if a card is not part of the deck used by
+/// the game.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{equity::Game, nuts::nuts, PokerRankCategory};
+///
+/// let nuts = nuts(Game::Holdem, &"Ts 9h 2c".parse()?, &"".parse()?).unwrap();
+/// assert_eq!(nuts.category, PokerRankCategory::ThreeOfAKind);
+/// assert_eq!(nuts.holdings.len(), 3);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn nuts(game: Game, board: &Hand, dead: &Hand) -> Result<NutHand, EquityError> {
+    let mut hands = nut_hands(game, board, dead, 1)?;
+    Ok(hands.remove(0))
+}
+
+/// Returns the number of distinct hands which beat the hole cards on the
+/// board, i.e. 0 if they hold the nuts and 1 if they hold the second nuts.
+///
+/// Only hands which an opponent could hold are counted, so that the hole and
+/// dead cards are removed from their holdings. Omaha Hi/Lo hands are ranked
+/// by their high hands.
+///
+/// Returns an error if the hole cards are invalid for the game, if the board
+/// does not hold between three and five cards, if any card was dealt twice,
+/// or if a card is not part of the deck used by the game.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{equity::Game, nuts::nut_position};
+///
+/// let board = "Ah 9h 5h 4c 2s".parse()?;
+/// let dead = "".parse()?;
+/// assert_eq!(nut_position(Game::Holdem, &"Kh Qh".parse()?, &board, &dead), Ok(0));
+/// // Holding the king of hearts blocks the only better flush.
+/// assert_eq!(nut_position(Game::Holdem, &"Kh Jh".parse()?, &board, &dead), Ok(0));
+/// // Any king of hearts with another heart makes a better flush.
+/// assert_eq!(nut_position(Game::Holdem, &"Qh Jh".parse()?, &board, &dead), Ok(7));
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn nut_position(
+    game: Game,
+    hole: &Hand,
+    board: &Hand,
+    dead: &Hand,
+) -> Result<usize, EquityError> {
+    if !game.hole_cards().contains(&hole.len()) {
+        return Err(EquityError::InvalidHoleCards);
+    }
+    if !hole.is_disjoint(dead) {
+        return Err(EquityError::DuplicateCard);
+    }
+    let mut excluded = *dead;
+    excluded.extend(hole.iter());
+    validate(game, board, &excluded)?;
+
+    let rank = match game {
+        Game::Holdem | Game::ShortDeck => rank_holding(game, hole, board).0,
+        Game::Omaha | Game::OmahaHiLo => omaha_rank(hole, board).0,
+    };
+
+    let mut better = ranked_holdings(game, board, &excluded)
+        .into_iter()
+        .map(|(r, _, _)| r)
+        .filter(|&r| r > rank)
+        .collect::<Vec<_>>();
+    better.sort_unstable();
+    better.dedup();
+
+    Ok(better.len())
+}
+
+/// Validates the board and dead cards.
+fn validate(game: Game, board: &Hand, dead: &Hand) -> Result<(), EquityError> {
+    if board.len() < 3 || board.len() > 5 {
+        return Err(EquityError::InvalidBoard);
+    }
+    if !board.is_disjoint(dead) {
+        return Err(EquityError::DuplicateCard);
+    }
+    if !board
+        .iter()
+        .chain(dead.iter())
+        .all(|c| game.deck_contains(c))
+    {
+        return Err(EquityError::InvalidCard);
+    }
+    Ok(())
+}
+
+/// Returns the rank and category of every holding made of cards which are
+/// neither on the board nor excluded.
+fn ranked_holdings(
+    game: Game,
+    board: &Hand,
+    excluded: &Hand,
+) -> Vec<(u16, PokerRankCategory, Hand)> {
+    let unseen = CARDS
+        .iter()
+        .filter(|c| game.deck_contains(c) && !board.contains(c) && !excluded.contains(c))
+        .copied()
+        .collect::<Vec<Card>>();
+
+    let mut holdings = Vec::with_capacity(unseen.len() * (unseen.len() - 1) / 2);
+    for_each_subhand(&unseen, 2, Hand::new(), &mut |holding| {
+        let (rank, category) = rank_holding(game, &holding, board);
+        holdings.push((rank, category, holding));
+    });
+    holdings
+}
+
+/// Returns the rank and category of the hand made by a holding.
+fn rank_holding(game: Game, holding: &Hand, board: &Hand) -> (u16, PokerRankCategory) {
+    let mut hand = *holding;
+    hand.extend(board.iter());
+    match game {
+        Game::Holdem => {
+            let rank = poker_rank(&hand);
+            (rank.0, rank.rank_category())
+        }
+        Game::ShortDeck => {
+            let rank = short_deck_rank(&hand);
+            (rank.0, rank.rank_category())
+        }
+        Game::Omaha | Game::OmahaHiLo => {
+            let rank = exact_hole_rank(holding, board, 2);
+            (rank.0, rank.rank_category())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::set("Ts 9h 2c", PokerRankCategory::ThreeOfAKind, 3)]
+    #[case::straight("Ts 9h 8c 2d", PokerRankCategory::Straight, 16)]
+    #[case::quads("Ks Kh 7c 7d 2s", PokerRankCategory::FourOfAKind, 1)]
+    #[case::royal_flush("As Ks Qs 3d", PokerRankCategory::RoyalFlush, 1)]
+    fn holdem_nuts(
+        #[case] board: &str,
+        #[case] category: PokerRankCategory,
+        #[case] holdings: usize,
+    ) -> Result<(), ParseError> {
+        let nuts = nuts(Game::Holdem, &board.parse()?, &"".parse()?).unwrap();
+        assert_eq!(nuts.category, category);
+        assert_eq!(nuts.holdings.len(), holdings);
+        Ok(())
+    }
+
+    #[test]
+    fn ranked_hands_on_a_paired_board() -> Result<(), ParseError> {
+        let board = "Qh Jh Th 4c 4s".parse()?;
+        let hands = nut_hands(Game::Holdem, &board, &"".parse()?, 5).unwrap();
+        let categories = hands.iter().map(|h| h.category).collect::<Vec<_>>();
+        assert_eq!(
+            categories,
+            [
+                PokerRankCategory::RoyalFlush,
+                PokerRankCategory::StraightFlush,
+                PokerRankCategory::StraightFlush,
+                PokerRankCategory::FourOfAKind,
+                PokerRankCategory::FullHouse,
+            ]
+        );
+        assert_eq!(hands[1].holdings, ["Kh 9h".parse::<Hand>()?]);
+        assert_eq!(hands[2].holdings, ["9h 8h".parse::<Hand>()?]);
+        assert_eq!(hands[3].holdings, ["4h 4d".parse::<Hand>()?]);
+        // Queens full of fours.
+        assert_eq!(hands[4].holdings.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn dead_cards_change_the_nuts() -> Result<(), ParseError> {
+        let board = "As Ks Qs 3d".parse()?;
+        let nuts = nuts(Game::Holdem, &board, &"Js".parse()?).unwrap();
+        assert_eq!(nuts.category, PokerRankCategory::Flush);
+        assert_eq!(nuts.holdings, ["Ts 9s".parse::<Hand>()?]);
+        Ok(())
+    }
+
+    #[test]
+    fn omaha_uses_two_hole_cards() -> Result<(), ParseError> {
+        let board = "Ah Kh Qh Jh 2c".parse()?;
+        let holdem = nuts(Game::Holdem, &board, &"".parse()?).unwrap();
+        assert_eq!(holdem.category, PokerRankCategory::RoyalFlush);
+        assert_eq!(holdem.holdings.len(), 46);
+
+        let omaha = nuts(Game::Omaha, &board, &"".parse()?).unwrap();
+        assert_eq!(omaha.category, PokerRankCategory::StraightFlush);
+        assert_eq!(omaha.holdings, ["Th 9h".parse::<Hand>()?]);
+        Ok(())
+    }
+
+    #[test]
+    fn short_deck_flush_beats_full_house() -> Result<(), ParseError> {
+        let board = "Ah Kh 7h 7c 9s".parse()?;
+        let hands = nut_hands(Game::ShortDeck, &board, &"".parse()?, 2).unwrap();
+        assert_eq!(hands[0].category, PokerRankCategory::FourOfAKind);
+        assert_eq!(hands[1].category, PokerRankCategory::Flush);
+        assert_eq!(hands[1].holdings, ["Qh Jh".parse::<Hand>()?]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::nut_flush(Game::Holdem, "Kh Qh", "Ah 9h 5h 4c 2s", 0)]
+    #[case::blocked_nut_flush(Game::Holdem, "Kh Jh", "Ah 9h 5h 4c 2s", 0)]
+    #[case::queen_high_flush(Game::Holdem, "Qh Jh", "Ah 9h 5h 4c 2s", 7)]
+    #[case::board_nuts(Game::Holdem, "2c 3d", "Ah Kh Qh Jh Th", 0)]
+    #[case::omaha_bottom_set(Game::Omaha, "4s 4d Kc Qd", "Ks 9d 4c", 2)]
+    #[case::omaha_top_set(Game::OmahaHiLo, "Kd Kh 9c 8c", "Ks 9d 4c", 0)]
+    fn nut_positions(
+        #[case] game: Game,
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] expected: usize,
+    ) -> Result<(), ParseError> {
+        let position = nut_position(game, &hole.parse()?, &board.parse()?, &"".parse()?);
+        assert_eq!(position, Ok(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn positions_match_ranked_hands() -> Result<(), ParseError> {
+        let board = "Jd 8c 7d 3s".parse()?;
+        let dead = "Ad 2h".parse()?;
+        let hands = nut_hands(Game::Holdem, &board, &dead, 8).unwrap();
+        for (i, hand) in hands.iter().enumerate() {
+            for holding in hand.holdings.iter() {
+                let position = nut_position(Game::Holdem, holding, &board, &dead).unwrap();
+                // Holding the cards can only block better hands.
+                assert!(position <= i);
+            }
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::flop_too_short(Game::Holdem, "Ah Kd", "Qs Jc", "", EquityError::InvalidBoard)]
+    #[case::wrong_hole_cards(Game::Omaha, "Ah Kd", "Qs Jc 2d", "", EquityError::InvalidHoleCards)]
+    #[case::dead_on_board(Game::Holdem, "Ah Kd", "Qs Jc 2d", "2d", EquityError::DuplicateCard)]
+    #[case::hole_on_board(Game::Holdem, "Ah Kd", "Qs Kd 2d", "", EquityError::DuplicateCard)]
+    #[case::not_in_deck(Game::ShortDeck, "Ah Kd", "Qs Jc 2d", "", EquityError::InvalidCard)]
+    fn invalid_deals(
+        #[case] game: Game,
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] dead: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let position = nut_position(game, &hole.parse()?, &board.parse()?, &dead.parse()?);
+        assert_eq!(position, Err(expected));
+        Ok(())
+    }
+}
@@ -0,0 +1,960 @@
+//! Reading and writing hand histories in the Open Hand History JSON format.
+//!
+//! Hands in the [Open Hand History](https://hh-specs.handhistory.org/) format
+//! are deserialized into [`OhhHand`]s and converted to and from the
+//! [`HandHistory`] of the [`history`](crate::history) module, with the card
+//! strings parsed into [`Card`]s and the game types mapped onto the rules of
+//! the [`registry`](crate::registry). The recorded winners of a hand can then
+//! be checked against the evaluators with [`OhhHand::verify_winners`].
+//!
+//! Amounts are converted to cents when the hand was played for a known
+//! currency and to chips otherwise. In fixed-limit games, the blind amounts
+//! hold the small and big bets, and the buy-ins and levels of tournaments are
+//! not stored.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::history::HandHistory;
+//! use aya_poker::ohh::{read_ohh, write_ohh};
+//!
+//! let text = "\
+//! PokerStars Hand #1001:  Hold'em No Limit ($0.01/$0.02 USD) - 2020/01/01 12:00:00 ET
+//! Table 'Alpha' 2-max Seat #2 is the button
+//! Seat 1: Alice ($2 in chips)
+//! Seat 2: Bob ($2 in chips)
+//! Bob: posts small blind $0.01
+//! Alice: posts big blind $0.02
+//! *** HOLE CARDS ***
+//! Bob: calls $0.01
+//! Alice: checks
+//! *** FLOP *** [Ah 9s 7d]
+//! Alice: checks
+//! Bob: checks
+//! *** TURN *** [Ah 9s 7d] [4c]
+//! Alice: checks
+//! Bob: checks
+//! *** RIVER *** [Ah 9s 7d 4c] [2h]
+//! Alice: checks
+//! Bob: checks
+//! *** SHOW DOWN ***
+//! Alice: shows [Kc Kd]
+//! Bob: shows [Qc Qd]
+//! Alice collected $0.04 from pot
+//! ";
+//!
+//! let hand: HandHistory = text.parse().unwrap();
+//! let json = write_ohh(&[hand.clone()]).unwrap();
+//! assert!(json.contains("\"game_type\": \"Holdem\""));
+//!
+//! let hands = read_ohh(&json).unwrap();
+//! assert_eq!(hands, [hand]);
+//! assert_eq!(hands[0].verify_showdown().unwrap().payouts, [4, 0]);
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use aya_base::Card;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    engine::BettingStructure,
+    history::{
+        parse_cards, uncalled_bet, ActionKind, HandHistory, HistoryAction, HistoryError, SeatInfo,
+        Tournament, CURRENCIES,
+    },
+    registry::find_game,
+    showdown::Showdown,
+};
+
+/// The version of the specification of the written hands.
+const SPEC_VERSION: &str = "1.4.6";
+
+/// The game types of the specification and the ids of the rules of the
+/// games they are mapped onto.
+const GAME_TYPES: [(&str, &str); 9] = [
+    ("Holdem", "holdem"),
+    ("Omaha", "omaha"),
+    ("Omaha", "omaha-5"),
+    ("Omaha", "omaha-6"),
+    ("OmahaHiLo", "omaha-hi-lo"),
+    ("OmahaHiLo", "omaha-5-hi-lo"),
+    ("Stud", "stud"),
+    ("StudHiLo", "stud-hi-lo"),
+    ("Razz", "razz"),
+];
+
+const SHOWDOWN: &str = "Showdown";
+
+/// The currency sign of hands played for money in a currency other than
+/// those in [`CURRENCIES`].
+const OTHER_CURRENCY: char = '¤';
+
+/// A hand in the Open Hand History format.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhHand {
+    /// The version of the specification the hand follows.
+    pub spec_version: String,
+    /// The name of the poker site.
+    #[serde(default)]
+    pub site_name: String,
+    /// The name of the poker network.
+    #[serde(default)]
+    pub network_name: String,
+    /// The version of the program which wrote the hand.
+    #[serde(default)]
+    pub internal_version: String,
+    /// Whether the hand was played in a tournament.
+    #[serde(default)]
+    pub tournament: bool,
+    /// The tournament the hand was played in, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tournament_info: Option<OhhTournament>,
+    /// The hand id.
+    pub game_number: String,
+    /// The date and time the hand started, in UTC.
+    #[serde(default)]
+    pub start_date_utc: String,
+    /// The name of the table.
+    #[serde(default)]
+    pub table_name: String,
+    /// The game, e.g. `Holdem` or `OmahaHiLo`.
+    pub game_type: String,
+    /// The betting structure.
+    pub bet_limit: OhhBetLimit,
+    /// The maximum number of players at the table.
+    pub table_size: usize,
+    /// The ISO 4217 code of the currency the hand was played for, if any.
+    #[serde(default)]
+    pub currency: String,
+    /// The number of the seat with the button, or 0 if there is none.
+    #[serde(default)]
+    pub dealer_seat: usize,
+    /// The small blind, or the small bet in fixed-limit games.
+    #[serde(default)]
+    pub small_blind_amount: f64,
+    /// The big blind, or the big bet in fixed-limit games.
+    #[serde(default)]
+    pub big_blind_amount: f64,
+    /// The ante.
+    #[serde(default)]
+    pub ante_amount: f64,
+    /// The players dealt into the hand.
+    pub players: Vec<OhhPlayer>,
+    /// The streets of the hand, followed by the showdown, if any.
+    pub rounds: Vec<OhhRound>,
+    /// The pots and the players who won them.
+    #[serde(default)]
+    pub pots: Vec<OhhPot>,
+}
+
+/// The tournament a hand was played in.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhTournament {
+    /// The tournament id.
+    pub tournament_number: String,
+}
+
+/// The betting structure of a hand.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhBetLimit {
+    /// One of `NL`, `PL` or `FL`.
+    pub bet_type: String,
+    /// The largest total bet allowed, or 0 if there is no cap.
+    #[serde(default)]
+    pub bet_cap: f64,
+}
+
+/// A player dealt into a hand.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhPlayer {
+    /// The id the actions of the player refer to.
+    pub id: u64,
+    /// The number of the seat of the player.
+    pub seat: usize,
+    /// The name of the player.
+    pub name: String,
+    /// The chips of the player at the start of the hand.
+    pub starting_stack: f64,
+}
+
+/// The cards dealt to the board and the actions on a street, or the actions
+/// at showdown.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhRound {
+    /// The index of the round in the hand.
+    pub id: u64,
+    /// The name of the street, e.g. `Flop`, or `Showdown`.
+    pub street: String,
+    /// The board cards dealt on the street.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<String>,
+    /// The actions on the street.
+    pub actions: Vec<OhhAction>,
+}
+
+/// An action of a player.
+///
+/// The amounts of raises are the total bets of the player on the street.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhAction {
+    /// The index of the action in the hand.
+    pub action_number: u64,
+    /// The id of the player acting.
+    pub player_id: u64,
+    /// The kind of the action, e.g. `Post SB`, `Raise` or `Shows Cards`.
+    pub action: String,
+    /// The chips put in the pot, or the total bet of a raise.
+    #[serde(default)]
+    pub amount: f64,
+    /// Whether the action put the player all-in.
+    #[serde(default)]
+    pub is_allin: bool,
+    /// The cards dealt or shown.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<String>,
+}
+
+/// A pot and the players who won it.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhPot {
+    /// The index of the pot, where 0 is the main pot.
+    pub number: u64,
+    /// The chips in the pot, including the rake.
+    pub amount: f64,
+    /// The chips taken from the pot by the site.
+    #[serde(default)]
+    pub rake: f64,
+    /// The chips taken from the pot for a jackpot.
+    #[serde(default)]
+    pub jackpot: f64,
+    /// The players who won chips from the pot.
+    pub player_wins: Vec<OhhWin>,
+}
+
+/// The chips won by a player from a pot.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct OhhWin {
+    /// The id of the player.
+    pub player_id: u64,
+    /// The chips won.
+    pub win_amount: f64,
+}
+
+/// A top-level Open Hand History object.
+#[derive(Serialize, Deserialize)]
+struct OhhDocument {
+    ohh: OhhHand,
+}
+
+/// An error which can be returned when reading or writing Open Hand History
+/// hands.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OhhError {
+    /// The JSON is malformed or does not match the specification.
+    InvalidJson,
+    /// The hand cannot be converted or verified.
+    InvalidHand(HistoryError),
+}
+
+impl From<HistoryError> for OhhError {
+    fn from(error: HistoryError) -> OhhError {
+        OhhError::InvalidHand(error)
+    }
+}
+
+impl fmt::Display for OhhError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OhhError::InvalidJson => write!(f, "invalid open hand history json"),
+            OhhError::InvalidHand(error) => write!(f, "invalid hand: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OhhError {}
+
+/// Reads every hand in a text containing any number of Open Hand History
+/// objects, separated by whitespace.
+///
+/// Dead blinds and chips added to the pot are read as antes, and straddles
+/// as big blinds, so that the contributions of the players are kept. Actions
+/// which do not move chips into the pot, such as sitting out, are skipped,
+/// and any other action is an error.
+pub fn read_ohh(text: &str) -> Result<Vec<HandHistory>, OhhError> {
+    serde_json::Deserializer::from_str(text)
+        .into_iter::<OhhDocument>()
+        .map(|document| {
+            let document = document.map_err(|_| OhhError::InvalidJson)?;
+            Ok(HandHistory::try_from(&document.ohh)?)
+        })
+        .collect()
+}
+
+/// Writes the hands as Open Hand History objects, separated by blank lines.
+///
+/// Returns an error if any of the games is not part of the specification.
+pub fn write_ohh(hands: &[HandHistory]) -> Result<String, OhhError> {
+    let mut text = String::new();
+    for hand in hands {
+        let document = OhhDocument {
+            ohh: OhhHand::try_from(hand)?,
+        };
+        let json = serde_json::to_string_pretty(&document).map_err(|_| OhhError::InvalidJson)?;
+        text.push_str(&json);
+        text.push_str("\n\n");
+    }
+    Ok(text)
+}
+
+impl OhhHand {
+    /// Re-evaluates the showdown of the hand and checks that the recorded
+    /// winners of the pots are the players with the best hands, as described
+    /// in [`HandHistory::verify_showdown`].
+    pub fn verify_winners(&self) -> Result<Showdown, OhhError> {
+        let hand = HandHistory::try_from(self)?;
+        Ok(hand.verify_showdown()?)
+    }
+}
+
+impl TryFrom<&HandHistory> for OhhHand {
+    type Error = HistoryError;
+
+    fn try_from(hand: &HandHistory) -> Result<OhhHand, HistoryError> {
+        let game_type = GAME_TYPES
+            .iter()
+            .find(|(_, id)| *id == hand.game.id)
+            .ok_or(HistoryError::UnsupportedGame)?
+            .0;
+        let amount = |amount: u64| from_amount(amount, hand.currency);
+
+        let mut rounds = Vec::new();
+        let mut wins = Vec::new();
+        let mut action_number = 0;
+        let mut street = None;
+        for action in hand.actions.iter() {
+            let (name, chips, cards) = match &action.kind {
+                ActionKind::PostAnte(chips) => ("Post Ante", *chips, None),
+                ActionKind::PostSmallBlind(chips) => ("Post SB", *chips, None),
+                ActionKind::PostBigBlind(chips) => ("Post BB", *chips, None),
+                ActionKind::BringIn(chips) => ("Post Bring In", *chips, None),
+                ActionKind::Dealt(cards) => ("Dealt Cards", 0, Some(cards)),
+                ActionKind::Fold => ("Fold", 0, None),
+                ActionKind::Check => ("Check", 0, None),
+                ActionKind::Call(chips) => ("Call", *chips, None),
+                ActionKind::Bet(chips) => ("Bet", *chips, None),
+                ActionKind::Raise { to, .. } => ("Raise", *to, None),
+                ActionKind::Complete(to) => ("Complete", *to, None),
+                ActionKind::Show { cards, .. } => ("Shows Cards", 0, Some(cards)),
+                ActionKind::Muck => ("Mucks Cards", 0, None),
+                ActionKind::UncalledBet(_) => continue,
+                ActionKind::Collect(chips) => {
+                    wins.push((action.seat, *chips));
+                    continue;
+                }
+            };
+
+            let showdown = matches!(action.kind, ActionKind::Show { .. } | ActionKind::Muck);
+            open_rounds(hand, &mut rounds, &mut street, action.street);
+            if showdown && rounds.last().map_or(true, |r| r.street != SHOWDOWN) {
+                rounds.push(OhhRound {
+                    id: rounds.len() as u64,
+                    street: SHOWDOWN.to_string(),
+                    cards: Vec::new(),
+                    actions: Vec::new(),
+                });
+            }
+            if let Some(round) = rounds.last_mut() {
+                round.actions.push(OhhAction {
+                    action_number,
+                    player_id: action.seat as u64,
+                    action: name.to_string(),
+                    amount: amount(chips),
+                    is_allin: action.all_in,
+                    cards: cards.map_or_else(Vec::new, |cards| card_strings(cards)),
+                });
+            }
+            action_number += 1;
+        }
+        let last_street = hand.game.streets.len() - 1;
+        if rounds.last().map_or(true, |r| r.street != SHOWDOWN) {
+            open_rounds(hand, &mut rounds, &mut street, last_street);
+        }
+
+        let bet_type = match hand.structure {
+            BettingStructure::FixedLimit => "FL",
+            BettingStructure::PotLimit => "PL",
+            BettingStructure::NoLimit => "NL",
+        };
+        let ante = hand.actions.iter().find_map(|a| match a.kind {
+            ActionKind::PostAnte(chips) => Some(chips),
+            _ => None,
+        });
+        let currency = CURRENCIES
+            .iter()
+            .find(|(symbol, _)| Some(*symbol) == hand.currency)
+            .map_or("", |(_, code)| code);
+
+        // The pots each win came from are not recorded, so every win is
+        // written into its own pot, with the rake taken from the first.
+        let mut pots: Vec<_> = wins
+            .iter()
+            .enumerate()
+            .map(|(i, &(seat, chips))| {
+                let rake = if i == 0 { hand.rake } else { 0 };
+                OhhPot {
+                    number: i as u64,
+                    amount: amount(chips + rake),
+                    rake: amount(rake),
+                    jackpot: 0.0,
+                    player_wins: alloc::vec![OhhWin {
+                        player_id: seat as u64,
+                        win_amount: amount(chips),
+                    }],
+                }
+            })
+            .collect();
+        if pots.is_empty() {
+            pots.push(OhhPot {
+                number: 0,
+                amount: amount(hand.pot()),
+                rake: amount(hand.rake),
+                jackpot: 0.0,
+                player_wins: Vec::new(),
+            });
+        }
+
+        Ok(OhhHand {
+            spec_version: SPEC_VERSION.to_string(),
+            site_name: String::new(),
+            network_name: String::new(),
+            internal_version: env!("CARGO_PKG_VERSION").to_string(),
+            tournament: hand.tournament.is_some(),
+            tournament_info: hand.tournament.as_ref().map(|t| OhhTournament {
+                tournament_number: t.id.to_string(),
+            }),
+            game_number: hand.id.to_string(),
+            start_date_utc: hand.date.clone(),
+            table_name: hand.table.clone(),
+            game_type: game_type.to_string(),
+            bet_limit: OhhBetLimit {
+                bet_type: bet_type.to_string(),
+                bet_cap: 0.0,
+            },
+            table_size: hand.max_seats,
+            currency: currency.to_string(),
+            dealer_seat: hand.button.unwrap_or(0),
+            small_blind_amount: amount(hand.stakes.0),
+            big_blind_amount: amount(hand.stakes.1),
+            ante_amount: amount(ante.unwrap_or(0)),
+            players: hand
+                .seats
+                .iter()
+                .enumerate()
+                .map(|(i, seat)| OhhPlayer {
+                    id: i as u64,
+                    seat: seat.number,
+                    name: seat.name.clone(),
+                    starting_stack: amount(seat.stack),
+                })
+                .collect(),
+            rounds,
+            pots,
+        })
+    }
+}
+
+impl TryFrom<&OhhHand> for HandHistory {
+    type Error = HistoryError;
+
+    fn try_from(ohh: &OhhHand) -> Result<HandHistory, HistoryError> {
+        // Amounts in other currencies are kept in their minor unit, as long
+        // as any of them is fractional.
+        let currency = match ohh.tournament {
+            true => None,
+            false => CURRENCIES
+                .iter()
+                .find(|(_, code)| *code == ohh.currency)
+                .map(|(symbol, _)| *symbol)
+                .or_else(|| has_fractional_amounts(ohh).then_some(OTHER_CURRENCY)),
+        };
+        let amount = |value: f64| to_amount(value, currency);
+
+        // Games with several sizes of hands are told apart by the largest
+        // number of cards held by a player.
+        let held = ohh
+            .rounds
+            .iter()
+            .flat_map(|r| r.actions.iter())
+            .map(|a| a.cards.len())
+            .max()
+            .unwrap_or(0);
+        let mut candidates = GAME_TYPES
+            .iter()
+            .filter(|(game_type, _)| *game_type == ohh.game_type)
+            .filter_map(|(_, id)| find_game(id));
+        let first = candidates.clone().next();
+        let game = candidates
+            .find(|game| game.hole_cards() == held)
+            .or(first)
+            .ok_or(HistoryError::UnsupportedGame)?;
+        let structure = match ohh.bet_limit.bet_type.as_str() {
+            "FL" => BettingStructure::FixedLimit,
+            "PL" => BettingStructure::PotLimit,
+            "NL" => BettingStructure::NoLimit,
+            _ => return Err(HistoryError::UnsupportedGame),
+        };
+
+        let tournament = match &ohh.tournament_info {
+            Some(info) => Some(Tournament {
+                id: info
+                    .tournament_number
+                    .parse()
+                    .map_err(|_| HistoryError::InvalidHeader)?,
+                buy_in: String::new(),
+                level: String::new(),
+            }),
+            None => None,
+        };
+        let seats = ohh
+            .players
+            .iter()
+            .map(|p| {
+                Ok(SeatInfo {
+                    number: p.seat,
+                    name: p.name.clone(),
+                    stack: amount(p.starting_stack)?,
+                })
+            })
+            .collect::<Result<Vec<_>, HistoryError>>()?;
+
+        let mut hand = HandHistory {
+            id: ohh
+                .game_number
+                .parse()
+                .map_err(|_| HistoryError::InvalidHeader)?,
+            tournament,
+            game,
+            structure,
+            stakes: (
+                amount(ohh.small_blind_amount)?,
+                amount(ohh.big_blind_amount)?,
+            ),
+            currency,
+            date: ohh.start_date_utc.clone(),
+            table: ohh.table_name.clone(),
+            max_seats: ohh.table_size,
+            button: Some(ohh.dealer_seat).filter(|&seat| seat > 0),
+            seats,
+            board: Vec::new(),
+            actions: Vec::new(),
+            rake: 0,
+        };
+
+        let seat_of = |id: u64| {
+            ohh.players
+                .iter()
+                .position(|p| p.id == id)
+                .ok_or(HistoryError::UnknownPlayer)
+        };
+        let mut street = 0;
+        let mut street_bets = alloc::vec![0; hand.seats.len()];
+        for round in ohh.rounds.iter() {
+            if round.street == SHOWDOWN {
+                push_uncalled_bet(&mut hand, street);
+            } else {
+                street = game
+                    .streets
+                    .iter()
+                    .position(|s| s.name.eq_ignore_ascii_case(&round.street))
+                    .ok_or(HistoryError::InvalidLine)?;
+                street_bets.iter_mut().for_each(|bet| *bet = 0);
+                let cards = round.cards.join(" ");
+                hand.board.extend(parse_cards(&cards)?);
+            }
+
+            for action in round.actions.iter() {
+                let seat = seat_of(action.player_id)?;
+                let chips = amount(action.amount)?;
+                let current_bet = street_bets.iter().copied().max().unwrap_or(0);
+                let kind = match action.action.as_str() {
+                    "Post Ante" => ActionKind::PostAnte(chips),
+                    "Post SB" => ActionKind::PostSmallBlind(chips),
+                    "Post BB" => ActionKind::PostBigBlind(chips),
+                    "Post Bring In" => ActionKind::BringIn(chips),
+                    "Dealt Cards" => ActionKind::Dealt(parse_cards(&action.cards.join(" "))?),
+                    "Fold" => ActionKind::Fold,
+                    "Check" => ActionKind::Check,
+                    "Call" => ActionKind::Call(chips),
+                    "Bet" => ActionKind::Bet(chips),
+                    "Raise" => ActionKind::Raise {
+                        by: chips.saturating_sub(current_bet),
+                        to: chips,
+                    },
+                    "Complete" => ActionKind::Complete(chips),
+                    "Shows Cards" => ActionKind::Show {
+                        cards: parse_cards(&action.cards.join(" "))?,
+                        description: None,
+                    },
+                    "Mucks Cards" => ActionKind::Muck,
+                    // Dead money does not count towards the bet of the player.
+                    "Post Dead" | "Added To Pot" => ActionKind::PostAnte(chips),
+                    "Straddle" => ActionKind::PostBigBlind(chips),
+                    "Sits Down" | "Stands Up" | "Sits Out" | "Sits In" | "Added Chips" => continue,
+                    _ => return Err(HistoryError::InvalidLine),
+                };
+                match kind {
+                    ActionKind::PostSmallBlind(chips)
+                    | ActionKind::PostBigBlind(chips)
+                    | ActionKind::BringIn(chips)
+                    | ActionKind::Call(chips)
+                    | ActionKind::Bet(chips) => street_bets[seat] += chips,
+                    ActionKind::Raise { to, .. } | ActionKind::Complete(to) => {
+                        street_bets[seat] = to
+                    }
+                    _ => {}
+                }
+                hand.actions.push(HistoryAction {
+                    street,
+                    seat,
+                    kind,
+                    all_in: action.is_allin,
+                });
+            }
+        }
+        if !ohh.rounds.iter().any(|r| r.street == SHOWDOWN) {
+            push_uncalled_bet(&mut hand, street);
+        }
+
+        for pot in ohh.pots.iter() {
+            hand.rake += amount(pot.rake)?;
+            for win in pot.player_wins.iter() {
+                hand.actions.push(HistoryAction {
+                    street,
+                    seat: seat_of(win.player_id)?,
+                    kind: ActionKind::Collect(amount(win.win_amount)?),
+                    all_in: false,
+                });
+            }
+        }
+        Ok(hand)
+    }
+}
+
+/// Adds rounds for every street up to the given one, with the board cards
+/// dealt on each of them.
+fn open_rounds(
+    hand: &HandHistory,
+    rounds: &mut Vec<OhhRound>,
+    opened: &mut Option<usize>,
+    street: usize,
+) {
+    while opened.map_or(true, |s| s < street) {
+        let next = opened.map_or(0, |s| s + 1);
+        let dealt = hand.game.streets[..next]
+            .iter()
+            .map(|s| s.board_cards)
+            .sum::<usize>();
+        let count = hand.game.streets[next].board_cards;
+        let cards = hand.board.get(dealt..dealt + count).unwrap_or(&[]);
+        if next > 0 && cards.len() < count {
+            // The hand ended before the street was dealt.
+            return;
+        }
+        rounds.push(OhhRound {
+            id: rounds.len() as u64,
+            street: hand.game.streets[next].name.to_string(),
+            cards: card_strings(cards),
+            actions: Vec::new(),
+        });
+        *opened = Some(next);
+    }
+}
+
+/// Returns the uncalled part of the last bet to its player, as it is not
+/// recorded in the rounds.
+fn push_uncalled_bet(hand: &mut HandHistory, street: usize) {
+    if let Some((seat, amount)) = uncalled_bet(&hand.contributions()) {
+        hand.actions.push(HistoryAction {
+            street,
+            seat,
+            kind: ActionKind::UncalledBet(amount),
+            all_in: false,
+        });
+    }
+}
+
+fn card_strings(cards: &[Card]) -> Vec<String> {
+    cards
+        .iter()
+        .map(|card| alloc::format!("{:?}", card))
+        .collect()
+}
+
+fn from_amount(amount: u64, currency: Option<char>) -> f64 {
+    match currency {
+        Some(_) => amount as f64 / 100.0,
+        None => amount as f64,
+    }
+}
+
+/// Returns `true` if any of the amounts of the hand has a fractional part.
+fn has_fractional_amounts(ohh: &OhhHand) -> bool {
+    let actions = ohh.rounds.iter().flat_map(|r| r.actions.iter());
+    let pots = ohh.pots.iter();
+    let wins = ohh.pots.iter().flat_map(|p| p.player_wins.iter());
+    [
+        ohh.small_blind_amount,
+        ohh.big_blind_amount,
+        ohh.ante_amount,
+    ]
+    .into_iter()
+    .chain(ohh.players.iter().map(|p| p.starting_stack))
+    .chain(actions.map(|a| a.amount))
+    .chain(pots.flat_map(|p| [p.amount, p.rake]))
+    .chain(wins.map(|w| w.win_amount))
+    .any(|value| value.fract() != 0.0)
+}
+
+fn to_amount(value: f64, currency: Option<char>) -> Result<u64, HistoryError> {
+    let value = match currency {
+        Some(_) => 100.0 * value,
+        None => value,
+    };
+    if !(0.0..u64::MAX as f64).contains(&value) {
+        return Err(HistoryError::InvalidAmount);
+    }
+    Ok((value + 0.5) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::tests::{HOLDEM, OMAHA_HI_LO, STUD};
+    use rstest::rstest;
+
+    const TOURNAMENT: &str = r#"{
+  "ohh": {
+    "spec_version": "1.4.6",
+    "site_name": "Example",
+    "tournament": true,
+    "tournament_info": { "tournament_number": "4242" },
+    "game_number": "77",
+    "start_date_utc": "2021-03-04T05:06:07Z",
+    "table_name": "Final",
+    "game_type": "Holdem",
+    "bet_limit": { "bet_type": "NL", "bet_cap": 0 },
+    "table_size": 9,
+    "currency": "T$",
+    "dealer_seat": 4,
+    "small_blind_amount": 50,
+    "big_blind_amount": 100,
+    "ante_amount": 0,
+    "players": [
+      { "id": 10, "seat": 2, "name": "Hero", "starting_stack": 1500 },
+      { "id": 11, "seat": 4, "name": "Villain", "starting_stack": 3000 }
+    ],
+    "rounds": [
+      {
+        "id": 0,
+        "street": "Preflop",
+        "actions": [
+          { "action_number": 1, "player_id": 11, "action": "Post SB", "amount": 50 },
+          { "action_number": 2, "player_id": 10, "action": "Post BB", "amount": 100 },
+          { "action_number": 3, "player_id": 10, "action": "Dealt Cards", "cards": ["Qs", "Qh"] },
+          { "action_number": 4, "player_id": 11, "action": "Raise", "amount": 300 },
+          { "action_number": 5, "player_id": 10, "action": "Raise", "amount": 1500, "is_allin": true },
+          { "action_number": 6, "player_id": 11, "action": "Call", "amount": 1200 },
+          { "action_number": 7, "player_id": 11, "action": "Sits Out" }
+        ]
+      },
+      { "id": 1, "street": "Flop", "cards": ["2c", "7d", "Jh"], "actions": [] },
+      { "id": 2, "street": "Turn", "cards": ["4s"], "actions": [] },
+      { "id": 3, "street": "River", "cards": ["9c"], "actions": [] },
+      {
+        "id": 4,
+        "street": "Showdown",
+        "actions": [
+          { "action_number": 8, "player_id": 10, "action": "Shows Cards", "cards": ["Qs", "Qh"] },
+          { "action_number": 9, "player_id": 11, "action": "Shows Cards", "cards": ["Ac", "Kd"] }
+        ]
+      }
+    ],
+    "pots": [
+      {
+        "number": 0,
+        "amount": 3000,
+        "rake": 0,
+        "player_wins": [{ "player_id": 10, "win_amount": 3000 }]
+      }
+    ]
+  }
+}"#;
+
+    fn without_descriptions(mut hand: HandHistory) -> HandHistory {
+        for action in hand.actions.iter_mut() {
+            if let ActionKind::Show { description, .. } = &mut action.kind {
+                *description = None;
+            }
+        }
+        hand
+    }
+
+    #[rstest]
+    #[case(HOLDEM)]
+    #[case(STUD)]
+    #[case(OMAHA_HI_LO)]
+    fn round_trips_histories(#[case] text: &str) {
+        let hand: HandHistory = text.parse().unwrap();
+        let json = write_ohh(&[hand.clone(), hand.clone()]).unwrap();
+        let hands = read_ohh(&json).unwrap();
+        assert_eq!(hands.len(), 2);
+        assert_eq!(hands[0], without_descriptions(hand));
+    }
+
+    #[test]
+    fn exports_rounds() {
+        let hand: HandHistory = HOLDEM.parse().unwrap();
+        let ohh = OhhHand::try_from(&hand).unwrap();
+        assert_eq!(ohh.game_type, "Holdem");
+        assert_eq!(ohh.bet_limit.bet_type, "NL");
+        assert_eq!(ohh.currency, "USD");
+        assert_eq!(ohh.dealer_seat, 1);
+        assert_eq!(ohh.big_blind_amount, 0.02);
+        assert_eq!(ohh.players[1].starting_stack, 2.15);
+        let streets: Vec<_> = ohh.rounds.iter().map(|r| r.street.as_str()).collect();
+        assert_eq!(streets, ["Preflop", "Flop", "Turn", "River", "Showdown"]);
+        assert_eq!(ohh.rounds[1].cards, ["2c", "7d", "Jh"]);
+        assert_eq!(ohh.rounds[0].actions[3].action, "Raise");
+        assert_eq!(ohh.rounds[0].actions[3].amount, 0.06);
+        assert_eq!(ohh.pots[0].amount, 0.49);
+        assert_eq!(ohh.pots[0].rake, 0.01);
+        assert_eq!(ohh.pots[0].player_wins[0].win_amount, 0.48);
+    }
+
+    #[test]
+    fn reads_tournament_hands() {
+        let hands = read_ohh(TOURNAMENT).unwrap();
+        let hand = &hands[0];
+        assert_eq!(hand.id, 77);
+        assert_eq!(hand.tournament.as_ref().unwrap().id, 4242);
+        assert_eq!(hand.currency, None);
+        assert_eq!(hand.stakes, (50, 100));
+        assert_eq!(hand.button, Some(4));
+        assert_eq!(hand.seats[1].stack, 3000);
+        assert_eq!(hand.board, parse_cards("2c 7d Jh 4s 9c").unwrap());
+        assert_eq!(hand.actions[3].kind, ActionKind::Raise { by: 200, to: 300 });
+        assert_eq!(
+            hand.actions[4].kind,
+            ActionKind::Raise { by: 1200, to: 1500 }
+        );
+        assert_eq!(hand.actions.len(), 9);
+        assert_eq!(hand.contributions(), [1500, 1500]);
+        assert_eq!(hand.collected(), [3000, 0]);
+    }
+
+    #[rstest]
+    #[case("\"Post SB\"", "\"Post Dead\"", 0, ActionKind::PostAnte(50))]
+    #[case("\"Post SB\"", "\"Added To Pot\"", 0, ActionKind::PostAnte(50))]
+    #[case("\"Post BB\"", "\"Straddle\"", 1, ActionKind::PostBigBlind(100))]
+    fn reads_other_posts(
+        #[case] from: &str,
+        #[case] to: &str,
+        #[case] index: usize,
+        #[case] expected: ActionKind,
+    ) {
+        let text = TOURNAMENT.replace(from, to);
+        let hands = read_ohh(&text).unwrap();
+        assert_eq!(hands[0].actions[index].kind, expected);
+    }
+
+    #[test]
+    fn writes_wins_into_own_pots() {
+        let text = TOURNAMENT.replace(
+            "[{ \"player_id\": 10, \"win_amount\": 3000 }]",
+            "[{ \"player_id\": 10, \"win_amount\": 2000 }, \
+             { \"player_id\": 11, \"win_amount\": 1000 }]",
+        );
+        let hands = read_ohh(&text).unwrap();
+        assert_eq!(hands[0].collected(), [2000, 1000]);
+
+        let ohh = OhhHand::try_from(&hands[0]).unwrap();
+        assert_eq!(ohh.pots.len(), 2);
+        assert_eq!(ohh.pots[1].number, 1);
+        assert_eq!(ohh.pots[1].amount, 1000.0);
+        assert_eq!(ohh.pots[1].player_wins[0].player_id, 1);
+    }
+
+    #[test]
+    fn keeps_minor_unit_of_other_currencies() {
+        let text = TOURNAMENT
+            .replace("\"tournament\": true", "\"tournament\": false")
+            .replace("\"T$\"", "\"CAD\"")
+            .replace("\"small_blind_amount\": 50", "\"small_blind_amount\": 0.01");
+        let hands = read_ohh(&text).unwrap();
+        assert_eq!(hands[0].currency, Some(OTHER_CURRENCY));
+        assert_eq!(hands[0].stakes, (1, 10_000));
+    }
+
+    #[test]
+    fn verifies_winners() {
+        let document: OhhDocument = serde_json::from_str(TOURNAMENT).unwrap();
+        let showdown = document.ohh.verify_winners().unwrap();
+        assert_eq!(showdown.payouts, [3000, 0]);
+
+        let mut ohh = document.ohh;
+        ohh.pots[0].player_wins[0].player_id = 11;
+        assert_eq!(
+            ohh.verify_winners(),
+            Err(OhhError::InvalidHand(HistoryError::ShowdownMismatch))
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "\"game_type\": \"Holdem\"",
+        "\"game_type\": \"Badugi\"",
+        HistoryError::UnsupportedGame
+    )]
+    #[case(
+        "\"bet_type\": \"NL\"",
+        "\"bet_type\": \"XL\"",
+        HistoryError::UnsupportedGame
+    )]
+    #[case(
+        "\"game_number\": \"77\"",
+        "\"game_number\": \"x\"",
+        HistoryError::InvalidHeader
+    )]
+    #[case(
+        "\"street\": \"Flop\"",
+        "\"street\": \"Fifth\"",
+        HistoryError::InvalidLine
+    )]
+    #[case("\"Ac\", \"Kd\"", "\"Ac\", \"Kx\"", HistoryError::InvalidCard)]
+    #[case(
+        "\"player_id\": 10, \"win",
+        "\"player_id\": 12, \"win",
+        HistoryError::UnknownPlayer
+    )]
+    #[case("\"amount\": 1200", "\"amount\": -1200", HistoryError::InvalidAmount)]
+    #[case("\"Sits Out\"", "\"Post Extra Blind\"", HistoryError::InvalidLine)]
+    fn invalid_hands(#[case] from: &str, #[case] to: &str, #[case] expected: HistoryError) {
+        assert!(TOURNAMENT.contains(from));
+        let text = TOURNAMENT.replace(from, to);
+        assert_eq!(read_ohh(&text), Err(OhhError::InvalidHand(expected)));
+    }
+
+    #[rstest]
+    #[case("{\"ohh\": {}}")]
+    #[case("[1, 2, 3]")]
+    #[case("{\"ohh\": ")]
+    fn invalid_json(#[case] text: &str) {
+        assert_eq!(read_ohh(text), Err(OhhError::InvalidJson));
+    }
+}
@@ -1,6 +1,12 @@
-use aya_base::{Hand, CARDS};
+use aya_base::{Card, Hand, Rank, CARDS};
 
-use crate::{ace_five_rank, insert_cards, poker_rank, AceFiveHandRank, PokerHandRank};
+use crate::{
+    ace_five_rank, for_each_subhand, insert_cards, poker_rank, short_deck_rank, AceFiveHandRank,
+    PokerHandRank, ShortDeckHandRank,
+};
+
+/// Rank mask of the A-7-8-9-T straight, the lowest straight in Manila.
+const MANILA_WHEEL: u16 = 0x11e0;
 
 /// Returns the rank of the best 5-card poker hand that can be made with
 /// two hole cards and three board cards.
@@ -22,6 +28,10 @@ use crate::{ace_five_rank, insert_cards, poker_rank, AceFiveHandRank, PokerHandR
 /// # Ok::<(), aya_poker::base::ParseError>(())
 /// ```
 pub fn omaha_rank(hole: &Hand, board: &Hand) -> PokerHandRank {
+    if hole.len() < 2 || board.len() < 3 {
+        return PokerHandRank(0);
+    }
+
     let mut buffer = [CARDS[0]; 7];
     let hole_cards = insert_cards(hole, &mut buffer);
 
@@ -73,6 +83,10 @@ pub fn omaha_rank(hole: &Hand, board: &Hand) -> PokerHandRank {
 /// # Ok::<(), aya_poker::base::ParseError>(())
 /// ```
 pub fn omaha_lo_rank(hole: &Hand, board: &Hand) -> AceFiveHandRank {
+    if hole.len() < 2 || board.len() < 3 {
+        return AceFiveHandRank(0);
+    }
+
     let mut buffer = [CARDS[0]; 7];
     let hole_cards = insert_cards(hole, &mut buffer);
 
@@ -104,10 +118,118 @@ pub fn omaha_lo_rank(hole: &Hand, board: &Hand) -> AceFiveHandRank {
     max_lo_rank
 }
 
+/// Returns the rank of the best 5-card poker hand that can be made with
+/// exactly `hole_count` hole cards and `5 - hole_count` board cards.
+///
+/// This generalizes the rule of [`omaha_rank`], which is the special case
+/// `hole_count = 2`, to any split between the hole cards and the board, e.g.
+/// Pineapple variants where a player must play exactly one hole card, or
+/// games with four or more board cards which must all play. Games in which
+/// the hole cards may be used freely, like Texas hold'em or Irish after the
+/// discard, should use [`poker_rank`] on the combined hand instead.
+///
+/// If `hole_count` is greater than 5, or there are fewer than `hole_count`
+/// hole cards or fewer than `5 - hole_count` board cards, it returns a
+/// ranking of Invalid (0).
+///
+/// # Examples
+/// ```
+/// use aya_poker::{exact_hole_rank, omaha_rank, PokerRankCategory};
+///
+/// let hole_cards = "Ah Kd 4c 3c".parse()?;
+/// let board_cards = "Jh 8h 5h 2h 9s".parse()?;
+/// let rank = exact_hole_rank(&hole_cards, &board_cards, 1);
+/// assert_eq!(rank.rank_category(), PokerRankCategory::Flush);
+/// let rank = exact_hole_rank(&hole_cards, &board_cards, 2);
+/// assert_eq!(rank, omaha_rank(&hole_cards, &board_cards));
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn exact_hole_rank(hole: &Hand, board: &Hand, hole_count: usize) -> PokerHandRank {
+    if hole_count > 5 {
+        return PokerHandRank(0);
+    }
+    best_exact_hole_hand(hole, board, hole_count, PokerHandRank(0), poker_rank)
+}
+
+/// Returns the rank of the best 5-card Manila poker hand that can be made
+/// with precisely two hole cards and three board cards.
+///
+/// Manila is played with a 32-card deck of sevens through aces. As in
+/// [`short_deck_rank`], flushes rank higher than full houses, while the
+/// ace can play low only in the A-7-8-9-T straight.
+///
+/// The caller is responsible for verifying that neither the hole nor the
+/// board cards contain any cards of rank less than 7. If there are fewer than
+/// 2 hole cards or fewer than 3 board cards, it returns `None`.
+///
+/// # Examples
+/// ```
+/// use aya_poker::{manila_rank, PokerRankCategory};
+///
+/// let hole_cards = "Ah 9d".parse()?;
+/// let board_cards = "Ts 8h 7h Kh Kc".parse()?;
+/// let rank = manila_rank(&hole_cards, &board_cards).unwrap();
+/// assert_eq!(rank.rank_category(), PokerRankCategory::Straight);
+/// assert_eq!(manila_rank(&hole_cards, &"Ts 8h".parse()?), None);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn manila_rank(hole: &Hand, board: &Hand) -> Option<ShortDeckHandRank> {
+    if hole.len() < 2 || board.len() < 3 {
+        return None;
+    }
+
+    let rank = best_exact_hole_hand(hole, board, 2, ShortDeckHandRank(0), |hand| {
+        if hand.rank_mask() == MANILA_WHEEL {
+            // Rank the A-7-8-9-T straight as the lowest short-deck straight,
+            // i.e. A-6-7-8-9, by swapping out the ten for a six.
+            let wheel = hand
+                .iter()
+                .map(|&c| match c.rank() {
+                    Rank::Ten => Card::new(Rank::Six, c.suit()),
+                    _ => c,
+                })
+                .collect();
+            short_deck_rank(&wheel)
+        } else {
+            short_deck_rank(hand)
+        }
+    });
+    Some(rank)
+}
+
+fn best_exact_hole_hand<R: Ord + Copy>(
+    hole: &Hand,
+    board: &Hand,
+    hole_count: usize,
+    invalid: R,
+    rank_fn: impl Fn(&Hand) -> R,
+) -> R {
+    let mut buffer = [CARDS[0]; 7];
+    let hole_cards = insert_cards(hole, &mut buffer);
+
+    let mut buffer = [CARDS[0]; 7];
+    let community_cards = insert_cards(board, &mut buffer);
+
+    let board_count = 5 - hole_count;
+    if hole_cards.len() < hole_count || community_cards.len() < board_count {
+        return invalid;
+    }
+
+    let mut max_rank = invalid;
+    for_each_subhand(hole_cards, hole_count, Hand::new(), &mut |hole_hand| {
+        for_each_subhand(community_cards, board_count, hole_hand, &mut |hand| {
+            max_rank = max_rank.max(rank_fn(&hand));
+        });
+    });
+
+    max_rank
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::base::ParseError;
+    use crate::{base::ParseError, PokerRankCategory};
+    use rstest::rstest;
 
     #[test]
     fn omaha_rank_ordering() -> Result<(), ParseError> {
@@ -372,4 +494,88 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn exact_two_matches_omaha() {
+        let mut rng = fastrand::Rng::with_seed(42);
+        for _ in 0..1000 {
+            let mut deck = CARDS;
+            rng.shuffle(&mut deck);
+            let hole = deck[..4].iter().collect();
+            let board = deck[4..9].iter().collect();
+
+            assert_eq!(exact_hole_rank(&hole, &board, 2), omaha_rank(&hole, &board));
+        }
+    }
+
+    #[rstest]
+    #[case::board_plays("Ah Ad", "Kh Qh Jh Th 9h", 0, PokerRankCategory::StraightFlush)]
+    #[case::one_hole_card("Ah Ad", "Kh Qh Jh Th 9h", 1, PokerRankCategory::RoyalFlush)]
+    #[case::three_hole_cards("Ah Ad Ac 2c", "Kh Qh Jh Th 9h", 3, PokerRankCategory::ThreeOfAKind)]
+    #[case::too_few_hole_cards("Ah", "Kh Qh Jh Th 9h", 2, PokerRankCategory::Ineligible)]
+    #[case::too_few_board_cards("Ah Ad As", "Kh Qh", 2, PokerRankCategory::Ineligible)]
+    #[case::too_many_hole_cards("Ah Ad Ac 2c 2d 2h", "Kh Qh Jh", 6, PokerRankCategory::Ineligible)]
+    fn exact_hole_category(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] hole_count: usize,
+        #[case] expected_category: PokerRankCategory,
+    ) -> Result<(), ParseError> {
+        let rank = exact_hole_rank(&hole.parse()?, &board.parse()?, hole_count);
+        assert_eq!(rank.rank_category(), expected_category);
+        Ok(())
+    }
+
+    #[test]
+    fn manila_rank_ordering() -> Result<(), ParseError> {
+        let hands = [
+            ("Ah 7c", "Kd Qs 9h 8c 8d"), // 8 8 A K Q
+            ("Ah 9d", "Ts 8h 7h Kh Kc"), // A 7 8 9 T
+            ("7c 8c", "Ts 9h Jh Kh Kc"), // 7 8 9 T J
+            ("Kc Kd", "Ks 7c 7d Ac Ad"), // K K K A A
+            ("7h 9h", "Ah Jh 8h Ac Ad"), // A J 9 8 7
+            ("Ac 8c", "Tc Jc Kc 7d 7h"), // A K J T 8
+            ("Ah Ac", "Ad As Kc 7s 7h"), // A A A A K
+            ("7c 8c", "Tc 9c Jc Ad Ah"), // J T 9 8 7
+        ]
+        .map(|(h, b)| (h.parse(), b.parse()));
+
+        for (&(h1, b1), &(h2, b2)) in hands.iter().zip(hands.iter().skip(1)) {
+            let r1 = manila_rank(&h1?, &b1?).unwrap();
+            let r2 = manila_rank(&h2?, &b2?).unwrap();
+
+            assert!(
+                r1 < r2,
+                "[{:?}], [{:?}] is ranked {:?}, which is larger than [{:?}], [{:?}] ({:?}).",
+                h1,
+                b1,
+                r1,
+                h2,
+                b2,
+                r2
+            );
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::too_few_hole_cards("Ah", "Ts 8h 7h Kh Kc")]
+    #[case::too_few_board_cards("Ah 9d", "Ts 8h")]
+    fn manila_invalid_hands(#[case] hole: &str, #[case] board: &str) -> Result<(), ParseError> {
+        assert_eq!(manila_rank(&hole.parse()?, &board.parse()?), None);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::no_hole_cards("", "Ts 8h 7h Kh Kc")]
+    #[case::too_few_hole_cards("Ah", "Ts 8h 7h Kh Kc")]
+    #[case::no_board_cards("Ah 2d Kc Kd", "")]
+    #[case::too_few_board_cards("Ah 2d Kc Kd", "Ts")]
+    fn omaha_invalid_hands(#[case] hole: &str, #[case] board: &str) -> Result<(), ParseError> {
+        let (hole, board) = (hole.parse()?, board.parse()?);
+        assert_eq!(omaha_rank(&hole, &board), PokerHandRank(0));
+        assert_eq!(omaha_lo_rank(&hole, &board), AceFiveHandRank(0));
+        Ok(())
+    }
 }
@@ -0,0 +1,597 @@
+//! Omaha hand ranges in ProPokerTools-style notation.
+//!
+//! An Omaha range is written as a comma-separated list of alternatives, each
+//! of which is a `:`-separated list of conditions that a hand must all
+//! satisfy. A condition can be
+//!
+//! - a pattern of cards that must be part of the hand, e.g. `"AA"` or
+//!   `"AA**"`, both matching any hand with two aces, where each card is given
+//!   by a rank or `*` for any rank, optionally followed by a suit (`"As"`) or a
+//!   suit variable `w`, `x`, `y` or `z` (`"AxKx"` is a suited ace and king).
+//!   Cards with the same suit variable must have the same suit, and cards
+//!   with different suit variables must have different suits. A lone suit
+//!   variable matches a card of any rank, so `"xxyy"` is any double-suited
+//!   hand,
+//! - a span of patterns that differ by a constant rank offset, e.g. the
+//!   rundowns `"KQJT-6543"`,
+//! - one of the macros `$ds` (double-suited), `$ss` (single-suited), `$r`
+//!   (rainbow), `$np` (unpaired) or `$R` (containing a four-card rundown),
+//! - a condition preceded by `!`, which matches the hands that do not satisfy
+//!   it.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::omaha_range::OmahaRange;
+//!
+//! let range = "AA**:$ds, KK**:xxyy, JT98-6543".parse::<OmahaRange>()?;
+//! assert!(range.contains(&"Ah Ad Kh 7d".parse()?));
+//! assert!(!range.contains(&"Ah Ad Kh 7c".parse()?));
+//! assert!(range.contains(&"Kc Ks 2c 3s".parse()?));
+//! assert!(range.contains(&"8h 7c 6d 5s".parse()?));
+//!
+//! let range = "AA:!$r".parse::<OmahaRange>()?;
+//! assert!(!range.contains(&"As Ah Kd Qc".parse()?));
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use aya_base::{constants::CARD_COUNT, Card, Hand, ParseError, Rank, Suit, CARDS};
+
+use crate::for_each_subhand;
+
+/// The character used to denote each rank in range notation.
+const RANK_CHARS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+/// The characters used to denote each suit in range notation.
+const SUIT_CHARS: [char; 4] = ['c', 'd', 'h', 's'];
+
+/// The characters used to denote suit variables in range notation.
+const SUIT_VARIABLES: [char; 4] = ['w', 'x', 'y', 'z'];
+
+/// A range of Omaha starting hands.
+///
+/// Unlike the hold'em [`Range`](crate::range::Range), an Omaha range is
+/// stored as a condition on the hole cards rather than as the set of all
+/// matching hands, which allows it to describe hands with any number of hole
+/// cards.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OmahaRange {
+    alternatives: Vec<Vec<Condition>>,
+}
+
+impl OmahaRange {
+    /// Creates a new range containing every starting hand.
+    pub fn full() -> OmahaRange {
+        OmahaRange {
+            alternatives: alloc::vec![Vec::new()],
+        }
+    }
+
+    /// Returns `true` if the hand belongs to the range.
+    pub fn contains(&self, hand: &Hand) -> bool {
+        let mut cards = [CARDS[0]; CARD_COUNT];
+        let mut len = 0;
+        for &card in hand.iter() {
+            cards[len] = card;
+            len += 1;
+        }
+        let cards = &cards[..len];
+
+        self.alternatives
+            .iter()
+            .any(|conditions| conditions.iter().all(|c| c.matches(hand, cards)))
+    }
+
+    /// Returns all the hands with `hole_cards` cards in the range which do
+    /// not contain any of the `dead` cards.
+    ///
+    /// Note that there are more than 20 million six-card hands, so listing
+    /// the hands of a wide six-card range can be slow and use a lot of
+    /// memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::omaha_range::OmahaRange;
+    ///
+    /// let range = "AAKK:$ds".parse::<OmahaRange>()?;
+    /// assert_eq!(range.hands(4, &"".parse()?).len(), 6);
+    /// assert_eq!(range.hands(4, &"As".parse()?).len(), 3);
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn hands(&self, hole_cards: usize, dead: &Hand) -> Vec<Hand> {
+        // The fully specified cards of a pattern must be part of every
+        // matching hand, so only the remaining cards need to be enumerated.
+        let mut required = None;
+        for conditions in self.alternatives.iter() {
+            let mut alternative_required = Hand::new();
+            for condition in conditions {
+                if let Condition::Pattern(pattern) = condition {
+                    alternative_required.extend(pattern.required_cards().iter());
+                }
+            }
+            required = Some(match required {
+                None => alternative_required,
+                Some(cards) => intersection(&cards, &alternative_required),
+            });
+        }
+        let required = required.unwrap_or_default();
+
+        let mut hands = Vec::new();
+        if required.len() > hole_cards || !required.is_disjoint(dead) {
+            return hands;
+        }
+
+        let mut deck = [CARDS[0]; CARD_COUNT];
+        let mut deck_len = 0;
+        for card in CARDS.iter() {
+            if !required.contains(card) && !dead.contains(card) {
+                deck[deck_len] = *card;
+                deck_len += 1;
+            }
+        }
+
+        let missing = hole_cards - required.len();
+        if missing <= deck_len {
+            for_each_subhand(&deck[..deck_len], missing, required, &mut |hand| {
+                if self.contains(&hand) {
+                    hands.push(hand);
+                }
+            });
+        }
+        hands
+    }
+}
+
+fn intersection(h1: &Hand, h2: &Hand) -> Hand {
+    h1.iter().filter(|c| h2.contains(c)).collect()
+}
+
+impl FromStr for OmahaRange {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let alternatives = s
+            .split(',')
+            .map(|alternative| {
+                // A lone `*` matches every hand, as displayed for the
+                // alternatives without any conditions.
+                if alternative.trim() == "*" {
+                    return Ok(Vec::new());
+                }
+                alternative
+                    .split(':')
+                    .map(|condition| condition.trim().parse::<Condition>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(OmahaRange { alternatives })
+    }
+}
+
+impl fmt::Display for OmahaRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, conditions) in self.alternatives.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if conditions.is_empty() {
+                write!(f, "*")?;
+            }
+            for (j, condition) in conditions.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ":")?;
+                }
+                write!(f, "{}", condition)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for OmahaRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OmahaRange({})", self)
+    }
+}
+
+/// A condition on the hole cards of a hand.
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Condition {
+    Pattern(Pattern),
+    Span(Pattern, Pattern),
+    Macro(Macro),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    fn matches(&self, hand: &Hand, cards: &[Card]) -> bool {
+        match self {
+            Condition::Pattern(pattern) => pattern.matches(cards),
+            Condition::Span(first, last) => {
+                let (offset, high) = match first.offset_to(last) {
+                    Some(offset) if offset >= 0 => (offset, first),
+                    Some(offset) => (-offset, last),
+                    None => return false,
+                };
+                (0..=offset).any(|shift| high.shifted(shift).matches(cards))
+            }
+            Condition::Macro(m) => m.matches(hand, cards),
+            Condition::Not(condition) => !condition.matches(hand, cards),
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(negated) = s.strip_prefix('!') {
+            return Ok(Condition::Not(Box::new(negated.trim().parse()?)));
+        }
+        if let Some(name) = s.strip_prefix('$') {
+            return Ok(Condition::Macro(name.parse()?));
+        }
+        if let Some((first, last)) = s.split_once('-') {
+            let first = first.trim().parse::<Pattern>()?;
+            let last = last.trim().parse::<Pattern>()?;
+            if first.offset_to(&last).is_none() {
+                return Err(ParseError);
+            }
+            return Ok(Condition::Span(first, last));
+        }
+        Ok(Condition::Pattern(s.parse()?))
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Pattern(pattern) => write!(f, "{}", pattern),
+            Condition::Span(first, last) => write!(f, "{}-{}", first, last),
+            Condition::Macro(m) => write!(f, "{}", m),
+            Condition::Not(condition) => write!(f, "!{}", condition),
+        }
+    }
+}
+
+/// A predefined condition on the suits or ranks of the hole cards.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Macro {
+    DoubleSuited,
+    SingleSuited,
+    Rainbow,
+    NoPair,
+    Rundown,
+}
+
+impl Macro {
+    fn matches(self, hand: &Hand, cards: &[Card]) -> bool {
+        let suited_suits = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades]
+            .into_iter()
+            .filter(|&suit| hand.suit_mask(suit).count_ones() >= 2)
+            .count();
+
+        match self {
+            Macro::DoubleSuited => suited_suits >= 2,
+            Macro::SingleSuited => suited_suits == 1,
+            Macro::Rainbow => suited_suits == 0,
+            Macro::NoPair => hand.rank_mask().count_ones() as usize == cards.len(),
+            Macro::Rundown => {
+                // Also treat the ace as a one, for A-2-3-4 rundowns.
+                let ranks = hand.rank_mask();
+                let ranks = (ranks << 1) | (ranks >> 12 & 1);
+                (0..11).any(|low| (ranks >> low) & 0b1111 == 0b1111)
+            }
+        }
+    }
+}
+
+impl FromStr for Macro {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ds" => Ok(Macro::DoubleSuited),
+            "ss" => Ok(Macro::SingleSuited),
+            "r" => Ok(Macro::Rainbow),
+            "np" => Ok(Macro::NoPair),
+            "R" => Ok(Macro::Rundown),
+            _ => Err(ParseError),
+        }
+    }
+}
+
+impl fmt::Display for Macro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Macro::DoubleSuited => "ds",
+            Macro::SingleSuited => "ss",
+            Macro::Rainbow => "r",
+            Macro::NoPair => "np",
+            Macro::Rundown => "R",
+        };
+        write!(f, "${}", name)
+    }
+}
+
+/// The suit required of a card in a pattern.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SuitPattern {
+    Any,
+    Suit(Suit),
+    Variable(usize),
+}
+
+/// A card in a pattern.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct CardPattern {
+    rank: Option<Rank>,
+    suit: SuitPattern,
+}
+
+impl CardPattern {
+    fn matches(&self, card: &Card) -> bool {
+        self.rank.map_or(true, |rank| card.rank() == rank)
+            && match self.suit {
+                SuitPattern::Suit(suit) => card.suit() == suit,
+                _ => true,
+            }
+    }
+}
+
+/// A pattern of cards which must be part of a hand.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Pattern {
+    cards: Vec<CardPattern>,
+}
+
+impl Pattern {
+    /// Returns `true` if the cards of the pattern can be matched to distinct
+    /// cards of the hand.
+    fn matches(&self, cards: &[Card]) -> bool {
+        let mut used = [false; CARD_COUNT];
+        let mut variables = [None; 4];
+        self.cards.len() <= cards.len() && self.assign(0, cards, &mut used, &mut variables)
+    }
+
+    fn assign(
+        &self,
+        idx: usize,
+        cards: &[Card],
+        used: &mut [bool],
+        variables: &mut [Option<Suit>; 4],
+    ) -> bool {
+        let pattern = match self.cards.get(idx) {
+            Some(pattern) => pattern,
+            None => return true,
+        };
+
+        for (i, card) in cards.iter().enumerate() {
+            if used[i] || !pattern.matches(card) {
+                continue;
+            }
+
+            let mut bound = *variables;
+            if let SuitPattern::Variable(v) = pattern.suit {
+                match bound[v] {
+                    Some(suit) if suit != card.suit() => continue,
+                    Some(_) => {}
+                    None if bound.contains(&Some(card.suit())) => continue,
+                    None => bound[v] = Some(card.suit()),
+                }
+            }
+
+            used[i] = true;
+            if self.assign(idx + 1, cards, used, &mut bound) {
+                return true;
+            }
+            used[i] = false;
+        }
+
+        false
+    }
+
+    /// Returns the cards with both a fixed rank and suit.
+    fn required_cards(&self) -> Hand {
+        self.cards
+            .iter()
+            .filter_map(|c| match (c.rank, c.suit) {
+                (Some(rank), SuitPattern::Suit(suit)) => Some(Card::new(rank, suit)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the rank offset between two patterns which differ only in the
+    /// ranks of their cards by a constant offset.
+    fn offset_to(&self, other: &Pattern) -> Option<i32> {
+        if self.cards.len() != other.cards.len() || self.cards.is_empty() {
+            return None;
+        }
+
+        let mut offset = None;
+        for (c1, c2) in self.cards.iter().zip(other.cards.iter()) {
+            let (r1, r2) = (c1.rank?, c2.rank?);
+            let diff = r1 as i32 - r2 as i32;
+            if c1.suit != c2.suit || offset.map_or(false, |o| o != diff) {
+                return None;
+            }
+            offset = Some(diff);
+        }
+        offset
+    }
+
+    /// Returns the pattern with every rank lowered by `shift`.
+    fn shifted(&self, shift: i32) -> Pattern {
+        let cards = self
+            .cards
+            .iter()
+            .map(|c| CardPattern {
+                rank: c
+                    .rank
+                    .map(|r| Rank::try_from((r as i32 - shift) as u8).unwrap()),
+                suit: c.suit,
+            })
+            .collect();
+        Pattern { cards }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards = Vec::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            let rank = if c == '*' {
+                None
+            } else if let Some(r) = RANK_CHARS.iter().position(|&rc| rc == c) {
+                Some(Rank::try_from(r as u8)?)
+            } else if let Some(v) = SUIT_VARIABLES.iter().position(|&vc| vc == c) {
+                cards.push(CardPattern {
+                    rank: None,
+                    suit: SuitPattern::Variable(v),
+                });
+                continue;
+            } else {
+                return Err(ParseError);
+            };
+
+            let suit = match chars.peek() {
+                Some(&c) if SUIT_CHARS.contains(&c) => {
+                    chars.next();
+                    let mut buffer = [0; 4];
+                    SuitPattern::Suit(c.encode_utf8(&mut buffer).parse()?)
+                }
+                Some(&c) if SUIT_VARIABLES.contains(&c) => {
+                    chars.next();
+                    SuitPattern::Variable(SUIT_VARIABLES.iter().position(|&v| v == c).unwrap())
+                }
+                _ => SuitPattern::Any,
+            };
+            cards.push(CardPattern { rank, suit });
+        }
+
+        if cards.is_empty() || cards.len() > 6 {
+            return Err(ParseError);
+        }
+        Ok(Pattern { cards })
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in self.cards.iter() {
+            match (card.rank, card.suit) {
+                (None, SuitPattern::Variable(v)) => write!(f, "{}", SUIT_VARIABLES[v])?,
+                (rank, suit) => {
+                    match rank {
+                        Some(rank) => write!(f, "{}", RANK_CHARS[rank as usize])?,
+                        None => write!(f, "*")?,
+                    }
+                    match suit {
+                        SuitPattern::Any => {}
+                        SuitPattern::Suit(suit) => write!(f, "{}", SUIT_CHARS[suit as usize])?,
+                        SuitPattern::Variable(v) => write!(f, "{}", SUIT_VARIABLES[v])?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("AA", "Ah Ad 7c 2s", true)]
+    #[case("AA**", "Ah Ad 7c 2s", true)]
+    #[case("AA", "Ah Kd 7c 2s", false)]
+    #[case("AAA", "Ah Ad Ac 2s", true)]
+    #[case("AxKx", "Ah Kh 7c 2s", true)]
+    #[case("AxKx", "Ah Kd 7h 2s", false)]
+    #[case("AxKy", "Ah Kh 7c 2s", false)]
+    #[case("AsKs", "As Ks 7c 2s", true)]
+    #[case("xxyy", "Ah Kh 7c 2c", true)]
+    #[case("xxyy", "Ah Kh 7h 2c", false)]
+    #[case("xxyy", "Ah Kh 7h 2h", false)]
+    #[case("$ds", "Ah Kh 7c 2c", true)]
+    #[case("$ds", "Ah Kh 7h 2c", false)]
+    #[case("$ss", "Ah Kh 7h 2c", true)]
+    #[case("$ss", "Ah Kh 7c 2c", false)]
+    #[case("$r", "Ah Kd 7c 2s", true)]
+    #[case("$r", "Ah Kh 7c 2s", false)]
+    #[case("$np", "Ah Kd 7c 2s", true)]
+    #[case("$np", "Ah Ad 7c 2s", false)]
+    #[case("$R", "Ah Kd Qc Js", true)]
+    #[case("$R", "Ah 2d 3c 4s", true)]
+    #[case("$R", "Ah Kd Qc Ts", false)]
+    #[case("$R", "9h 8d 7c 6s 2d", true)]
+    #[case("KK**:xxyy", "Kh Ks 7h 2s", true)]
+    #[case("KK**:xxyy", "Kh Ks 7c 2d", false)]
+    #[case("KK:!$r", "Kh Ks 7c 2d", false)]
+    #[case("KK:!$r", "Kh Ks 7c 2h", true)]
+    #[case("JT98-6543", "9h 8d 7c 6s", true)]
+    #[case("6543-JT98", "9h 8d 7c 6s", true)]
+    #[case("JT98-6543", "9h 8d 7c 5s", false)]
+    #[case("AA, KK", "Kh Ks 7c 2d", true)]
+    #[case("*", "Kh Ks 7c 2d", true)]
+    #[case("AA**", "Ah Ad 7c 2s Kd", true)]
+    #[case("AA**", "Ah Kd 7c 2s 3s 4s", false)]
+    fn contains(#[case] range: &str, #[case] hand: &str, #[case] expected: bool) {
+        let range = range.parse::<OmahaRange>().unwrap();
+        let hand = hand.parse::<Hand>().unwrap();
+        assert_eq!(range.contains(&hand), expected);
+    }
+
+    #[rstest]
+    #[case("AB")]
+    #[case("A**:$xx")]
+    #[case("AA,,KK")]
+    #[case("KQJT-543")]
+    #[case("KQJT-6543s")]
+    #[case("AAKKQQJ")]
+    fn parse_errors(#[case] s: &str) {
+        assert_eq!(s.parse::<OmahaRange>(), Err(ParseError));
+    }
+
+    #[rstest]
+    #[case("AA**:$ds, KK**:xxyy")]
+    #[case("AxKx**, !$r:JT98-6543")]
+    #[case("AsKs, *")]
+    fn display_round_trip(#[case] s: &str) {
+        let range = s.parse::<OmahaRange>().unwrap();
+        assert_eq!(range.to_string(), s);
+    }
+
+    #[test]
+    fn full_range_round_trip() {
+        let full = OmahaRange::full();
+        assert_eq!(full.to_string().parse::<OmahaRange>(), Ok(full));
+    }
+
+    #[rstest]
+    #[case("AA", 4, 6 * 1128 + 4 * 48 + 1)]
+    #[case("AAKK", 4, 36)]
+    #[case("AAKK:$ds", 4, 6)]
+    #[case("AsAhKsKh", 4, 1)]
+    #[case("AsAhKsKhQs", 5, 1)]
+    #[case("AsAhKsKhQsQh", 6, 1)]
+    #[case("$ds", 4, 6 * 78 * 78)]
+    fn hand_counts(#[case] range: &str, #[case] hole_cards: usize, #[case] expected: usize) {
+        let range = range.parse::<OmahaRange>().unwrap();
+        let hands = range.hands(hole_cards, &Hand::new());
+        assert_eq!(hands.len(), expected);
+        assert!(hands.iter().all(|h| h.len() == hole_cards));
+    }
+}
@@ -0,0 +1,367 @@
+//! Outs and draw classification for hold'em hands on the flop and turn.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::outs::{analyze_draws, Draw};
+//! use aya_poker::PokerRankCategory;
+//!
+//! let analysis = analyze_draws(&"Ah Kh".parse()?, &"Qh 7h 2c".parse()?, &"".parse()?).unwrap();
+//! assert!(analysis.has_draw(Draw::FlushDraw));
+//! assert!(analysis.has_draw(Draw::Overcards));
+//! assert_eq!(analysis.outs_to(PokerRankCategory::Flush).count(), 9);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::vec::Vec;
+
+use aya_base::{constants::RANK_OFFSET, Card, Hand, Suit, CARDS};
+
+use crate::{equity::EquityError, poker_rank, PokerHandRank, PokerRankCategory};
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+const HIGH_CARD_LEVEL: usize = 0;
+const STRAIGHT_LEVEL: usize = 4;
+const FLUSH_LEVEL: usize = 5;
+
+/// A kind of drawing hand.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Draw {
+    /// Four cards of the same suit, at least one of them a hole card.
+    FlushDraw,
+    /// Three cards of the same suit on the flop, at least one of them a hole
+    /// card, which need both the turn and the river to make a flush.
+    BackdoorFlushDraw,
+    /// Four cards of sequential rank which make a straight with a card of
+    /// either of two ranks.
+    OpenEndedStraightDraw,
+    /// Cards which make a straight with a card of either of two ranks, but
+    /// not with four cards of sequential rank, e.g. 9-7-6-5-3.
+    DoubleGutshot,
+    /// Cards which make a straight with a card of a single rank.
+    Gutshot,
+    /// Cards on the flop which make a straight with cards of two more ranks.
+    BackdoorStraightDraw,
+    /// Two hole cards ranked higher than every card on the board, without
+    /// having made a pair or better.
+    Overcards,
+}
+
+/// A card which improves a hand to a better hand-ranking category.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct Out {
+    /// The card improving the hand.
+    pub card: Card,
+    /// The category of the hand made with the card.
+    pub category: PokerRankCategory,
+    /// Whether no opponent could make a hand of a better category than the
+    /// improved hand once the card is dealt.
+    pub clean: bool,
+}
+
+/// The outs and draws of a hold'em hand.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct DrawAnalysis {
+    /// The category of the hand currently made by the hole cards and the
+    /// board.
+    pub category: PokerRankCategory,
+    /// The draws held by the hand.
+    pub draws: Vec<Draw>,
+    /// The outs of the hand, in the order of the deck.
+    pub outs: Vec<Out>,
+}
+
+impl DrawAnalysis {
+    /// Returns `true` if the hand holds the given draw.
+    pub fn has_draw(&self, draw: Draw) -> bool {
+        self.draws.contains(&draw)
+    }
+
+    /// Returns an iterator over the outs improving the hand to the given
+    /// category.
+    pub fn outs_to(&self, category: PokerRankCategory) -> impl Iterator<Item = &Out> + '_ {
+        self.outs.iter().filter(move |out| out.category == category)
+    }
+
+    /// Returns an iterator over the clean outs of the hand.
+    pub fn clean_outs(&self) -> impl Iterator<Item = &Out> + '_ {
+        self.outs.iter().filter(|out| out.clean)
+    }
+}
+
+/// Returns the outs and draws of the hole cards on the given flop or turn.
+///
+/// A card, other than the dealt or `dead` cards, is counted as an out if it
+/// improves the hand made with [`poker_rank`] to a better hand-ranking
+/// category than both its current one and the one made by the board and the
+/// card alone, so cards which only pair the board are not outs. An out is
+/// clean if no opponent holding two of the remaining cards could make a hand
+/// of a better category than the improved hand.
+///
+/// Returns an error if the hole cards are not exactly two cards, if the
+/// board does not hold three or four cards, or if any card was dealt twice.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::outs::{analyze_draws, Draw};
+/// use aya_poker::PokerRankCategory;
+///
+/// let analysis = analyze_draws(&"9c 8d".parse()?, &"Ts 7h 2d".parse()?, &"".parse()?).unwrap();
+/// assert!(analysis.has_draw(Draw::OpenEndedStraightDraw));
+/// assert_eq!(analysis.outs_to(PokerRankCategory::Straight).count(), 8);
+/// // Pairing a hole card could still lose to two pair or better.
+/// assert_eq!(analysis.outs_to(PokerRankCategory::Pair).count(), 6);
+/// assert_eq!(analysis.clean_outs().count(), 8);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn analyze_draws(hole: &Hand, board: &Hand, dead: &Hand) -> Result<DrawAnalysis, EquityError> {
+    if hole.len() != 2 {
+        return Err(EquityError::InvalidHoleCards);
+    }
+    if board.len() < 3 || board.len() > 4 {
+        return Err(EquityError::InvalidBoard);
+    }
+    if !hole.is_disjoint(board) || !dead.is_disjoint(hole) || !dead.is_disjoint(board) {
+        return Err(EquityError::DuplicateCard);
+    }
+
+    let mut hand = *hole;
+    hand.extend(board.iter());
+    let rank = poker_rank(&hand);
+    let mut dealt = hand;
+    dealt.extend(dead.iter());
+
+    let outs = CARDS
+        .iter()
+        .filter(|card| !dealt.contains(card))
+        .filter_map(|card| {
+            let improved = poker_rank(&with_card(&hand, card));
+            let level = category_level(improved);
+            let is_out = level > category_level(rank)
+                && level > category_level(poker_rank(&with_card(board, card)));
+            is_out.then(|| Out {
+                card: *card,
+                category: improved.rank_category(),
+                clean: is_clean(&with_card(&dealt, card), &with_card(board, card), level),
+            })
+        })
+        .collect();
+
+    Ok(DrawAnalysis {
+        category: rank.rank_category(),
+        draws: classify_draws(hole, board, &hand, rank),
+        outs,
+    })
+}
+
+fn classify_draws(hole: &Hand, board: &Hand, hand: &Hand, rank: PokerHandRank) -> Vec<Draw> {
+    let mut draws = Vec::new();
+    let is_flop = board.len() == 3;
+    let level = category_level(rank);
+
+    if level < FLUSH_LEVEL {
+        for suit in SUITS {
+            if hole.suit_mask(suit) == 0 {
+                continue;
+            }
+            match hand.suit_mask(suit).count_ones() {
+                4 => draws.push(Draw::FlushDraw),
+                3 if is_flop => draws.push(Draw::BackdoorFlushDraw),
+                _ => {}
+            }
+        }
+    }
+
+    if level < STRAIGHT_LEVEL {
+        let (hand_mask, board_mask) = (hand.rank_mask(), board.rank_mask());
+        let completes =
+            |added: u16| straights(hand_mask | added) & !straights(board_mask | added) != 0;
+
+        let completing_ranks = (0..13)
+            .map(|r| 1 << r)
+            .filter(|&bit| hand_mask & bit == 0 && completes(bit))
+            .count();
+
+        match completing_ranks {
+            0 if is_flop => {
+                let backdoor = (0..13).any(|r1| {
+                    (0..r1).any(|r2| {
+                        let added = (1 << r1) | (1 << r2);
+                        hand_mask & added == 0 && completes(added)
+                    })
+                });
+                if backdoor {
+                    draws.push(Draw::BackdoorStraightDraw);
+                }
+            }
+            0 => {}
+            1 => draws.push(Draw::Gutshot),
+            _ if has_four_in_a_row(hand_mask) => draws.push(Draw::OpenEndedStraightDraw),
+            _ => draws.push(Draw::DoubleGutshot),
+        }
+    }
+
+    let high_board_rank = 15 - board.rank_mask().leading_zeros();
+    let low_hole_rank = hole.rank_mask().trailing_zeros();
+    if level == HIGH_CARD_LEVEL && low_hole_rank > high_board_rank {
+        draws.push(Draw::Overcards);
+    }
+
+    draws
+}
+
+/// Returns whether no opponent could make a hand of a category better than
+/// the given one on the board.
+fn is_clean(dealt: &Hand, board: &Hand, level: usize) -> bool {
+    let remaining = CARDS
+        .iter()
+        .filter(|card| !dealt.contains(card))
+        .collect::<Vec<_>>();
+
+    remaining.iter().enumerate().all(|(i, first)| {
+        remaining[(i + 1)..].iter().all(|second| {
+            let opponent = with_card(&with_card(board, first), second);
+            category_level(poker_rank(&opponent)) <= level
+        })
+    })
+}
+
+/// Returns a mask of the straights present in the 13-bit rank mask, with
+/// bit `i` set if there is a straight whose lowest card has rank `i - 1`,
+/// where the rank of the ace is -1 in the wheel.
+fn straights(rank_mask: u16) -> u16 {
+    let mask = (rank_mask << 1) | (rank_mask >> 12);
+    mask & (mask >> 1) & (mask >> 2) & (mask >> 3) & (mask >> 4)
+}
+
+/// Returns whether the rank mask holds four cards of sequential rank which
+/// can be completed to a straight at both ends.
+fn has_four_in_a_row(rank_mask: u16) -> bool {
+    // Only a run ending with the ace cannot be completed at both ends, as
+    // the ace also completes 2-3-4-5 from below.
+    let runs = rank_mask & (rank_mask >> 1) & (rank_mask >> 2) & (rank_mask >> 3);
+    runs & 0x01ff != 0
+}
+
+fn with_card(hand: &Hand, card: &Card) -> Hand {
+    let mut hand = *hand;
+    hand.insert_unchecked(card);
+    hand
+}
+
+fn category_level(rank: PokerHandRank) -> usize {
+    rank.0 as usize / RANK_OFFSET
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::flush_draw(
+        "Ah Kh",
+        "Qh 7h 2c",
+        &[Draw::FlushDraw, Draw::BackdoorStraightDraw, Draw::Overcards]
+    )]
+    #[case::open_ender("9c 8d", "Ts 7h 2d", &[Draw::OpenEndedStraightDraw])]
+    #[case::wheel_open_ender("2c 3d", "4s 5h Kd", &[Draw::OpenEndedStraightDraw])]
+    #[case::double_gutshot("7c 5d", "9s 6h 3d", &[Draw::DoubleGutshot])]
+    #[case::gutshot("9c 8d", "Js 7h 2d", &[Draw::Gutshot])]
+    #[case::wheel_gutshot("Ac 2d", "3s 4h 9d", &[Draw::Gutshot])]
+    #[case::broadway_gutshot("Ac Kd", "Qs Jh 4d", &[Draw::Gutshot, Draw::Overcards])]
+    #[case::backdoors(
+        "Ah 9h",
+        "Th 7c 2d",
+        &[Draw::BackdoorFlushDraw, Draw::BackdoorStraightDraw]
+    )]
+    #[case::combo_draw(
+        "Jh Th",
+        "9h 8h 2c",
+        &[Draw::FlushDraw, Draw::OpenEndedStraightDraw, Draw::Overcards]
+    )]
+    #[case::board_draw("2c 3d", "Ts 9s 8h 7h", &[])]
+    #[case::made_flush("Ah Kh", "Qh 7h 2h", &[])]
+    #[case::made_pair("Ah Kd", "As 7h 2d", &[])]
+    #[case::no_turn_backdoors("Ah 9h", "Th 7c 2d 3s", &[])]
+    fn draws(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] expected: &[Draw],
+    ) -> Result<(), ParseError> {
+        let analysis = analyze_draws(&hole.parse()?, &board.parse()?, &"".parse()?).unwrap();
+        assert_eq!(analysis.draws, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn flush_draw_outs() -> Result<(), ParseError> {
+        let analysis =
+            analyze_draws(&"Ah Kh".parse()?, &"Qh 7h 2c".parse()?, &"".parse()?).unwrap();
+        assert_eq!(analysis.category, PokerRankCategory::HighCard);
+        assert_eq!(analysis.outs_to(PokerRankCategory::Flush).count(), 9);
+        // Three aces and three kings.
+        assert_eq!(analysis.outs_to(PokerRankCategory::Pair).count(), 6);
+        assert_eq!(analysis.outs.len(), 15);
+
+        // The pair outs could be beaten by two pair, while the 2h pairs the
+        // board, allowing for full houses and quads.
+        let clean = analysis.clean_outs().collect::<Vec<_>>();
+        assert_eq!(clean.len(), 8);
+        assert!(clean
+            .iter()
+            .all(|out| out.category == PokerRankCategory::Flush));
+        let deuce = "2h".parse()?;
+        assert!(clean.iter().all(|out| out.card != deuce));
+        Ok(())
+    }
+
+    #[test]
+    fn full_house_outs() -> Result<(), ParseError> {
+        let analysis =
+            analyze_draws(&"8c 8d".parse()?, &"Ks 2h 2d 5c".parse()?, &"".parse()?).unwrap();
+        assert_eq!(analysis.category, PokerRankCategory::TwoPair);
+
+        let outs = analysis.outs.iter().map(|out| out.card).collect::<Hand>();
+        assert_eq!(outs, "2c 2s 8h 8s".parse()?);
+        // Full houses are beaten by quads, available to an opponent with 22.
+        assert!(analysis.outs.iter().all(|out| !out.clean));
+        Ok(())
+    }
+
+    #[test]
+    fn dead_cards_are_not_outs() -> Result<(), ParseError> {
+        let analysis = analyze_draws(
+            &"9c 8d".parse()?,
+            &"Ts 7h 2d".parse()?,
+            &"Js 6c 6d".parse()?,
+        )
+        .unwrap();
+        let outs = analysis
+            .outs_to(PokerRankCategory::Straight)
+            .map(|out| out.card)
+            .collect::<Hand>();
+        assert_eq!(outs, "Jc Jd Jh 6h 6s".parse()?);
+        assert_eq!(analysis.outs_to(PokerRankCategory::Pair).count(), 6);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::one_hole_card("Ah", "Kc 7d 2s", "", EquityError::InvalidHoleCards)]
+    #[case::river("Ah Kd", "Kc 7d 2s 3s 4s", "", EquityError::InvalidBoard)]
+    #[case::duplicate("Ah Kd", "Ah 7d 2s", "", EquityError::DuplicateCard)]
+    #[case::dead("Ah Kd", "Kc 7d 2s", "Kc", EquityError::DuplicateCard)]
+    fn invalid_hands(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] dead: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let result = analyze_draws(&hole.parse()?, &board.parse()?, &dead.parse()?);
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+// This module is also included by the build script, which sizes the preflop
+// equity tables by the maximum number of players.
+
+/// The minimum number of players in an equity calculation.
+pub const MIN_PLAYERS: usize = 2;
+
+/// The maximum number of players in an equity calculation.
+pub const MAX_PLAYERS: usize = 10;
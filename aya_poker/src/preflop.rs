@@ -0,0 +1,227 @@
+//! Precomputed preflop hold'em equities for the 169 starting hand classes.
+//!
+//! Starting hands which only differ by a permutation of suits are grouped
+//! into 169 classes: 13 pairs, 78 suited and 78 offsuit hands. The equity
+//! of every class against every other class, and against 1 to
+//! [`MAX_OPPONENTS`] random hands, is estimated by Monte Carlo simulation
+//! when the crate is built with the `preflop-tables` feature, so that the
+//! lookups in this module take constant time. The tables are stored in
+//! hundredths of a percent and have a sampling error of roughly 0.5%.
+//!
+//! Generating the tables takes about a minute when build scripts are
+//! optimized, and considerably longer otherwise, so crates enabling the
+//! feature may want to set `opt-level = 3` for their `build-override`
+//! profiles.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::preflop::{equity_vs_random, heads_up_equity};
+//!
+//! let equity = heads_up_equity(&"Ah Ad".parse()?, &"Kc Kd".parse()?).unwrap();
+//! assert!((equity - 0.82).abs() < 0.01);
+//!
+//! let equity = equity_vs_random(&"7c 2d".parse()?, 1).unwrap();
+//! assert!((equity - 0.35).abs() < 0.01);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use aya_base::Hand;
+
+use crate::equity::{EquityError, MAX_PLAYERS};
+
+/// The number of preflop starting hand classes in hold'em.
+pub const HAND_CLASS_COUNT: usize = 169;
+
+/// The maximum number of random opponents in the precomputed tables.
+pub const MAX_OPPONENTS: usize = MAX_PLAYERS - 1;
+
+include!(concat!(env!("OUT_DIR"), "/preflop.rs"));
+
+/// Returns the index of the starting hand class of the hole cards.
+///
+/// Classes are indexed as on a 13x13 grid with aces in the first row and
+/// column, pairs on the diagonal, suited hands above it and offsuit hands
+/// below it, i.e. AA is 0, AKs is 1, AKo is 13 and 22 is 168.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::preflop::hand_class;
+///
+/// assert_eq!(hand_class(&"As Kh".parse()?), Ok(13));
+/// assert_eq!(hand_class(&"2c 2d".parse()?), Ok(168));
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn hand_class(hole: &Hand) -> Result<usize, EquityError> {
+    if hole.len() != 2 {
+        return Err(EquityError::InvalidHoleCards);
+    }
+
+    let mut cards = hole.iter();
+    let (first, second) = (cards.next().unwrap(), cards.next().unwrap());
+    let high = 12 - (first.rank() as usize).max(second.rank() as usize);
+    let low = 12 - (first.rank() as usize).min(second.rank() as usize);
+
+    if first.suit() == second.suit() {
+        Ok(13 * high + low)
+    } else {
+        Ok(13 * low + high)
+    }
+}
+
+/// Returns the preflop equity of `hero` against `villain` in a heads-up
+/// hold'em showdown.
+///
+/// The equity is that of the starting hand classes of the two hands,
+/// averaged over all of their non-conflicting combos, so it does not account
+/// for the interactions between the particular suits of the hands.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::preflop::heads_up_equity;
+///
+/// let hero = "Ah Kh".parse()?;
+/// let villain = "Qc Qd".parse()?;
+/// let equity = heads_up_equity(&hero, &villain).unwrap();
+/// assert!((equity - 0.46).abs() < 0.01);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn heads_up_equity(hero: &Hand, villain: &Hand) -> Result<f64, EquityError> {
+    let hero_class = hand_class(hero)?;
+    let villain_class = hand_class(villain)?;
+    if !hero.is_disjoint(villain) {
+        return Err(EquityError::DuplicateCard);
+    }
+
+    Ok(class_equity(hero_class, villain_class))
+}
+
+/// Returns the preflop equity of the starting hand class `hero` against the
+/// class `villain`, where classes are indexed as in [`hand_class`].
+///
+/// # Panics
+///
+/// Panics if either index is not less than [`HAND_CLASS_COUNT`].
+pub fn class_equity(hero: usize, villain: usize) -> f64 {
+    assert!(hero < HAND_CLASS_COUNT && villain < HAND_CLASS_COUNT);
+    HEADS_UP_EQUITIES[HAND_CLASS_COUNT * hero + villain] as f64 / 10_000.0
+}
+
+/// Returns the preflop equity of the hole cards against the given number of
+/// opponents holding random hands.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::preflop::equity_vs_random;
+///
+/// let hole = "Ks Kd".parse()?;
+/// let heads_up = equity_vs_random(&hole, 1).unwrap();
+/// let multiway = equity_vs_random(&hole, 5).unwrap();
+/// assert!(multiway < heads_up);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn equity_vs_random(hole: &Hand, opponents: usize) -> Result<f64, EquityError> {
+    let class = hand_class(hole)?;
+    if opponents == 0 || opponents > MAX_OPPONENTS {
+        return Err(EquityError::InvalidPlayerCount);
+    }
+
+    Ok(MULTIWAY_EQUITIES[MAX_OPPONENTS * class + opponents - 1] as f64 / 10_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Ah Ad", 0)]
+    #[case("Kd Ac", 13)]
+    #[case("As Ks", 1)]
+    #[case("2s 3s", 155)]
+    #[case("3d 2h", 167)]
+    #[case("Td 9d", 4 * 13 + 5)]
+    fn class_indices(#[case] hole: &str, #[case] expected: usize) -> Result<(), ParseError> {
+        assert_eq!(hand_class(&hole.parse()?), Ok(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn every_class_is_indexed() {
+        let mut seen = [false; HAND_CLASS_COUNT];
+        for (i, first) in aya_base::CARDS.iter().enumerate() {
+            for second in aya_base::CARDS[(i + 1)..].iter() {
+                let hole = [*first, *second].iter().collect();
+                seen[hand_class(&hole).unwrap()] = true;
+            }
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[rstest]
+    #[case::overpair("Ah Ad", "Kc Kd", 0.82)]
+    #[case::race("Ah Kd", "2c 2s", 0.47)]
+    #[case::suited_connectors("8h 7h", "As Kd", 0.41)]
+    #[case::dominated("Ac Qd", "As Kh", 0.26)]
+    fn known_heads_up_equities(
+        #[case] hero: &str,
+        #[case] villain: &str,
+        #[case] expected: f64,
+    ) -> Result<(), ParseError> {
+        let equity = heads_up_equity(&hero.parse()?, &villain.parse()?).unwrap();
+        assert!((equity - expected).abs() < 0.01, "{}", equity);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Ah Ad", 1, 0.85)]
+    #[case("Ah Ad", 4, 0.56)]
+    #[case("7c 2d", 1, 0.35)]
+    #[case("Ks Qs", 2, 0.48)]
+    fn known_multiway_equities(
+        #[case] hole: &str,
+        #[case] opponents: usize,
+        #[case] expected: f64,
+    ) -> Result<(), ParseError> {
+        let equity = equity_vs_random(&hole.parse()?, opponents).unwrap();
+        assert!((equity - expected).abs() < 0.015, "{}", equity);
+        Ok(())
+    }
+
+    #[test]
+    fn heads_up_symmetry() {
+        for hero in 0..HAND_CLASS_COUNT {
+            assert_eq!(class_equity(hero, hero), 0.5);
+            for villain in 0..HAND_CLASS_COUNT {
+                let total = class_equity(hero, villain) + class_equity(villain, hero);
+                assert!((total - 1.0).abs() < 2e-4);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::not_two_cards("Ah Ad Ac", "Kc Kd", EquityError::InvalidHoleCards)]
+    #[case::shared_card("Ah Ad", "Ad Kd", EquityError::DuplicateCard)]
+    fn invalid_heads_up(
+        #[case] hero: &str,
+        #[case] villain: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let result = heads_up_equity(&hero.parse()?, &villain.parse()?);
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(MAX_OPPONENTS + 1)]
+    fn invalid_opponent_count(#[case] opponents: usize) -> Result<(), ParseError> {
+        let result = equity_vs_random(&"Ah Ad".parse()?, opponents);
+        assert_eq!(result, Err(EquityError::InvalidPlayerCount));
+        Ok(())
+    }
+}
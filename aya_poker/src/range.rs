@@ -0,0 +1,653 @@
+//! Weighted hold'em hand ranges in PokerStove-style notation.
+//!
+//! A range is written as a comma-separated list of hands, which can be
+//!
+//! - a pocket pair (`"TT"`), a suited (`"AKs"`), offsuit (`"AKo"`), or any
+//!   (`"AK"`) unpaired hand,
+//! - a hand followed by `+`, which includes all better pairs (`"QQ+"`) or
+//!   kickers (`"KTo+"` is KTo, KJo and KQo),
+//! - a span of pairs (`"JJ-88"`) or of hands with the same top card
+//!   (`"A5s-A2s"`),
+//! - a specific combination of hole cards (`"AhKh"`),
+//!
+//! each optionally followed by a weight between 0 and 1 (`"76s:0.5"`), the
+//! fraction of the time the hand is held. Hands listed later replace the
+//! weights of the hands listed before them.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::range::Range;
+//!
+//! let range = "QQ+, AKs, A2s-A5s, KTo+, 76s:0.5, AhKh".parse::<Range>()?;
+//! assert_eq!(range.combo_count(), 18.0 + 4.0 + 16.0 + 36.0 + 2.0);
+//!
+//! let range = range.without_blocked(&"Ks Qs Qh".parse()?);
+//! assert_eq!(range.combo_count(), 10.0 + 3.0 + 16.0 + 22.0 + 2.0);
+//!
+//! let range = "AKs, AQs, 88-66, AdKh:0.5".parse::<Range>()?;
+//! assert_eq!(range.to_string(), "88-66, AQs+, AdKh:0.5");
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use aya_base::{constants::CARD_COUNT, Card, Hand, ParseError, Rank, CARDS};
+
+/// The number of distinct combinations of two hole cards.
+pub const COMBO_COUNT: usize = CARD_COUNT * (CARD_COUNT - 1) / 2;
+
+/// The character used to denote each rank in range notation.
+const RANK_CHARS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+/// The indices in [`CARDS`] of the lower and the higher card of each combo.
+const COMBO_CARDS: [(u8, u8); COMBO_COUNT] = combo_cards();
+
+const fn combo_cards() -> [(u8, u8); COMBO_COUNT] {
+    let mut combos = [(0, 0); COMBO_COUNT];
+    let mut hi = 1;
+    while hi < CARD_COUNT {
+        let mut lo = 0;
+        while lo < hi {
+            combos[hi * (hi - 1) / 2 + lo] = (lo as u8, hi as u8);
+            lo += 1;
+        }
+        hi += 1;
+    }
+    combos
+}
+
+/// Returns the index of the combo containing the cards with the given
+/// indices in [`CARDS`].
+fn combo_index(c1: usize, c2: usize) -> usize {
+    let (lo, hi) = if c1 < c2 { (c1, c2) } else { (c2, c1) };
+    hi * (hi - 1) / 2 + lo
+}
+
+/// Returns the two cards of a combo.
+pub(crate) fn combo_hand(idx: usize) -> Hand {
+    let (lo, hi) = COMBO_CARDS[idx];
+    [CARDS[lo as usize], CARDS[hi as usize]].iter().collect()
+}
+
+/// The suitedness of a class of starting hands.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+/// A class of starting hands, e.g. "TT", "AKs" or "72o".
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct HandClass {
+    high: Rank,
+    low: Rank,
+    suitedness: Suitedness,
+}
+
+impl HandClass {
+    fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    /// Calls `f` with the index of every combo in the class.
+    fn for_each_combo(&self, mut f: impl FnMut(usize)) {
+        for s1 in 0..4 {
+            for s2 in 0..4 {
+                let suited = s1 == s2;
+                let included = match self.suitedness {
+                    _ if self.is_pair() => s1 < s2,
+                    Suitedness::Suited => suited,
+                    Suitedness::Offsuit => !suited,
+                    Suitedness::Any => true,
+                };
+                if included {
+                    let c1 = 4 * self.high as usize + s1;
+                    let c2 = 4 * self.low as usize + s2;
+                    f(combo_index(c1, c2));
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for HandClass {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.is_ascii() || s.len() < 2 || s.len() > 3 {
+            return Err(ParseError);
+        }
+
+        let r1 = s[..1].parse::<Rank>()?;
+        let r2 = s[1..2].parse::<Rank>()?;
+        let suitedness = match &s[2..] {
+            "" => Suitedness::Any,
+            "s" if r1 != r2 => Suitedness::Suited,
+            "o" if r1 != r2 => Suitedness::Offsuit,
+            _ => return Err(ParseError),
+        };
+
+        Ok(HandClass {
+            high: r1.max(r2),
+            low: r1.min(r2),
+            suitedness,
+        })
+    }
+}
+
+/// A weighted range of hold'em starting hands.
+///
+/// Each of the 1326 combinations of two hole cards is assigned a weight
+/// between 0 and 1, the fraction of the time the combination is part of the
+/// range.
+///
+/// A range can be parsed from and displayed in PokerStove-style notation,
+/// as described in the [module documentation](self).
+#[derive(Clone, PartialEq)]
+pub struct Range {
+    weights: [f64; COMBO_COUNT],
+}
+
+impl Range {
+    /// Creates a new empty range.
+    pub fn new() -> Range {
+        Range {
+            weights: [0.0; COMBO_COUNT],
+        }
+    }
+
+    /// Creates a new range containing every starting hand.
+    pub fn full() -> Range {
+        Range {
+            weights: [1.0; COMBO_COUNT],
+        }
+    }
+
+    /// Returns the weight of the given hole cards in the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hole` does not contain exactly 2 cards.
+    pub fn weight(&self, hole: &Hand) -> f64 {
+        self.weights[hole_index(hole)]
+    }
+
+    /// Sets the weight of the given hole cards in the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hole` does not contain exactly 2 cards, or if `weight` does
+    /// not lie between 0 and 1.
+    pub fn set_weight(&mut self, hole: &Hand, weight: f64) {
+        assert!(
+            (0.0..=1.0).contains(&weight),
+            "weight must lie between 0 and 1"
+        );
+        self.weights[hole_index(hole)] = weight;
+    }
+
+    /// Returns an iterator over the hole cards with a non-zero weight in the
+    /// range, together with their weights.
+    pub fn combos(&self) -> impl Iterator<Item = (Hand, f64)> + '_ {
+        self.weights
+            .iter()
+            .enumerate()
+            .filter(|(_, &w)| w > 0.0)
+            .map(|(i, &w)| (combo_hand(i), w))
+    }
+
+    /// Returns the number of combinations of hole cards in the range, with
+    /// each combination counted according to its weight.
+    pub fn combo_count(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+
+    /// Returns the number of combinations of hole cards with a non-zero
+    /// weight in the range.
+    pub fn len(&self) -> usize {
+        self.weights.iter().filter(|&&w| w > 0.0).count()
+    }
+
+    /// Returns `true` if no combination of hole cards has a non-zero weight.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the combinations of hole cards which share a card with `dead`.
+    pub fn remove_blocked(&mut self, dead: &Hand) {
+        for (i, weight) in self.weights.iter_mut().enumerate() {
+            if *weight > 0.0 && !combo_hand(i).is_disjoint(dead) {
+                *weight = 0.0;
+            }
+        }
+    }
+
+    /// Returns a copy of the range without the combinations of hole cards
+    /// which share a card with `dead`.
+    pub fn without_blocked(&self, dead: &Hand) -> Range {
+        let mut range = self.clone();
+        range.remove_blocked(dead);
+        range
+    }
+
+    /// Returns the union of two ranges, where each combination is given the
+    /// greater of its two weights.
+    pub fn union(&self, other: &Range) -> Range {
+        self.combine(other, f64::max)
+    }
+
+    /// Returns the intersection of two ranges, where each combination is
+    /// given the lesser of its two weights.
+    pub fn intersection(&self, other: &Range) -> Range {
+        self.combine(other, f64::min)
+    }
+
+    /// Returns the difference of two ranges, where the weight of each
+    /// combination is reduced by its weight in `other`.
+    pub fn difference(&self, other: &Range) -> Range {
+        self.combine(other, |w1, w2| (w1 - w2).max(0.0))
+    }
+
+    fn combine(&self, other: &Range, op: impl Fn(f64, f64) -> f64) -> Range {
+        let mut range = Range::new();
+        for (i, weight) in range.weights.iter_mut().enumerate() {
+            *weight = op(self.weights[i], other.weights[i]);
+        }
+        range
+    }
+
+    fn set_class(&mut self, class: &HandClass, weight: f64) {
+        class.for_each_combo(|i| self.weights[i] = weight);
+    }
+
+    /// Returns the weight shared by every combo of the class, if any.
+    fn class_weight(&self, class: &HandClass) -> Option<f64> {
+        let mut weight = None;
+        let mut uniform = true;
+        class.for_each_combo(|i| match weight {
+            None => weight = Some(self.weights[i]),
+            Some(w) => uniform &= w == self.weights[i],
+        });
+        weight.filter(|_| uniform)
+    }
+
+    fn parse_token(&mut self, token: &str) -> Result<(), ParseError> {
+        let (hands, weight) = match token.split_once(':') {
+            Some((hands, weight)) => {
+                let weight = weight.trim().parse::<f64>().map_err(|_| ParseError)?;
+                if !(0.0..=1.0).contains(&weight) {
+                    return Err(ParseError);
+                }
+                (hands.trim(), weight)
+            }
+            None => (token, 1.0),
+        };
+
+        if let Some((first, last)) = hands.split_once('-') {
+            let first = first.trim().parse::<HandClass>()?;
+            let last = last.trim().parse::<HandClass>()?;
+            if first.suitedness != last.suitedness || first.is_pair() != last.is_pair() {
+                return Err(ParseError);
+            }
+
+            if first.is_pair() {
+                for class in span(first.low, last.low, pair_class) {
+                    self.set_class(&class, weight);
+                }
+            } else {
+                if first.high != last.high {
+                    return Err(ParseError);
+                }
+                let kicker_class = |low| HandClass { low, ..first };
+                for class in span(first.low, last.low, kicker_class) {
+                    self.set_class(&class, weight);
+                }
+            }
+        } else if let Some(hand) = hands.strip_suffix('+') {
+            let class = hand.trim().parse::<HandClass>()?;
+            if class.is_pair() {
+                for class in span(class.low, Rank::Ace, pair_class) {
+                    self.set_class(&class, weight);
+                }
+            } else {
+                let top_kicker = rank_below(class.high);
+                let kicker_class = |low| HandClass { low, ..class };
+                for class in span(class.low, top_kicker, kicker_class) {
+                    self.set_class(&class, weight);
+                }
+            }
+        } else if hands.len() == 4 && hands.is_ascii() {
+            match (hands[..2].parse::<Card>(), hands[2..].parse::<Card>()) {
+                (Ok(c1), Ok(c2)) if c1 != c2 => {
+                    self.weights[combo_index(c1.idx(), c2.idx())] = weight;
+                }
+                _ => return Err(ParseError),
+            }
+        } else {
+            let class = hands.parse::<HandClass>()?;
+            self.set_class(&class, weight);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Range {
+    fn default() -> Self {
+        Range::new()
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::new();
+        for token in s.split(',').map(str::trim) {
+            if token.is_empty() {
+                if s.trim().is_empty() {
+                    continue;
+                }
+                return Err(ParseError);
+            }
+            range.parse_token(token)?;
+        }
+        Ok(range)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = TokenWriter { f, first: true };
+
+        // Runs of pairs with the same weight, starting from aces.
+        let pairs = (0..13).rev().map(|r| pair_class(rank(r)));
+        for (top, bottom, weight) in runs(self, pairs) {
+            match (top.high, bottom.high) {
+                (Rank::Ace, b) if b != Rank::Ace => {
+                    writer.token(format_args!("{0}{0}+", char_of(b)), weight)?
+                }
+                (t, b) if t != b => writer.token(
+                    format_args!("{0}{0}-{1}{1}", char_of(t), char_of(b)),
+                    weight,
+                )?,
+                (t, _) => writer.token(format_args!("{0}{0}", char_of(t)), weight)?,
+            }
+        }
+
+        // Runs of unpaired hands with the same top card and weight, starting
+        // from the best kicker.
+        for suitedness in [Suitedness::Suited, Suitedness::Offsuit] {
+            let suffix = if suitedness == Suitedness::Suited {
+                's'
+            } else {
+                'o'
+            };
+            for high in (1..13).rev().map(rank) {
+                let classes = (0..(high as u8)).rev().map(|low| HandClass {
+                    high,
+                    low: rank(low),
+                    suitedness,
+                });
+                for (top, bottom, weight) in runs(self, classes) {
+                    let (h, t, b) = (char_of(high), char_of(top.low), char_of(bottom.low));
+                    if top.low == rank_below(high) && top != bottom {
+                        writer.token(format_args!("{}{}{}+", h, b, suffix), weight)?;
+                    } else if top != bottom {
+                        writer
+                            .token(format_args!("{0}{1}{3}-{0}{2}{3}", h, t, b, suffix), weight)?;
+                    } else {
+                        writer.token(format_args!("{}{}{}", h, t, suffix), weight)?;
+                    }
+                }
+            }
+        }
+
+        // Specific combos of the hand classes with uneven weights.
+        for high in (0..13).rev().map(rank) {
+            for low in (0..=(high as u8)).rev().map(rank) {
+                for suitedness in [Suitedness::Suited, Suitedness::Offsuit] {
+                    let class = HandClass {
+                        high,
+                        low,
+                        suitedness,
+                    };
+                    if self.class_weight(&class).is_some()
+                        || (class.is_pair() && suitedness == Suitedness::Offsuit)
+                    {
+                        continue;
+                    }
+
+                    let mut result = Ok(());
+                    class.for_each_combo(|i| {
+                        let weight = self.weights[i];
+                        if weight > 0.0 && result.is_ok() {
+                            let (lo, hi) = COMBO_CARDS[i];
+                            let (c1, c2) = (CARDS[hi as usize], CARDS[lo as usize]);
+                            result = writer.token(format_args!("{:?}{:?}", c1, c2), weight);
+                        }
+                    });
+                    result?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Range({})", self)
+    }
+}
+
+/// Writes comma-separated range tokens with their weights.
+struct TokenWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    first: bool,
+}
+
+impl TokenWriter<'_, '_> {
+    fn token(&mut self, hands: fmt::Arguments<'_>, weight: f64) -> fmt::Result {
+        if !self.first {
+            write!(self.f, ", ")?;
+        }
+        self.first = false;
+
+        write!(self.f, "{}", hands)?;
+        if weight != 1.0 {
+            write!(self.f, ":{}", weight)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the first and last classes and the weight of each run of
+/// consecutive classes with the same non-zero uniform weight.
+fn runs(
+    range: &Range,
+    classes: impl Iterator<Item = HandClass>,
+) -> impl Iterator<Item = (HandClass, HandClass, f64)> {
+    let mut runs: [Option<(HandClass, HandClass, f64)>; 13] = [None; 13];
+    let mut len = 0;
+    let mut current: Option<(HandClass, HandClass, f64)> = None;
+
+    for class in classes {
+        let weight = range.class_weight(&class).filter(|&w| w > 0.0);
+        current = match (current, weight) {
+            (Some((top, _, w)), Some(weight)) if w == weight => Some((top, class, w)),
+            (run, weight) => {
+                if run.is_some() {
+                    runs[len] = run;
+                    len += 1;
+                }
+                weight.map(|w| (class, class, w))
+            }
+        };
+    }
+    if current.is_some() {
+        runs[len] = current;
+    }
+
+    runs.into_iter().flatten()
+}
+
+fn span(r1: Rank, r2: Rank, class: impl Fn(Rank) -> HandClass) -> impl Iterator<Item = HandClass> {
+    let (lo, hi) = (r1.min(r2) as u8, r1.max(r2) as u8);
+    (lo..=hi).map(move |r| class(rank(r)))
+}
+
+fn pair_class(rank: Rank) -> HandClass {
+    HandClass {
+        high: rank,
+        low: rank,
+        suitedness: Suitedness::Any,
+    }
+}
+
+fn rank(r: u8) -> Rank {
+    Rank::try_from(r).unwrap()
+}
+
+fn rank_below(r: Rank) -> Rank {
+    rank(r as u8 - 1)
+}
+
+fn char_of(r: Rank) -> char {
+    RANK_CHARS[r as usize]
+}
+
+fn hole_index(hole: &Hand) -> usize {
+    assert_eq!(hole.len(), 2, "hole cards must contain exactly 2 cards");
+    let mut cards = hole.iter();
+    let c1 = cards.next().unwrap();
+    let c2 = cards.next().unwrap();
+    combo_index(c1.idx(), c2.idx())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn combo_indices() {
+        for (i, &(lo, hi)) in COMBO_CARDS.iter().enumerate() {
+            assert!(lo < hi);
+            assert_eq!(combo_index(lo as usize, hi as usize), i);
+            assert_eq!(combo_index(hi as usize, lo as usize), i);
+        }
+    }
+
+    #[rstest]
+    #[case("", 0.0)]
+    #[case("AA", 6.0)]
+    #[case("AKs", 4.0)]
+    #[case("AKo", 12.0)]
+    #[case("AK", 16.0)]
+    #[case("KA", 16.0)]
+    #[case("QQ+", 18.0)]
+    #[case("JJ-88", 24.0)]
+    #[case("88-JJ", 24.0)]
+    #[case("A2s-A5s", 16.0)]
+    #[case("A5s-A2s", 16.0)]
+    #[case("KTo+", 36.0)]
+    #[case("K2+", 176.0)]
+    #[case("76s:0.5", 2.0)]
+    #[case("AhKh", 1.0)]
+    #[case("AhKh:0.25", 0.25)]
+    #[case("QQ+, AKs, A2s-A5s, KTo+, 76s:0.5, AhKh", 76.0)]
+    #[case("AK, AKs:0", 12.0)]
+    #[case(
+        "22+, A2+, K2+, Q2+, J2+, T2+, 92+, 82+, 72+, 62+, 52+, 42+, 32",
+        1326.0
+    )]
+    fn parse_combo_count(#[case] s: &str, #[case] expected: f64) -> Result<(), ParseError> {
+        let range = s.parse::<Range>()?;
+        assert_eq!(range.combo_count(), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("AKx")]
+    #[case("AAs")]
+    #[case("AKs-QJs")]
+    #[case("AKs-AQo")]
+    #[case("AA-AKs")]
+    #[case("AhAh")]
+    #[case("AK:1.5")]
+    #[case("AK:x")]
+    #[case("AK,,QQ")]
+    #[case("1K")]
+    fn parse_errors(#[case] s: &str) {
+        assert_eq!(s.parse::<Range>(), Err(ParseError));
+    }
+
+    #[rstest]
+    #[case("QQ+", "QQ+")]
+    #[case("AA", "AA")]
+    #[case("KK-TT, 77:0.5", "KK-TT, 77:0.5")]
+    #[case("AKs, AQs", "AQs+")]
+    #[case("A5s-A2s, AKo", "A5s-A2s, AKo")]
+    #[case("AK", "AKs, AKo")]
+    #[case("KTo+, 76s:0.5", "76s:0.5, KTo+")]
+    #[case("AhKh, AsAd", "AsAd, AhKh")]
+    #[case("", "")]
+    fn display(#[case] s: &str, #[case] expected: &str) -> Result<(), ParseError> {
+        let range = s.parse::<Range>()?;
+        assert_eq!(range.to_string(), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("QQ+, AKs, A2s-A5s, KTo+, 76s:0.5, AhKh")]
+    #[case("22+:0.3, AKo, AdQd, 9h8h:0.75")]
+    #[case("K2+, T9s-T6s, 54")]
+    fn display_round_trip(#[case] s: &str) -> Result<(), ParseError> {
+        let range = s.parse::<Range>()?;
+        assert_eq!(range.to_string().parse::<Range>()?, range);
+        Ok(())
+    }
+
+    #[test]
+    fn weights() -> Result<(), ParseError> {
+        let mut range = "AK:0.5, AhKh".parse::<Range>()?;
+        assert_eq!(range.weight(&"Kh Ah".parse()?), 1.0);
+        assert_eq!(range.weight(&"As Kd".parse()?), 0.5);
+        assert_eq!(range.weight(&"As Qd".parse()?), 0.0);
+
+        range.set_weight(&"As Qd".parse()?, 0.25);
+        assert_eq!(range.weight(&"Qd As".parse()?), 0.25);
+        assert_eq!(range.len(), 17);
+        assert_eq!(range.combos().count(), 17);
+        Ok(())
+    }
+
+    #[test]
+    fn blockers() -> Result<(), ParseError> {
+        let range = "AA, AK".parse::<Range>()?;
+        let range = range.without_blocked(&"As 7d".parse()?);
+        assert_eq!(range.combo_count(), 3.0 + 12.0);
+
+        let ace = "As".parse()?;
+        assert!(range.combos().all(|(hole, _)| !hole.contains(&ace)));
+        Ok(())
+    }
+
+    #[test]
+    fn set_operations() -> Result<(), ParseError> {
+        let r1 = "TT+, AK:0.5".parse::<Range>()?;
+        let r2 = "QQ-88, AKs".parse::<Range>()?;
+
+        assert_eq!(r1.union(&r2), "88+, AKo:0.5, AKs".parse()?);
+        assert_eq!(r1.intersection(&r2), "QQ-TT, AKs:0.5".parse()?);
+        assert_eq!(r1.difference(&r2), "AA-KK, AKo:0.5".parse()?);
+        assert!(r1.difference(&r1).is_empty());
+        Ok(())
+    }
+}
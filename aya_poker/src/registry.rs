@@ -0,0 +1,1114 @@
+//! A registry of the rules of every supported poker variant, selected at
+//! runtime by name.
+//!
+//! Every variant is described by a [`GameDef`], which holds the deck, the
+//! cards dealt on each street, whether the pot is split between the high and
+//! low hands and how each of them is [ranked](Ranking), and an evaluator
+//! returning ranks of a single type, [`HandValue`], regardless of the
+//! variant. Games are looked up by their id
+//! or by one of their common abbreviations, e.g. `"nlhe"`, `"plo8"`,
+//! `"2-7td"`, `"badugi"` or `"short-deck"`.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::registry::find_game;
+//!
+//! let game = find_game("plo8").unwrap();
+//! assert_eq!(game.name, "Omaha Hi/Lo");
+//! assert_eq!(game.hole_cards(), 4);
+//! assert!(game.hi_lo);
+//!
+//! let board = "Ks 8d 7c 3h 2s".parse()?;
+//! let nuts_low = game.evaluate(&"Ah 4d Kc Kd".parse()?, &board).unwrap();
+//! let no_low = game.evaluate(&"Qc Qd Jc Js".parse()?, &board).unwrap();
+//! assert!(nuts_low.hi > no_low.hi);
+//! assert!(nuts_low.lo.is_some() && no_low.lo.is_none());
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use core::fmt;
+
+use aya_base::{Card, Deck, Hand, Rank, CARDS};
+
+use crate::{
+    equity::{EquityError, Game},
+    evaluator::{
+        Badacey, Badeucy, Baduci, Badugi, DeuceSeven, DeucesWild, Evaluator, FiveCardDraw, Holdem,
+        Manila, Omaha, OmahaHiLo, Razz, ShortDeckHoldem, SplitEvaluator, Stud, StudHiLo, Variant,
+    },
+    for_each_subhand, AceFiveHandRank, BaduciHandRank, BadugiHandRank, BadugiRankCategory,
+    DeuceSevenHandRank, PokerHandRank, PokerRankCategory, ShortDeckHandRank,
+};
+
+/// The deck of cards used by a variant.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum DeckKind {
+    /// The standard 52-card deck.
+    Standard,
+    /// The 36-card deck of sixes through aces.
+    ShortDeck,
+    /// The 32-card deck of sevens through aces.
+    Manila,
+}
+
+impl DeckKind {
+    /// Returns `true` if the card is part of the deck.
+    pub fn contains(self, card: &Card) -> bool {
+        match self {
+            DeckKind::Standard => Holdem::deck_contains(card),
+            DeckKind::ShortDeck => ShortDeckHoldem::deck_contains(card),
+            DeckKind::Manila => Manila::deck_contains(card),
+        }
+    }
+
+    /// Returns the number of cards in the deck.
+    pub fn card_count(self) -> usize {
+        CARDS.iter().filter(|c| self.contains(c)).count()
+    }
+
+    /// Returns a shuffled deck of all of the cards.
+    pub fn deck(self, seed: u64) -> Deck {
+        Deck::with_seed(CARDS.iter().filter(|c| self.contains(c)), seed)
+    }
+}
+
+/// A betting round and the cards dealt before it.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct Street {
+    /// The name of the street.
+    pub name: &'static str,
+    /// The number of cards dealt to each player before the street.
+    pub hole_cards: usize,
+    /// The number of cards dealt to the board before the street.
+    pub board_cards: usize,
+    /// Whether players may discard and draw replacement cards before the
+    /// street.
+    pub draw: bool,
+}
+
+/// The strength ranking of a hand, or of one half of a hand, in any variant.
+///
+/// Greater values are better, but only values of hands ranked for the same
+/// half of the pot of the same game can be compared.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub struct HandValue(pub u16);
+
+/// The strength ranking of a hand in any variant.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct GameRank {
+    /// The ranking of the hand, or of its high half in a hi/lo game.
+    pub hi: HandValue,
+    /// The ranking of the qualifying low half of the hand in a hi/lo game,
+    /// if any.
+    pub lo: Option<HandValue>,
+}
+
+/// How the hands of one half of the pot of a variant are ranked.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Ranking {
+    /// Standard high hands, as ranked by [`poker_rank`](crate::poker_rank).
+    Standard,
+    /// High hands where flushes beat full houses, as ranked by
+    /// [`short_deck_rank`](crate::short_deck_rank) and
+    /// [`manila_rank`](crate::manila_rank).
+    ShortDeck,
+    /// High hands with deuces wild, as ranked by
+    /// [`deuces_wild_rank`](crate::deuces_wild_rank).
+    DeucesWild,
+    /// Ace-to-five lowball, as ranked by
+    /// [`ace_five_rank`](crate::ace_five_rank).
+    AceFive,
+    /// Deuce-to-seven lowball, as ranked by
+    /// [`deuce_seven_rank`](crate::deuce_seven_rank).
+    DeuceSeven,
+    /// Badugi, with aces low, as ranked by [`badugi_rank`](crate::badugi_rank).
+    Badugi,
+    /// Badugi with aces high, as ranked by [`baduci_rank`](crate::baduci_rank).
+    Baduci,
+}
+
+impl Ranking {
+    /// Returns the hand-ranking category of a value ranked by this ranking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::registry::{find_game, HandCategory};
+    /// use aya_poker::PokerRankCategory;
+    ///
+    /// let game = find_game("holdem").unwrap();
+    /// let rank = game.evaluate(&"Ah Ad".parse()?, &"Ac 7d 2s".parse()?).unwrap();
+    /// let category = game.hi_ranking.category(rank.hi);
+    /// assert_eq!(category, HandCategory::Poker(PokerRankCategory::ThreeOfAKind));
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn category(self, value: HandValue) -> HandCategory {
+        let poker = HandCategory::Poker;
+        let badugi = HandCategory::Badugi;
+        match self {
+            Ranking::Standard | Ranking::DeucesWild => {
+                poker(PokerHandRank(value.0).rank_category())
+            }
+            Ranking::ShortDeck => poker(ShortDeckHandRank(value.0).rank_category()),
+            Ranking::AceFive => poker(AceFiveHandRank(value.0).rank_category()),
+            Ranking::DeuceSeven => poker(DeuceSevenHandRank(value.0).rank_category()),
+            Ranking::Badugi => badugi(BadugiHandRank(value.0).rank_category()),
+            Ranking::Baduci => badugi(BaduciHandRank(value.0).rank_category()),
+        }
+    }
+}
+
+/// The hand-ranking category of a hand in any variant.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum HandCategory {
+    /// The category of a poker hand, high or low.
+    Poker(PokerRankCategory),
+    /// The category of a Badugi or Baduci hand.
+    Badugi(BadugiRankCategory),
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandCategory::Poker(category) => write!(f, "{}", category),
+            HandCategory::Badugi(category) => write!(f, "{}", category),
+        }
+    }
+}
+
+/// The best hand that can be made for one half of the pot.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct BestHand {
+    /// The ranking of the hand.
+    pub value: HandValue,
+    /// The hand-ranking category of the hand.
+    pub category: HandCategory,
+    /// The cards the hand is made of.
+    pub cards: Hand,
+}
+
+/// The best hands that can be made for each half of the pot.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct BestHands {
+    /// The best hand, or the best high hand in a hi/lo game, if it can be
+    /// ranked.
+    pub hi: Option<BestHand>,
+    /// The best qualifying low hand in a hi/lo game, if any.
+    pub lo: Option<BestHand>,
+}
+
+/// The rules of a poker variant.
+pub struct GameDef {
+    /// The unique identifier of the game.
+    pub id: &'static str,
+    /// Other names the game can be looked up by.
+    pub aliases: &'static [&'static str],
+    /// The human-readable name of the game.
+    pub name: &'static str,
+    /// The deck used by the game.
+    pub deck: DeckKind,
+    /// The betting rounds of the game, in order.
+    pub streets: &'static [Street],
+    /// Whether the pot is split between the best high and low hands.
+    pub hi_lo: bool,
+    /// The highest card allowed in a low hand qualifying for the low half of
+    /// the pot, if any.
+    ///
+    /// This is informational only, as the evaluator of the game already
+    /// ranks the hands which do not qualify as having no low.
+    pub lo_qualifier: Option<Rank>,
+    /// How the hands, or the high halves of the hands in a hi/lo game, are
+    /// ranked.
+    pub hi_ranking: Ranking,
+    /// How the low halves of the hands are ranked in a hi/lo game.
+    pub lo_ranking: Option<Ranking>,
+    /// The game of the [`equity`](crate::equity) calculations with the same
+    /// rules, if they support the variant.
+    pub equity_game: Option<Game>,
+    evaluator: &'static dyn DynEvaluator,
+}
+
+impl GameDef {
+    /// Returns the number of hole cards each player holds at showdown.
+    pub fn hole_cards(&self) -> usize {
+        self.streets.iter().map(|s| s.hole_cards).sum()
+    }
+
+    /// Returns the number of cards on the board at showdown.
+    pub fn board_cards(&self) -> usize {
+        self.streets.iter().map(|s| s.board_cards).sum()
+    }
+
+    /// Returns the rank of the best hand that can be made from the hole and
+    /// board cards.
+    ///
+    /// Hands may be evaluated on any street, as long as the numbers of hole
+    /// and board cards are those dealt by the end of some street. Hands which
+    /// cannot be ranked until later streets, e.g. Omaha hands before the
+    /// flop, have a value of 0.
+    ///
+    /// Returns an error if the numbers of hole or board cards cannot be dealt
+    /// in the game, if a card is not part of its deck, or if any card was
+    /// dealt twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::registry::find_game;
+    ///
+    /// let razz = find_game("razz").unwrap();
+    /// let wheel = razz.evaluate(&"Ac 2d 3h 4s 5c Kd Kc".parse()?, &"".parse()?).unwrap();
+    /// let pair = razz.evaluate(&"Ac Ad 3h".parse()?, &"".parse()?).unwrap();
+    /// assert!(wheel.hi > pair.hi);
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn evaluate(&self, hole: &Hand, board: &Hand) -> Result<GameRank, EquityError> {
+        let mut dealt = (0, 0);
+        let mut valid_hole = false;
+        let mut valid_board = false;
+        for street in self.streets {
+            dealt = (dealt.0 + street.hole_cards, dealt.1 + street.board_cards);
+            valid_hole |= hole.len() == dealt.0;
+            valid_board |= board.len() == dealt.1;
+        }
+        if !valid_hole {
+            return Err(EquityError::InvalidHoleCards);
+        }
+        if !valid_board {
+            return Err(EquityError::InvalidBoard);
+        }
+        if !hole.is_disjoint(board) {
+            return Err(EquityError::DuplicateCard);
+        }
+        if !hole
+            .iter()
+            .chain(board.iter())
+            .all(|c| self.deck.contains(c))
+        {
+            return Err(EquityError::InvalidCard);
+        }
+
+        if !self.evaluator.can_rank(hole, board) {
+            return Ok(UNRANKED);
+        }
+        Ok(self.evaluator.rank(hole, board))
+    }
+
+    /// Returns the best hands that can be made from the hole and board cards
+    /// for each half of the pot, i.e. the fewest cards ranked as highly as
+    /// all of them.
+    ///
+    /// Returns an error under the same conditions as [`GameDef::evaluate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::registry::find_game;
+    ///
+    /// let game = find_game("plo8").unwrap();
+    /// let best = game.best_hands(&"Ah 2d Kc Kd".parse()?, &"3c 7s 8h Ks Qd".parse()?).unwrap();
+    /// assert_eq!(best.hi.unwrap().cards, "Kc Kd Ks Qd 8h".parse()?);
+    /// assert_eq!(best.lo.unwrap().cards, "Ah 2d 3c 7s 8h".parse()?);
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn best_hands(&self, hole: &Hand, board: &Hand) -> Result<BestHands, EquityError> {
+        let rank = self.evaluate(hole, board)?;
+        let hi = self.best_hand(hole, board, self.hi_ranking, Some(rank.hi), |r| Some(r.hi));
+        let lo = match self.lo_ranking {
+            Some(ranking) => self.best_hand(hole, board, ranking, rank.lo, |r| r.lo),
+            None => None,
+        };
+        Ok(BestHands { hi, lo })
+    }
+
+    /// Finds the smallest subset of the cards ranked as the value for the
+    /// half of the pot returned by `half`.
+    fn best_hand(
+        &self,
+        hole: &Hand,
+        board: &Hand,
+        ranking: Ranking,
+        value: Option<HandValue>,
+        half: impl Fn(GameRank) -> Option<HandValue>,
+    ) -> Option<BestHand> {
+        let value = value.filter(|value| value.0 != 0)?;
+        let mut buffer = [CARDS[0]; 14];
+        for (slot, &card) in buffer.iter_mut().zip(hole.iter().chain(board.iter())) {
+            *slot = card;
+        }
+        let cards = &buffer[..hole.len() + board.len()];
+        // Every ranking plays at most five cards.
+        for size in 1..=cards.len().min(5) {
+            let mut best = None;
+            for_each_subhand(cards, size, Hand::new(), &mut |subhand| {
+                if best.is_some() {
+                    return;
+                }
+                let sub_hole = subhand.iter().filter(|c| hole.contains(c)).collect();
+                let sub_board = subhand.iter().filter(|c| board.contains(c)).collect();
+                if half(self.evaluator.rank(&sub_hole, &sub_board)) == Some(value) {
+                    best = Some(subhand);
+                }
+            });
+            if let Some(cards) = best {
+                return Some(BestHand {
+                    value,
+                    category: ranking.category(value),
+                    cards,
+                });
+            }
+        }
+        None
+    }
+}
+
+impl PartialEq for GameDef {
+    fn eq(&self, other: &GameDef) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for GameDef {}
+
+impl fmt::Debug for GameDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameDef")
+            .field("id", &self.id)
+            .field("aliases", &self.aliases)
+            .field("name", &self.name)
+            .field("deck", &self.deck)
+            .field("streets", &self.streets)
+            .field("hi_lo", &self.hi_lo)
+            .field("lo_qualifier", &self.lo_qualifier)
+            .field("hi_ranking", &self.hi_ranking)
+            .field("lo_ranking", &self.lo_ranking)
+            .field("equity_game", &self.equity_game)
+            .finish()
+    }
+}
+
+impl fmt::Display for GameDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Returns the rules of every supported variant.
+pub fn games() -> &'static [GameDef] {
+    &GAMES
+}
+
+/// Returns the rules of the game with the given id or alias, ignoring case.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::registry::find_game;
+///
+/// assert_eq!(find_game("NLHE").unwrap().id, "holdem");
+/// assert_eq!(find_game("2-7td").unwrap().streets.len(), 4);
+/// assert!(find_game("canasta").is_none());
+/// ```
+pub fn find_game(name: &str) -> Option<&'static GameDef> {
+    GAMES.iter().find(|game| {
+        game.id.eq_ignore_ascii_case(name)
+            || game.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    })
+}
+
+/// An evaluator which can be called through a trait object.
+trait DynEvaluator: Sync {
+    /// Returns `true` if the numbers of cards can be ranked by the variant.
+    fn can_rank(&self, hole: &Hand, board: &Hand) -> bool;
+
+    /// Ranks the cards, without checking their numbers.
+    fn rank(&self, hole: &Hand, board: &Hand) -> GameRank;
+}
+
+/// The rank of a hand which cannot be ranked before later streets.
+const UNRANKED: GameRank = GameRank {
+    hi: HandValue(0),
+    lo: None,
+};
+
+/// Adapts an [`Evaluator`] to [`DynEvaluator`].
+struct HighOnly<E>(E);
+
+impl<E> DynEvaluator for HighOnly<E>
+where
+    E: Evaluator + Sync,
+    E::Rank: Into<HandValue>,
+{
+    fn can_rank(&self, hole: &Hand, board: &Hand) -> bool {
+        E::HOLE_CARDS.contains(&hole.len()) && E::BOARD_CARDS.contains(&board.len())
+    }
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> GameRank {
+        GameRank {
+            hi: self.0.rank(hole, board).into(),
+            lo: None,
+        }
+    }
+}
+
+/// Adapts a [`SplitEvaluator`] to [`DynEvaluator`].
+struct HiLo<E>(E);
+
+impl<E> DynEvaluator for HiLo<E>
+where
+    E: SplitEvaluator + Sync,
+    E::HiRank: Into<HandValue>,
+    E::LoRank: Into<HandValue>,
+{
+    fn can_rank(&self, hole: &Hand, board: &Hand) -> bool {
+        E::HOLE_CARDS.contains(&hole.len()) && E::BOARD_CARDS.contains(&board.len())
+    }
+
+    fn rank(&self, hole: &Hand, board: &Hand) -> GameRank {
+        let (hi, lo) = self.0.rank(hole, board);
+        GameRank {
+            hi: hi.into(),
+            lo: lo.map(Into::into),
+        }
+    }
+}
+
+impl From<PokerHandRank> for HandValue {
+    fn from(rank: PokerHandRank) -> HandValue {
+        HandValue(rank.0)
+    }
+}
+
+impl From<ShortDeckHandRank> for HandValue {
+    fn from(rank: ShortDeckHandRank) -> HandValue {
+        HandValue(rank.0)
+    }
+}
+
+impl From<Option<ShortDeckHandRank>> for HandValue {
+    fn from(rank: Option<ShortDeckHandRank>) -> HandValue {
+        rank.map_or(HandValue(0), HandValue::from)
+    }
+}
+
+impl From<AceFiveHandRank> for HandValue {
+    fn from(rank: AceFiveHandRank) -> HandValue {
+        HandValue(rank.0)
+    }
+}
+
+impl From<DeuceSevenHandRank> for HandValue {
+    fn from(rank: DeuceSevenHandRank) -> HandValue {
+        HandValue(rank.0)
+    }
+}
+
+impl From<BadugiHandRank> for HandValue {
+    fn from(rank: BadugiHandRank) -> HandValue {
+        HandValue(rank.0)
+    }
+}
+
+impl From<BaduciHandRank> for HandValue {
+    fn from(rank: BaduciHandRank) -> HandValue {
+        HandValue(rank.0)
+    }
+}
+
+const fn street(name: &'static str, hole_cards: usize, board_cards: usize) -> Street {
+    Street {
+        name,
+        hole_cards,
+        board_cards,
+        draw: false,
+    }
+}
+
+const fn draw(name: &'static str) -> Street {
+    Street {
+        name,
+        hole_cards: 0,
+        board_cards: 0,
+        draw: true,
+    }
+}
+
+const fn community_streets(hole_cards: usize) -> [Street; 4] {
+    [
+        street("Preflop", hole_cards, 0),
+        street("Flop", 0, 3),
+        street("Turn", 0, 1),
+        street("River", 0, 1),
+    ]
+}
+
+const HOLDEM_STREETS: [Street; 4] = community_streets(2);
+const OMAHA_STREETS: [Street; 4] = community_streets(4);
+const OMAHA_5_STREETS: [Street; 4] = community_streets(5);
+const OMAHA_6_STREETS: [Street; 4] = community_streets(6);
+
+const MANILA_STREETS: [Street; 5] = [
+    street("First Card", 2, 1),
+    street("Second Card", 0, 1),
+    street("Third Card", 0, 1),
+    street("Fourth Card", 0, 1),
+    street("Fifth Card", 0, 1),
+];
+
+const STUD_STREETS: [Street; 5] = [
+    street("Third Street", 3, 0),
+    street("Fourth Street", 1, 0),
+    street("Fifth Street", 1, 0),
+    street("Sixth Street", 1, 0),
+    street("Seventh Street", 1, 0),
+];
+
+const SINGLE_DRAW_STREETS: [Street; 2] = [street("Predraw", 5, 0), draw("Draw")];
+
+const TRIPLE_DRAW_STREETS: [Street; 4] = [
+    street("Predraw", 5, 0),
+    draw("First Draw"),
+    draw("Second Draw"),
+    draw("Third Draw"),
+];
+
+const BADUGI_STREETS: [Street; 4] = [
+    street("Predraw", 4, 0),
+    draw("First Draw"),
+    draw("Second Draw"),
+    draw("Third Draw"),
+];
+
+static GAMES: [GameDef; 20] = [
+    GameDef {
+        id: "holdem",
+        aliases: &["nlhe", "lhe", "plhe", "texas-holdem"],
+        name: "Texas Hold'em",
+        deck: DeckKind::Standard,
+        streets: &HOLDEM_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Standard,
+        lo_ranking: None,
+        equity_game: Some(Game::Holdem),
+        evaluator: &HighOnly(Holdem),
+    },
+    GameDef {
+        id: "short-deck",
+        aliases: &["6+", "sd", "shortdeck", "six-plus"],
+        name: "Short-Deck Hold'em",
+        deck: DeckKind::ShortDeck,
+        streets: &HOLDEM_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::ShortDeck,
+        lo_ranking: None,
+        equity_game: Some(Game::ShortDeck),
+        evaluator: &HighOnly(ShortDeckHoldem),
+    },
+    GameDef {
+        id: "manila",
+        aliases: &[],
+        name: "Manila",
+        deck: DeckKind::Manila,
+        streets: &MANILA_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::ShortDeck,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(Manila),
+    },
+    GameDef {
+        id: "omaha",
+        aliases: &["plo", "plo4", "omaha4"],
+        name: "Omaha",
+        deck: DeckKind::Standard,
+        streets: &OMAHA_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Standard,
+        lo_ranking: None,
+        equity_game: Some(Game::Omaha),
+        evaluator: &HighOnly(Omaha),
+    },
+    GameDef {
+        id: "omaha-5",
+        aliases: &["plo5", "5-card-omaha"],
+        name: "5-Card Omaha",
+        deck: DeckKind::Standard,
+        streets: &OMAHA_5_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Standard,
+        lo_ranking: None,
+        equity_game: Some(Game::Omaha),
+        evaluator: &HighOnly(Omaha),
+    },
+    GameDef {
+        id: "omaha-6",
+        aliases: &["plo6", "6-card-omaha"],
+        name: "6-Card Omaha",
+        deck: DeckKind::Standard,
+        streets: &OMAHA_6_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Standard,
+        lo_ranking: None,
+        equity_game: Some(Game::Omaha),
+        evaluator: &HighOnly(Omaha),
+    },
+    GameDef {
+        id: "omaha-hi-lo",
+        aliases: &["plo8", "o8", "lo8", "omaha8"],
+        name: "Omaha Hi/Lo",
+        deck: DeckKind::Standard,
+        streets: &OMAHA_STREETS,
+        hi_lo: true,
+        lo_qualifier: Some(Rank::Eight),
+        hi_ranking: Ranking::Standard,
+        lo_ranking: Some(Ranking::AceFive),
+        equity_game: Some(Game::OmahaHiLo),
+        evaluator: &HiLo(OmahaHiLo),
+    },
+    GameDef {
+        id: "omaha-5-hi-lo",
+        aliases: &["plo58", "big-o"],
+        name: "5-Card Omaha Hi/Lo",
+        deck: DeckKind::Standard,
+        streets: &OMAHA_5_STREETS,
+        hi_lo: true,
+        lo_qualifier: Some(Rank::Eight),
+        hi_ranking: Ranking::Standard,
+        lo_ranking: Some(Ranking::AceFive),
+        equity_game: Some(Game::OmahaHiLo),
+        evaluator: &HiLo(OmahaHiLo),
+    },
+    GameDef {
+        id: "stud",
+        aliases: &["7cs", "7-card-stud"],
+        name: "Seven-Card Stud",
+        deck: DeckKind::Standard,
+        streets: &STUD_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Standard,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(Stud),
+    },
+    GameDef {
+        id: "stud-hi-lo",
+        aliases: &["stud8", "7cs8"],
+        name: "Seven-Card Stud Hi/Lo",
+        deck: DeckKind::Standard,
+        streets: &STUD_STREETS,
+        hi_lo: true,
+        lo_qualifier: Some(Rank::Eight),
+        hi_ranking: Ranking::Standard,
+        lo_ranking: Some(Ranking::AceFive),
+        equity_game: None,
+        evaluator: &HiLo(StudHiLo),
+    },
+    GameDef {
+        id: "razz",
+        aliases: &[],
+        name: "Razz",
+        deck: DeckKind::Standard,
+        streets: &STUD_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::AceFive,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(Razz),
+    },
+    GameDef {
+        id: "five-card-draw",
+        aliases: &["5cd"],
+        name: "Five-Card Draw",
+        deck: DeckKind::Standard,
+        streets: &SINGLE_DRAW_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Standard,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(FiveCardDraw),
+    },
+    GameDef {
+        id: "deuces-wild",
+        aliases: &["5cd-deuces"],
+        name: "Five-Card Draw, Deuces Wild",
+        deck: DeckKind::Standard,
+        streets: &SINGLE_DRAW_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::DeucesWild,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(DeucesWild),
+    },
+    GameDef {
+        id: "2-7-single-draw",
+        aliases: &["2-7sd", "27sd", "nl27"],
+        name: "2-7 Single Draw",
+        deck: DeckKind::Standard,
+        streets: &SINGLE_DRAW_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::DeuceSeven,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(DeuceSeven),
+    },
+    GameDef {
+        id: "2-7-triple-draw",
+        aliases: &["2-7td", "27td"],
+        name: "2-7 Triple Draw",
+        deck: DeckKind::Standard,
+        streets: &TRIPLE_DRAW_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::DeuceSeven,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(DeuceSeven),
+    },
+    GameDef {
+        id: "a-5-triple-draw",
+        aliases: &["a-5td", "a5td"],
+        name: "A-5 Triple Draw",
+        deck: DeckKind::Standard,
+        streets: &TRIPLE_DRAW_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::AceFive,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(Razz),
+    },
+    GameDef {
+        id: "badugi",
+        aliases: &[],
+        name: "Badugi",
+        deck: DeckKind::Standard,
+        streets: &BADUGI_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Badugi,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(Badugi),
+    },
+    GameDef {
+        id: "baduci",
+        aliases: &[],
+        name: "Baduci",
+        deck: DeckKind::Standard,
+        streets: &BADUGI_STREETS,
+        hi_lo: false,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Baduci,
+        lo_ranking: None,
+        equity_game: None,
+        evaluator: &HighOnly(Baduci),
+    },
+    GameDef {
+        id: "badeucy",
+        aliases: &[],
+        name: "Badeucy",
+        deck: DeckKind::Standard,
+        streets: &TRIPLE_DRAW_STREETS,
+        hi_lo: true,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Baduci,
+        lo_ranking: Some(Ranking::DeuceSeven),
+        equity_game: None,
+        evaluator: &HiLo(Badeucy),
+    },
+    GameDef {
+        id: "badacey",
+        aliases: &[],
+        name: "Badacey",
+        deck: DeckKind::Standard,
+        streets: &TRIPLE_DRAW_STREETS,
+        hi_lo: true,
+        lo_qualifier: None,
+        hi_ranking: Ranking::Badugi,
+        lo_ranking: Some(Ranking::AceFive),
+        equity_game: None,
+        evaluator: &HiLo(Badacey),
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        badugi_rank, base::ParseError, deuce_seven_rank, omaha_rank, poker_rank, short_deck_rank,
+    };
+    use rstest::rstest;
+
+    #[test]
+    fn ids_and_aliases_are_unique() {
+        let names = games()
+            .iter()
+            .flat_map(|g| core::iter::once(&g.id).chain(g.aliases.iter()))
+            .collect::<Vec<_>>();
+        for (i, name) in names.iter().enumerate() {
+            assert!(
+                names[(i + 1)..]
+                    .iter()
+                    .all(|n| !n.eq_ignore_ascii_case(name)),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[rstest]
+    #[case("nlhe", "holdem", 2, 5)]
+    #[case("PLO8", "omaha-hi-lo", 4, 5)]
+    #[case("2-7td", "2-7-triple-draw", 5, 0)]
+    #[case("badugi", "badugi", 4, 0)]
+    #[case("short-deck", "short-deck", 2, 5)]
+    #[case("big-o", "omaha-5-hi-lo", 5, 5)]
+    #[case("stud8", "stud-hi-lo", 7, 0)]
+    #[case("manila", "manila", 2, 5)]
+    fn lookups(
+        #[case] name: &str,
+        #[case] id: &str,
+        #[case] hole_cards: usize,
+        #[case] board_cards: usize,
+    ) {
+        let game = find_game(name).unwrap();
+        assert_eq!(game.id, id);
+        assert_eq!(game.hole_cards(), hole_cards);
+        assert_eq!(game.board_cards(), board_cards);
+    }
+
+    #[test]
+    fn rules_of_split_games() {
+        let split = games()
+            .iter()
+            .filter(|g| g.hi_lo)
+            .map(|g| (g.id, g.lo_qualifier))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            split,
+            [
+                ("omaha-hi-lo", Some(Rank::Eight)),
+                ("omaha-5-hi-lo", Some(Rank::Eight)),
+                ("stud-hi-lo", Some(Rank::Eight)),
+                ("badeucy", None),
+                ("badacey", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn rankings_of_halves() {
+        for game in games() {
+            assert_eq!(game.lo_ranking.is_some(), game.hi_lo, "{}", game.id);
+        }
+        let razz = find_game("razz").unwrap();
+        assert_eq!((razz.hi_ranking, razz.lo_ranking), (Ranking::AceFive, None));
+        let badeucy = find_game("badeucy").unwrap();
+        assert_eq!(badeucy.hi_ranking, Ranking::Baduci);
+        assert_eq!(badeucy.lo_ranking, Some(Ranking::DeuceSeven));
+    }
+
+    #[test]
+    fn equity_games() {
+        let supported = games()
+            .iter()
+            .filter_map(|g| Some((g.id, g.equity_game?)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            supported,
+            [
+                ("holdem", Game::Holdem),
+                ("short-deck", Game::ShortDeck),
+                ("omaha", Game::Omaha),
+                ("omaha-5", Game::Omaha),
+                ("omaha-6", Game::Omaha),
+                ("omaha-hi-lo", Game::OmahaHiLo),
+                ("omaha-5-hi-lo", Game::OmahaHiLo),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("holdem", "Ah Kd", "Qs Jh Tc 2d 3c", "Ah Kd Qs Jh Tc", "Straight")]
+    #[case("holdem", "Ah Kd", "", "Ah Kd", "High Card")]
+    #[case("omaha", "Ah Ad Kh Qh", "2h 3h 4h 5c 6c", "Ah Kh 2h 3h 4h", "Flush")]
+    #[case("manila", "Ah 9d", "Ts 8h 7h Kh Kc", "Ah 9d Ts 8h 7h", "Straight")]
+    #[case("razz", "Ac 2d 3h 4s 5c Kd Kc", "", "Ac 2d 3h 4s 5c", "High Card")]
+    #[case("badugi", "Kc 4d 3h 2h", "", "Kc 4d 2h", "Three Cards")]
+    #[case(
+        "deuces-wild",
+        "2c 2d Ah As Kd",
+        "",
+        "2c 2d Ah As Kd",
+        "Four of a Kind"
+    )]
+    fn best_hands(
+        #[case] id: &str,
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] cards: &str,
+        #[case] category: &str,
+    ) -> Result<(), ParseError> {
+        let game = find_game(id).unwrap();
+        let (hole, board) = (hole.parse()?, board.parse()?);
+        let best = game.best_hands(&hole, &board).unwrap();
+        let hi = best.hi.unwrap();
+        assert_eq!(hi.value, game.evaluate(&hole, &board).unwrap().hi);
+        assert_eq!(hi.cards, cards.parse()?);
+        assert_eq!(hi.category.to_string(), category);
+        assert_eq!(best.lo, None);
+        Ok(())
+    }
+
+    #[test]
+    fn best_hands_of_split_games() -> Result<(), ParseError> {
+        let game = find_game("badacey").unwrap();
+        let best = game
+            .best_hands(&"Ac 2d 3h 4s 4c".parse()?, &Hand::new())
+            .unwrap();
+        assert_eq!(best.hi.unwrap().cards, "Ac 2d 3h 4s".parse()?);
+        let lo = best.lo.unwrap();
+        assert_eq!(lo.cards, "Ac 2d 3h 4s 4c".parse()?);
+        assert_eq!(lo.category, HandCategory::Poker(PokerRankCategory::Pair));
+
+        // Omaha hands cannot be ranked before the flop, and nine-lows do not
+        // qualify.
+        let game = find_game("plo8").unwrap();
+        let hole = "Ah 2d Kc Kd".parse()?;
+        let best = game.best_hands(&hole, &Hand::new()).unwrap();
+        assert_eq!((best.hi, best.lo), (None, None));
+        let best = game.best_hands(&hole, &"9c 7d 5s Kh Qd".parse()?).unwrap();
+        assert_eq!(best.hi.unwrap().cards, "Kc Kd Kh Qd 9c".parse()?);
+        assert_eq!(best.lo, None);
+        Ok(())
+    }
+
+    #[test]
+    fn qualifiers_match_evaluators() -> Result<(), ParseError> {
+        // A nine-low does not qualify in the eight-or-better games.
+        let board = "9c 7d 5s Kh Qd".parse()?;
+        let omaha = find_game("plo8").unwrap();
+        let nine_low = omaha.evaluate(&"Ah 2d Kc Kd".parse()?, &board).unwrap();
+        assert_eq!(omaha.lo_qualifier, Some(Rank::Eight));
+        assert_eq!(nine_low.lo, None);
+
+        let stud = find_game("stud8").unwrap();
+        let nine_low = stud
+            .evaluate(&"Ah 2d 5c 7s 9h Kd Kc".parse()?, &Hand::new())
+            .unwrap();
+        assert_eq!(nine_low.lo, None);
+        Ok(())
+    }
+
+    #[test]
+    fn decks() {
+        for game in games() {
+            assert_eq!(game.deck.deck(0).len(), game.deck.card_count());
+        }
+        assert_eq!(DeckKind::Standard.card_count(), 52);
+        assert_eq!(DeckKind::ShortDeck.card_count(), 36);
+        assert_eq!(DeckKind::Manila.card_count(), 32);
+        assert_eq!(find_game("6+").unwrap().deck, DeckKind::ShortDeck);
+        assert_eq!(
+            find_game("2-7td")
+                .unwrap()
+                .streets
+                .iter()
+                .filter(|s| s.draw)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn values_match_rank_functions() -> Result<(), ParseError> {
+        let hole: Hand = "Ah Kh".parse()?;
+        let board: Hand = "Qh Jh 9h 7c 6d".parse()?;
+        let mut hand = hole;
+        hand.extend(board.iter());
+
+        let holdem = find_game("holdem")
+            .unwrap()
+            .evaluate(&hole, &board)
+            .unwrap();
+        assert_eq!(holdem.hi, HandValue(poker_rank(&hand).0));
+        assert_eq!(holdem.lo, None);
+        let short_deck = find_game("6+").unwrap().evaluate(&hole, &board).unwrap();
+        assert_eq!(short_deck.hi, HandValue(short_deck_rank(&hand).0));
+
+        let hole = "Ah Kh 2c 3d".parse()?;
+        let omaha = find_game("plo").unwrap().evaluate(&hole, &board).unwrap();
+        assert_eq!(omaha.hi, HandValue(omaha_rank(&hole, &board).0));
+
+        let hand = "7c 5d 4h 3s 2c".parse()?;
+        let empty = Hand::new();
+        let deuce_seven = find_game("27sd").unwrap().evaluate(&hand, &empty).unwrap();
+        assert_eq!(deuce_seven.hi, HandValue(deuce_seven_rank(&hand).0));
+
+        let hand = "Ac 2d 3h 4s".parse()?;
+        let badugi = find_game("badugi")
+            .unwrap()
+            .evaluate(&hand, &empty)
+            .unwrap();
+        assert_eq!(badugi.hi, HandValue(badugi_rank(&hand).0));
+        Ok(())
+    }
+
+    #[test]
+    fn split_values() -> Result<(), ParseError> {
+        let empty = Hand::new();
+        let badacey = find_game("badacey").unwrap();
+        let wheel = badacey
+            .evaluate(&"Ac 2d 3h 4s 5c".parse()?, &empty)
+            .unwrap();
+        let paired = badacey
+            .evaluate(&"Ac Ad 3h 4s 5c".parse()?, &empty)
+            .unwrap();
+        assert!(wheel.hi > paired.hi);
+        assert!(wheel.lo > paired.lo);
+
+        let omaha = find_game("plo8").unwrap();
+        let preflop = omaha.evaluate(&"Ah 2h 3c 4c".parse()?, &empty).unwrap();
+        assert_eq!(preflop.hi, HandValue(0));
+        assert_eq!(preflop.lo, None);
+
+        let stud = find_game("stud8").unwrap();
+        let no_low = stud.evaluate(&"Kc Kd Qh".parse()?, &empty).unwrap();
+        assert_eq!(no_low.lo, None);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::holdem_three_hole("holdem", "Ah Kh Qh", "", EquityError::InvalidHoleCards)]
+    #[case::holdem_two_board("holdem", "Ah Kh", "2c 3c", EquityError::InvalidBoard)]
+    #[case::omaha_two_hole("plo", "Ah Kh", "2c 3c 4c", EquityError::InvalidHoleCards)]
+    #[case::stud_board("stud", "Ah Kh Qh", "2c 3c 4c", EquityError::InvalidBoard)]
+    #[case::short_deck_card("6+", "Ah 2h", "", EquityError::InvalidCard)]
+    #[case::manila_card("manila", "Ah 6h", "Kc", EquityError::InvalidCard)]
+    #[case::shared_card("holdem", "Ah Kh", "Ah Qh Jh", EquityError::DuplicateCard)]
+    fn invalid_deals(
+        #[case] game: &str,
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let game = find_game(game).unwrap();
+        assert_eq!(
+            game.evaluate(&hole.parse()?, &board.parse()?),
+            Err(expected)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,466 @@
+//! Resolution of showdowns, with side pots, split pots and hi/lo halves.
+//!
+//! The main and side pots are built from the chips every player contributed
+//! to the pot, and each of them is awarded to the best hands among the players
+//! who did not fold and contributed enough to be eligible for it. Hands may be
+//! ranked with any of the crate's evaluators, as the ranks are only compared
+//! with each other.
+//!
+//! Players must be given in order of position, starting from the first player
+//! to the left of the button. Whenever a pot, or half a pot, cannot be split
+//! evenly between its winners, the odd chips are awarded one at a time to the
+//! winners in that order.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::poker_rank;
+//! use aya_poker::showdown::{resolve_showdown, Player};
+//!
+//! let board = "Ah 9s 7d 4c 2h".parse::<aya_poker::base::Hand>()?;
+//! let rank = |hole: &str| -> Result<_, aya_poker::base::ParseError> {
+//!     let mut hand = hole.parse::<aya_poker::base::Hand>()?;
+//!     hand.extend(board.iter());
+//!     Ok(poker_rank(&hand))
+//! };
+//!
+//! let players = [
+//!     // All-in short stack with the best hand.
+//!     Player::new(50, rank("Ac Ad")?),
+//!     Player::new(200, rank("Kc Kd")?),
+//!     Player::new(200, rank("Qc Qd")?),
+//! ];
+//! let showdown = resolve_showdown(&players).unwrap();
+//!
+//! assert_eq!(showdown.pots.len(), 2);
+//! assert_eq!(showdown.pots[0].amount, 150);
+//! assert_eq!(showdown.pots[1].eligible, [1, 2]);
+//! assert_eq!(showdown.payouts, [150, 300, 0]);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A player taking part in a showdown.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct Player<H, L = ()> {
+    /// The number of chips the player contributed to the pot.
+    pub contribution: u64,
+    /// Whether the player folded, so that they cannot win any of the pots.
+    pub folded: bool,
+    /// The rank of the high hand of the player.
+    pub hi: H,
+    /// The rank of the low hand of the player, if it qualifies for the low
+    /// half of a hi/lo pot.
+    pub lo: Option<L>,
+}
+
+impl<H> Player<H> {
+    /// Creates a player contending for the high hand only.
+    pub fn new(contribution: u64, hi: H) -> Player<H> {
+        Player {
+            contribution,
+            folded: false,
+            hi,
+            lo: None,
+        }
+    }
+}
+
+impl<H, L> Player<H, L> {
+    /// Creates a player in a hi/lo game, with the rank of their qualifying
+    /// low hand, if any.
+    pub fn hi_lo(contribution: u64, hi: H, lo: Option<L>) -> Player<H, L> {
+        Player {
+            contribution,
+            folded: false,
+            hi,
+            lo,
+        }
+    }
+
+    /// Returns the player after folding, which leaves their contribution in
+    /// the pot but makes them ineligible to win it.
+    pub fn fold(mut self) -> Player<H, L> {
+        self.folded = true;
+        self
+    }
+}
+
+/// A main or side pot and its distribution among the players.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Pot {
+    /// The number of chips in the pot.
+    pub amount: u64,
+    /// The indices of the players eligible to win the pot.
+    pub eligible: Vec<usize>,
+    /// The indices of the players with the best high hand.
+    pub hi_winners: Vec<usize>,
+    /// The indices of the players with the best qualifying low hand, or none
+    /// if none of the eligible players holds a qualifying low.
+    pub lo_winners: Vec<usize>,
+    /// The number of chips awarded to each of the players.
+    pub payouts: Vec<u64>,
+}
+
+/// The outcome of a showdown.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Showdown {
+    /// The main pot followed by the side pots, in order of increasing
+    /// contributions of the eligible players.
+    pub pots: Vec<Pot>,
+    /// The total number of chips awarded to each of the players.
+    pub payouts: Vec<u64>,
+}
+
+/// An error which can be returned when resolving a showdown.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShowdownError {
+    /// Every player folded, so that no one can win the pot.
+    NoLivePlayers,
+}
+
+impl fmt::Display for ShowdownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShowdownError::NoLivePlayers => write!(f, "every player folded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ShowdownError {}
+
+/// Builds the main and side pots from the contributions of the players and
+/// awards each of them to the best eligible hands.
+///
+/// A pot is built for every distinct contribution of the players who did not
+/// fold, and chips contributed by folded players beyond the largest such
+/// contribution are added to the last pot. If any eligible player holds a
+/// qualifying low, the pot is split into a high half, which receives the odd
+/// chip, and a low half, and each half is split between its winners.
+///
+/// Returns an error if every player folded.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{omaha_lo_rank, omaha_rank, showdown::{resolve_showdown, Player}};
+///
+/// let board = "Ks 8d 5c 3h 2s".parse()?;
+/// let holes = ["Ah 4d Kc Qd", "Ad 4c Jh Jc", "9h 9s Td 7c"];
+///
+/// let players = holes
+///     .iter()
+///     .map(|hole| {
+///         let hole = hole.parse()?;
+///         let lo = omaha_lo_rank(&hole, &board).to_lo_8_rank();
+///         let lo = Some(lo).filter(|lo| lo.0 != 0);
+///         Ok(Player::hi_lo(100, omaha_rank(&hole, &board), lo))
+///     })
+///     .collect::<Result<Vec<_>, aya_poker::base::ParseError>>()?;
+///
+/// // The two wheels split both halves of the pot.
+/// let showdown = resolve_showdown(&players).unwrap();
+/// assert_eq!(showdown.pots[0].hi_winners, [0, 1]);
+/// assert_eq!(showdown.pots[0].lo_winners, [0, 1]);
+/// assert_eq!(showdown.payouts, [150, 150, 0]);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn resolve_showdown<H: Ord, L: Ord>(
+    players: &[Player<H, L>],
+) -> Result<Showdown, ShowdownError> {
+    let mut levels = players
+        .iter()
+        .filter(|p| !p.folded)
+        .map(|p| p.contribution)
+        .collect::<Vec<_>>();
+    if levels.is_empty() {
+        return Err(ShowdownError::NoLivePlayers);
+    }
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots = Vec::with_capacity(levels.len());
+    let mut previous = 0;
+    for (i, &level) in levels.iter().enumerate() {
+        let is_last = i == levels.len() - 1;
+        let amount = players
+            .iter()
+            .map(|p| {
+                let capped = if is_last {
+                    p.contribution
+                } else {
+                    p.contribution.min(level)
+                };
+                capped.saturating_sub(previous)
+            })
+            .sum();
+        previous = level;
+        if amount == 0 {
+            continue;
+        }
+
+        let eligible = (0..players.len())
+            .filter(|&j| !players[j].folded && players[j].contribution >= level)
+            .collect();
+        pots.push(award_pot(players, amount, eligible));
+    }
+
+    let mut payouts = alloc::vec![0; players.len()];
+    for pot in pots.iter() {
+        for (total, payout) in payouts.iter_mut().zip(pot.payouts.iter()) {
+            *total += payout;
+        }
+    }
+
+    Ok(Showdown { pots, payouts })
+}
+
+fn award_pot<H: Ord, L: Ord>(players: &[Player<H, L>], amount: u64, eligible: Vec<usize>) -> Pot {
+    let hi_winners = best_players(&eligible, |j| Some(&players[j].hi));
+    let lo_winners = best_players(&eligible, |j| players[j].lo.as_ref());
+
+    let mut payouts = alloc::vec![0; players.len()];
+    if lo_winners.is_empty() {
+        split(amount, &hi_winners, &mut payouts);
+    } else {
+        split(amount - amount / 2, &hi_winners, &mut payouts);
+        split(amount / 2, &lo_winners, &mut payouts);
+    }
+
+    Pot {
+        amount,
+        eligible,
+        hi_winners,
+        lo_winners,
+        payouts,
+    }
+}
+
+/// Returns the players with the best of the given ranks, in order of
+/// position.
+fn best_players<'a, R: Ord + 'a>(
+    eligible: &[usize],
+    rank: impl Fn(usize) -> Option<&'a R>,
+) -> Vec<usize> {
+    let best = eligible.iter().filter_map(|&j| rank(j)).max();
+    match best {
+        Some(best) => eligible
+            .iter()
+            .copied()
+            .filter(|&j| rank(j) == Some(best))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Splits the chips evenly between the winners, awarding the odd chips to
+/// the winners in order of position.
+fn split(amount: u64, winners: &[usize], payouts: &mut [u64]) {
+    let share = amount / winners.len() as u64;
+    let odd_chips = (amount % winners.len() as u64) as usize;
+    for (i, &winner) in winners.iter().enumerate() {
+        payouts[winner] += share + u64::from(i < odd_chips);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base::ParseError, omaha_lo_rank, omaha_rank, poker_rank, PokerHandRank};
+    use rstest::rstest;
+
+    fn holdem_player(
+        contribution: u64,
+        hole: &str,
+        board: &str,
+    ) -> Result<Player<PokerHandRank>, ParseError> {
+        let mut hand: aya_base::Hand = hole.parse()?;
+        hand.extend(board.parse::<aya_base::Hand>()?.iter());
+        Ok(Player::new(contribution, poker_rank(&hand)))
+    }
+
+    #[test]
+    fn single_pot_to_best_hand() -> Result<(), ParseError> {
+        let board = "Ah 9s 7d 4c 2h";
+        let players = [
+            holdem_player(100, "Kc Kd", board)?,
+            holdem_player(100, "Ac Qd", board)?,
+            holdem_player(100, "9c 8c", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.pots.len(), 1);
+        assert_eq!(showdown.pots[0].hi_winners, [1]);
+        assert!(showdown.pots[0].lo_winners.is_empty());
+        assert_eq!(showdown.payouts, [0, 300, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn side_pots_from_all_ins() -> Result<(), ParseError> {
+        let board = "Ah 9s 7d 4c 2h";
+        let players = [
+            holdem_player(300, "Kc Kd", board)?,
+            holdem_player(50, "Ac Ad", board)?,
+            holdem_player(300, "Qc Qd", board)?,
+            holdem_player(120, "Ts Th", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+
+        let amounts = showdown.pots.iter().map(|p| p.amount).collect::<Vec<_>>();
+        assert_eq!(amounts, [200, 210, 360]);
+        assert_eq!(showdown.pots[0].eligible, [0, 1, 2, 3]);
+        assert_eq!(showdown.pots[1].eligible, [0, 2, 3]);
+        assert_eq!(showdown.pots[2].eligible, [0, 2]);
+        assert_eq!(showdown.payouts, [570, 200, 0, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn uncalled_bet_is_returned() -> Result<(), ParseError> {
+        let board = "Ah 9s 7d 4c 2h";
+        let players = [
+            holdem_player(500, "3c 3d", board)?,
+            holdem_player(200, "Ac Ad", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.pots[1].amount, 300);
+        assert_eq!(showdown.pots[1].eligible, [0]);
+        assert_eq!(showdown.payouts, [300, 400]);
+        Ok(())
+    }
+
+    #[test]
+    fn folded_players_cannot_win() -> Result<(), ParseError> {
+        let board = "Ah 9s 7d 4c 2h";
+        let players = [
+            holdem_player(20, "Ac Ad", board)?.fold(),
+            holdem_player(100, "Kc Kd", board)?,
+            holdem_player(60, "5c 3d", board)?.fold(),
+            holdem_player(100, "Qc Qd", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.pots.len(), 1);
+        assert_eq!(showdown.pots[0].eligible, [1, 3]);
+        assert_eq!(showdown.payouts, [0, 280, 0, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn dead_money_above_live_contributions() {
+        let players = [
+            Player::new(150, 3).fold(),
+            Player::new(100, 2),
+            Player::new(40, 1),
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        let amounts = showdown.pots.iter().map(|p| p.amount).collect::<Vec<_>>();
+        assert_eq!(amounts, [120, 170]);
+        assert_eq!(showdown.payouts, [0, 290, 0]);
+    }
+
+    #[rstest]
+    #[case::even_split(&[1, 2, 2], 33, 1, &[0, 50, 50, 0])]
+    #[case::one_odd_chip(&[2, 2, 1], 33, 2, &[51, 50, 0, 0])]
+    #[case::two_odd_chips(&[2, 2, 2], 33, 2, &[34, 34, 33, 0])]
+    #[case::odd_chip_by_position(&[1, 2, 1, 2], 25, 1, &[0, 51, 0, 50, 0])]
+    fn odd_chips(
+        #[case] ranks: &[u8],
+        #[case] contribution: u64,
+        #[case] dead: u64,
+        #[case] expected: &[u64],
+    ) {
+        let mut players = ranks
+            .iter()
+            .map(|&rank| Player::new(contribution, rank))
+            .collect::<Vec<_>>();
+        players.push(Player::new(dead, 0).fold());
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.payouts, expected);
+    }
+
+    fn omaha_player(
+        contribution: u64,
+        hole: &str,
+        board: &str,
+    ) -> Result<Player<PokerHandRank, crate::AceFiveHandRank>, ParseError> {
+        let hole = hole.parse()?;
+        let board = board.parse()?;
+        let lo = omaha_lo_rank(&hole, &board).to_lo_8_rank();
+        let lo = Some(lo).filter(|lo| lo.0 != 0);
+        Ok(Player::hi_lo(contribution, omaha_rank(&hole, &board), lo))
+    }
+
+    #[test]
+    fn hi_lo_scoop_without_low() -> Result<(), ParseError> {
+        let board = "Ks Td 9c 9h 2s";
+        let players = [
+            omaha_player(100, "Ah 3d Kc Qd", board)?,
+            omaha_player(100, "9s 8s 4c 3c", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert!(showdown.pots[0].lo_winners.is_empty());
+        assert_eq!(showdown.payouts, [0, 200]);
+        Ok(())
+    }
+
+    #[test]
+    fn hi_lo_split_with_odd_chip_to_high() -> Result<(), ParseError> {
+        let board = "Ks 8d 7c 3h 2s";
+        let players = [
+            omaha_player(67, "Kc Kd Qh Jh", board)?,
+            omaha_player(67, "Ah 4d Tc 9d", board)?,
+            omaha_player(67, "7h 6h Tc Td", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.pots[0].hi_winners, [0]);
+        assert_eq!(showdown.pots[0].lo_winners, [1]);
+        assert_eq!(showdown.payouts, [101, 100, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn hi_lo_quartering() -> Result<(), ParseError> {
+        let board = "Ks 8d 7c 3h 2s";
+        let players = [
+            omaha_player(100, "Kc Kd Qh Jh", board)?,
+            omaha_player(100, "Ah 4d Tc 9d", board)?,
+            omaha_player(100, "Ad 4c Jd 9h", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.pots[0].lo_winners, [1, 2]);
+        assert_eq!(showdown.payouts, [150, 75, 75]);
+        Ok(())
+    }
+
+    #[test]
+    fn hi_lo_side_pot_without_low() -> Result<(), ParseError> {
+        let board = "Ks 8d 7c 3h 2s";
+        let players = [
+            omaha_player(100, "Ah 4d Tc 9d", board)?,
+            omaha_player(300, "Kc Kd Qh Jh", board)?,
+            omaha_player(300, "Qc Qd Jc Js", board)?,
+        ];
+        let showdown = resolve_showdown(&players).unwrap();
+        assert_eq!(showdown.pots[0].lo_winners, [0]);
+        assert!(showdown.pots[1].lo_winners.is_empty());
+        assert_eq!(showdown.payouts, [150, 550, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn everyone_folded() {
+        let players = [Player::new(10, 1).fold(), Player::new(20, 2).fold()];
+        assert_eq!(
+            resolve_showdown(&players),
+            Err(ShowdownError::NoLivePlayers)
+        );
+        let players: [Player<u8>; 0] = [];
+        assert_eq!(
+            resolve_showdown(&players),
+            Err(ShowdownError::NoLivePlayers)
+        );
+    }
+}
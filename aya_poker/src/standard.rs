@@ -62,6 +62,7 @@ impl PokerHandRank {
             7 => PokerRankCategory::FourOfAKind,
             8 => PokerRankCategory::StraightFlush,
             9 => PokerRankCategory::RoyalFlush,
+            10 => PokerRankCategory::FiveOfAKind,
             _ => unreachable!(),
         }
     }
@@ -0,0 +1,401 @@
+//! Hand strength and hand potential metrics for hold'em.
+//!
+//! The metrics follow the definitions used in poker bots and in the
+//! bucketing of hands for abstractions: the current hand strength (HS)
+//! against an opponent's range, the positive and negative potentials (PPot
+//! and NPot) to move ahead or fall behind by the river, the effective hand
+//! strength (EHS) combining the three, and the expected square of the hand
+//! strength on the river (EHS²).
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::range::Range;
+//! use aya_poker::strength::exact_hand_strength;
+//!
+//! let hole = "Ah Kh".parse()?;
+//! let board = "Qh 7h 2c".parse()?;
+//! let strength = exact_hand_strength(&hole, &board, &"".parse()?, &Range::full()).unwrap();
+//! assert!(strength.positive_potential > 0.3);
+//! assert!(strength.ehs > strength.strength);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use aya_base::{Card, Hand, CARDS};
+
+use crate::{
+    combine,
+    deck::Deck,
+    equity::{remaining_deck, EquityError, Game},
+    for_each_subhand, poker_rank,
+    range::Range,
+};
+
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+/// The strength and potential of a hold'em hand against an opponent's range.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct HandStrength {
+    /// The probability that the hand is currently ahead of the opponent's
+    /// hand, counting ties as half, i.e. HS.
+    pub strength: f64,
+    /// The probability that the hand ends up ahead on the river when it is
+    /// currently behind, counting ties as half, i.e. PPot.
+    pub positive_potential: f64,
+    /// The probability that the hand ends up behind on the river when it is
+    /// currently ahead, counting ties as half, i.e. NPot.
+    pub negative_potential: f64,
+    /// The effective hand strength, i.e. `HS * (1 - NPot) + (1 - HS) * PPot`.
+    pub ehs: f64,
+    /// The expected square of the hand strength on the river, i.e. EHS²,
+    /// which rewards hands whose strength varies more across runouts.
+    pub ehs2: f64,
+}
+
+/// Returns the strength and potential of the hole cards against an
+/// opponent holding a hand from `range`, by enumerating every runout of the
+/// board and every hand of the range.
+///
+/// The hands are ranked with [`poker_rank`], and the combos of `range` are
+/// weighted by their weight in the range, while combos sharing a card with
+/// the hole cards, the board or the `dead` cards are ignored. Both
+/// potentials are zero on the river.
+///
+/// Returns an error if the hole cards are not exactly two cards, if the
+/// board does not hold between three and five cards, if any card was dealt
+/// twice, or if no hand of the range can be dealt.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::range::Range;
+/// use aya_poker::strength::exact_hand_strength;
+///
+/// let range = "QQ+, AK".parse::<Range>()?;
+/// let strength =
+///     exact_hand_strength(&"Jc Jd".parse()?, &"Js 8d 3c".parse()?, &"".parse()?, &range)
+///         .unwrap();
+/// assert_eq!(strength.strength, 1.0);
+/// assert!(strength.negative_potential > 0.0);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn exact_hand_strength(
+    hole: &Hand,
+    board: &Hand,
+    dead: &Hand,
+    range: &Range,
+) -> Result<HandStrength, EquityError> {
+    let (deck, deck_len) = prepare(hole, board, dead)?;
+    let opponents = Opponents::new(hole, board, dead, range)?;
+
+    let mut potential = Potential::default();
+    for_each_subhand(&deck[..deck_len], 5 - board.len(), *board, &mut |runout| {
+        potential.add_runout(hole, &runout, &opponents)
+    });
+
+    Ok(potential.result(opponents.strength()))
+}
+
+/// Returns the strength and potential of the hole cards against an
+/// opponent holding a hand from `range`, estimated from `samples` random
+/// runouts of the board drawn with the given random seed.
+///
+/// Every hand of the range is played out on each sampled runout, so that
+/// HS is always exact and EHS² averages the exact river strengths of the
+/// sampled runouts. On the river, the result is the same as that of
+/// [`exact_hand_strength`].
+///
+/// Returns an error under the same conditions as [`exact_hand_strength`].
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::range::Range;
+/// use aya_poker::strength::{exact_hand_strength, sampled_hand_strength};
+///
+/// let hole = "9s 8s".parse()?;
+/// let board = "Ts 7d 2s".parse()?;
+/// let range = "22+, A2s+, KTs+, ATo+".parse::<Range>()?;
+/// let exact = exact_hand_strength(&hole, &board, &"".parse()?, &range).unwrap();
+/// let sampled = sampled_hand_strength(&hole, &board, &"".parse()?, &range, 300, 42).unwrap();
+/// assert_eq!(sampled.strength, exact.strength);
+/// assert!((sampled.ehs - exact.ehs).abs() < 0.05);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn sampled_hand_strength(
+    hole: &Hand,
+    board: &Hand,
+    dead: &Hand,
+    range: &Range,
+    samples: usize,
+    seed: u64,
+) -> Result<HandStrength, EquityError> {
+    if board.len() == 5 {
+        return exact_hand_strength(hole, board, dead, range);
+    }
+
+    let (deck_cards, deck_len) = prepare(hole, board, dead)?;
+    let opponents = Opponents::new(hole, board, dead, range)?;
+    let mut deck = Deck::with_seed(deck_cards[..deck_len].iter(), seed);
+
+    let mut potential = Potential::default();
+    for _ in 0..samples {
+        deck.reset();
+        let mut runout = *board;
+        runout.extend(deck.deal(5 - board.len()).unwrap());
+        potential.add_runout(hole, &runout, &opponents);
+    }
+
+    Ok(potential.result(opponents.strength()))
+}
+
+/// Validates the cards and returns the cards left in the deck.
+fn prepare(
+    hole: &Hand,
+    board: &Hand,
+    dead: &Hand,
+) -> Result<([Card; CARDS.len()], usize), EquityError> {
+    if hole.len() != 2 {
+        return Err(EquityError::InvalidHoleCards);
+    }
+    if board.len() < 3 || board.len() > 5 {
+        return Err(EquityError::InvalidBoard);
+    }
+    remaining_deck(Game::Holdem, &[*hole], board, dead)
+}
+
+/// The opponent's possible hands, together with their weights and whether
+/// the hero is currently ahead of, tied with or behind them.
+struct Opponents {
+    hands: Vec<(Hand, f64, usize)>,
+}
+
+impl Opponents {
+    fn new(
+        hole: &Hand,
+        board: &Hand,
+        dead: &Hand,
+        range: &Range,
+    ) -> Result<Opponents, EquityError> {
+        let mut dealt = *hole;
+        dealt.extend(board.iter());
+        dealt.extend(dead.iter());
+
+        let hero_rank = poker_rank(&combine(hole, board));
+        let hands = range
+            .combos()
+            .filter(|(opponent, _)| opponent.is_disjoint(&dealt))
+            .map(|(opponent, weight)| {
+                let rank = poker_rank(&combine(&opponent, board));
+                (opponent, weight, state(hero_rank.cmp(&rank)))
+            })
+            .collect::<Vec<_>>();
+
+        if hands.is_empty() {
+            return Err(EquityError::EmptyRange);
+        }
+        Ok(Opponents { hands })
+    }
+
+    fn strength(&self) -> f64 {
+        let mut totals = [0.0; 3];
+        for &(_, weight, now) in &self.hands {
+            totals[now] += weight;
+        }
+        (totals[AHEAD] + totals[TIED] / 2.0) / totals.iter().sum::<f64>()
+    }
+}
+
+/// The weighted counts of the transitions between the hero being ahead,
+/// tied or behind now and on the river.
+#[derive(Default)]
+struct Potential {
+    transitions: [[f64; 3]; 3],
+    squared_strengths: f64,
+    runouts: u64,
+}
+
+impl Potential {
+    fn add_runout(&mut self, hole: &Hand, runout: &Hand, opponents: &Opponents) {
+        let hero_rank = poker_rank(&combine(hole, runout));
+
+        let mut shares = 0.0;
+        let mut total = 0.0;
+        for &(opponent, weight, now) in &opponents.hands {
+            if !opponent.is_disjoint(runout) {
+                continue;
+            }
+
+            let rank = poker_rank(&combine(&opponent, runout));
+            let river = state(hero_rank.cmp(&rank));
+            self.transitions[now][river] += weight;
+            shares += weight * [1.0, 0.5, 0.0][river];
+            total += weight;
+        }
+
+        if total > 0.0 {
+            let strength = shares / total;
+            self.squared_strengths += strength * strength;
+            self.runouts += 1;
+        }
+    }
+
+    fn result(&self, strength: f64) -> HandStrength {
+        let hp = &self.transitions;
+        let totals = hp.map(|row| row.iter().sum::<f64>());
+
+        let positive_potential = ratio(
+            hp[BEHIND][AHEAD] + hp[BEHIND][TIED] / 2.0 + hp[TIED][AHEAD] / 2.0,
+            totals[BEHIND] + totals[TIED] / 2.0,
+        );
+        let negative_potential = ratio(
+            hp[AHEAD][BEHIND] + hp[TIED][BEHIND] / 2.0 + hp[AHEAD][TIED] / 2.0,
+            totals[AHEAD] + totals[TIED] / 2.0,
+        );
+
+        HandStrength {
+            strength,
+            positive_potential,
+            negative_potential,
+            ehs: strength * (1.0 - negative_potential) + (1.0 - strength) * positive_potential,
+            ehs2: ratio(self.squared_strengths, self.runouts as f64),
+        }
+    }
+}
+
+fn state(ordering: Ordering) -> usize {
+    match ordering {
+        Ordering::Greater => AHEAD,
+        Ordering::Equal => TIED,
+        Ordering::Less => BEHIND,
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    fn strength(hole: &str, board: &str, range: &str) -> Result<HandStrength, ParseError> {
+        let range = if range.is_empty() {
+            Range::full()
+        } else {
+            range.parse()?
+        };
+        Ok(exact_hand_strength(&hole.parse()?, &board.parse()?, &"".parse()?, &range).unwrap())
+    }
+
+    #[test]
+    fn river_has_no_potential() -> Result<(), ParseError> {
+        let result = strength("Ah Kd", "As 7c 4d 9h 2s", "AQ, 77, 22, KJs")?;
+        // Ahead of 8 AQ and 3 KJs, tied with 0 hands, behind 3 77 and 3 22.
+        assert_eq!(result.strength, 11.0 / 17.0);
+        assert_eq!(result.positive_potential, 0.0);
+        assert_eq!(result.negative_potential, 0.0);
+        assert_eq!(result.ehs, result.strength);
+        assert_eq!(result.ehs2, result.strength * result.strength);
+        Ok(())
+    }
+
+    #[test]
+    fn chopped_river() -> Result<(), ParseError> {
+        let result = strength("2c 3d", "Ah Kh Qh Jh Th", "")?;
+        assert_eq!(result.strength, 0.5);
+        assert_eq!(result.ehs2, 0.25);
+        Ok(())
+    }
+
+    #[test]
+    fn turn_potentials() -> Result<(), ParseError> {
+        // The nut flush draw is behind the set and wins on 7 of the 44
+        // rivers, as the 4h fills up the set and the 9h makes quads.
+        let result = strength("Ah Kh", "7h 2h 9c 4d", "99")?;
+        assert_eq!(result.strength, 0.0);
+        assert_eq!(result.positive_potential, 7.0 / 44.0);
+        assert_eq!(result.negative_potential, 0.0);
+        assert_eq!(result.ehs, result.positive_potential);
+
+        let result = strength("9s 9d", "7h 2h 9c 4d", "AhKh")?;
+        assert_eq!(result.strength, 1.0);
+        assert_eq!(result.positive_potential, 0.0);
+        assert_eq!(result.negative_potential, 7.0 / 44.0);
+        assert_eq!(result.ehs, 1.0 - 7.0 / 44.0);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::nuts("Ah Kh", "Qh Jh Th")]
+    #[case::draw("9s 8s", "Ts 7d 2c")]
+    #[case::weak("7c 2d", "Ks Qs 9h")]
+    fn metrics_are_probabilities(
+        #[case] hole: &str,
+        #[case] board: &str,
+    ) -> Result<(), ParseError> {
+        let result = strength(hole, board, "")?;
+        for metric in [
+            result.strength,
+            result.positive_potential,
+            result.negative_potential,
+            result.ehs,
+            result.ehs2,
+        ] {
+            assert!((0.0..=1.0).contains(&metric));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sampled_matches_exact() -> Result<(), ParseError> {
+        let hole = "Jd Td".parse()?;
+        let board = "9d 8c 2d".parse()?;
+        let range = Range::full();
+        let exact = exact_hand_strength(&hole, &board, &"".parse()?, &range).unwrap();
+        let sampled = sampled_hand_strength(&hole, &board, &"".parse()?, &range, 1_000, 7).unwrap();
+
+        assert_eq!(sampled.strength, exact.strength);
+        assert!((sampled.positive_potential - exact.positive_potential).abs() < 0.03);
+        assert!((sampled.negative_potential - exact.negative_potential).abs() < 0.03);
+        assert!((sampled.ehs2 - exact.ehs2).abs() < 0.03);
+
+        let again = sampled_hand_strength(&hole, &board, &"".parse()?, &range, 1_000, 7).unwrap();
+        assert_eq!(sampled, again);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::one_hole_card("Ah", "Kc 7d 2s", "", "", EquityError::InvalidHoleCards)]
+    #[case::preflop("Ah Kd", "", "", "", EquityError::InvalidBoard)]
+    #[case::duplicate("Ah Kd", "Ah 7d 2s", "", "", EquityError::DuplicateCard)]
+    #[case::dead("Ah Kd", "Kc 7d 2s", "Kc", "", EquityError::DuplicateCard)]
+    #[case::blocked_range("Ah Kd", "Kc 7d 2s", "", "AhKd", EquityError::EmptyRange)]
+    fn invalid_hands(
+        #[case] hole: &str,
+        #[case] board: &str,
+        #[case] dead: &str,
+        #[case] range: &str,
+        #[case] expected: EquityError,
+    ) -> Result<(), ParseError> {
+        let range = if range.is_empty() {
+            Range::full()
+        } else {
+            range.parse()?
+        };
+        let result = exact_hand_strength(&hole.parse()?, &board.parse()?, &dead.parse()?, &range);
+        assert_eq!(result, Err(expected));
+        Ok(())
+    }
+}
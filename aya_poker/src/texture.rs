@@ -0,0 +1,359 @@
+//! Analysis of the texture of hold'em and Omaha boards.
+//!
+//! Board textures describe how the suits and ranks of the community cards
+//! interact, e.g. how many flushes and straights they make possible, and are
+//! commonly used to group similar flops together. All of the features are
+//! computed directly from the suit and rank masks of the board.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::base::Rank;
+//! use aya_poker::texture::{board_texture, Pairing, SuitTexture, TextureCategory};
+//!
+//! let texture = board_texture(&"Jh Th 4c".parse()?).unwrap();
+//! assert_eq!(texture.suits, SuitTexture::TwoTone);
+//! assert_eq!(texture.pairing, Pairing::Unpaired);
+//! assert_eq!(texture.high_card, Rank::Jack);
+//! assert_eq!(texture.category, TextureCategory::Dynamic);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use core::{convert::TryFrom, fmt};
+
+use aya_base::{constants::RANK_COUNT, Hand, Rank, Suit};
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+/// The number of distinct five-card straights, from the wheel to broadway.
+const STRAIGHT_COUNT: usize = 10;
+
+/// How the cards of the board are distributed among the suits.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum SuitTexture {
+    /// No two cards share a suit.
+    Rainbow,
+    /// At most two cards share a suit.
+    TwoTone,
+    /// Exactly three cards, but not all of them, share a suit.
+    ThreeSuited,
+    /// Exactly four cards, but not all of them, share a suit.
+    FourSuited,
+    /// All of the cards share a suit.
+    Monotone,
+}
+
+/// How the ranks of the board cards repeat.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Pairing {
+    /// No two cards share a rank.
+    Unpaired,
+    /// Exactly two cards share a rank.
+    Paired,
+    /// Two different pairs of cards share a rank.
+    DoublePaired,
+    /// Three cards share a rank.
+    Trips,
+    /// Three cards share a rank and two other cards share another.
+    FullHouse,
+    /// Four cards share a rank.
+    Quads,
+}
+
+/// A coarse category of boards for grouping similar boards together.
+///
+/// The categories are checked in order, so that e.g. a paired monotone board
+/// is categorized as [`TextureCategory::Paired`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum TextureCategory {
+    /// A board with at least two cards of the same rank.
+    Paired,
+    /// An unpaired board on which a flush can be made.
+    Suited,
+    /// An unpaired board on which a straight, but no flush, can be made.
+    Connected,
+    /// An unpaired board on which neither a flush nor a straight can be
+    /// made, but with two cards of a suit or two cards close enough in rank
+    /// to draw to one.
+    Dynamic,
+    /// An unpaired board offering few draws.
+    Dry,
+}
+
+/// The features of a board.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct BoardTexture {
+    /// How the cards are distributed among the suits.
+    pub suits: SuitTexture,
+    /// How the ranks of the cards repeat.
+    pub pairing: Pairing,
+    /// The highest rank on the board.
+    pub high_card: Rank,
+    /// The largest number of distinct board ranks which can be part of the
+    /// same straight, with the ace playing high or low.
+    pub connectedness: usize,
+    /// The number of distinct straights which can be made with at most two
+    /// hole cards.
+    pub straights: usize,
+    /// The fewest hole cards needed to make a flush, i.e. 0 if the board is
+    /// a flush, or `None` if no flush can be made on the board.
+    pub flush_hole_cards: Option<usize>,
+    /// The category of the board.
+    pub category: TextureCategory,
+}
+
+/// An error which can be returned when analyzing the texture of a board.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TextureError {
+    /// The board holds fewer than three or more than five cards.
+    InvalidBoard,
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureError::InvalidBoard => write!(f, "invalid number of board cards"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TextureError {}
+
+/// Returns the texture of a board of three to five cards.
+///
+/// Returns an error if the board holds fewer than three or more than five
+/// cards.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::texture::{board_texture, Pairing, SuitTexture, TextureCategory};
+///
+/// let texture = board_texture(&"9s 8s 7s 6d".parse()?).unwrap();
+/// assert_eq!(texture.suits, SuitTexture::ThreeSuited);
+/// assert_eq!(texture.connectedness, 4);
+/// assert_eq!(texture.straights, 4);
+/// assert_eq!(texture.flush_hole_cards, Some(2));
+/// assert_eq!(texture.category, TextureCategory::Suited);
+///
+/// let texture = board_texture(&"Kd Kc 2h 2s".parse()?).unwrap();
+/// assert_eq!(texture.pairing, Pairing::DoublePaired);
+/// assert_eq!(texture.suits, SuitTexture::Rainbow);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn board_texture(board: &Hand) -> Result<BoardTexture, TextureError> {
+    let len = board.len();
+    if !(3..=5).contains(&len) {
+        return Err(TextureError::InvalidBoard);
+    }
+
+    let suited = SUITS
+        .iter()
+        .map(|&suit| board.suit_mask(suit).count_ones() as usize)
+        .max()
+        .unwrap();
+    let suits = match suited {
+        1 => SuitTexture::Rainbow,
+        2 => SuitTexture::TwoTone,
+        n if n == len => SuitTexture::Monotone,
+        3 => SuitTexture::ThreeSuited,
+        _ => SuitTexture::FourSuited,
+    };
+    let flush_hole_cards = (suited >= 3).then_some(5 - suited);
+
+    let rank_mask = board.rank_mask();
+    let pairing = pairing(board);
+    let high_card = Rank::try_from((15 - rank_mask.leading_zeros()) as u8).unwrap();
+
+    let windows = straight_windows(rank_mask);
+    let connectedness = windows.iter().copied().max().unwrap();
+    let straights = windows.iter().filter(|&&n| n >= 3).count();
+
+    let category = if pairing != Pairing::Unpaired {
+        TextureCategory::Paired
+    } else if flush_hole_cards.is_some() {
+        TextureCategory::Suited
+    } else if straights > 0 {
+        TextureCategory::Connected
+    } else if suited == 2 || connectedness == 2 {
+        TextureCategory::Dynamic
+    } else {
+        TextureCategory::Dry
+    };
+
+    Ok(BoardTexture {
+        suits,
+        pairing,
+        high_card,
+        connectedness,
+        straights,
+        flush_hole_cards,
+        category,
+    })
+}
+
+/// Returns the pairing of the board from the ranks shared by its suits.
+fn pairing(board: &Hand) -> Pairing {
+    let [c, d, h, s] = SUITS.map(|suit| board.suit_mask(suit));
+    let pairs = (c & d) | (c & h) | (c & s) | (d & h) | (d & s) | (h & s);
+    let trips = (c & d & h) | (c & d & s) | (c & h & s) | (d & h & s);
+    let quads = c & d & h & s;
+
+    if quads != 0 {
+        Pairing::Quads
+    } else if trips != 0 && pairs & !trips != 0 {
+        Pairing::FullHouse
+    } else if trips != 0 {
+        Pairing::Trips
+    } else if pairs.count_ones() >= 2 {
+        Pairing::DoublePaired
+    } else if pairs != 0 {
+        Pairing::Paired
+    } else {
+        Pairing::Unpaired
+    }
+}
+
+/// Returns the number of board ranks in each of the five-rank windows of the
+/// straights, from the wheel to broadway.
+fn straight_windows(rank_mask: u16) -> [usize; STRAIGHT_COUNT] {
+    // Prepend the ace below the deuce so that the wheel is the first window.
+    let mask = (rank_mask << 1) | (rank_mask >> (RANK_COUNT - 1));
+    let mut windows = [0; STRAIGHT_COUNT];
+    for (i, window) in windows.iter_mut().enumerate() {
+        *window = ((mask >> i) & 0x1f).count_ones() as usize;
+    }
+    windows
+}
+
+impl fmt::Display for SuitTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuitTexture::Rainbow => write!(f, "Rainbow"),
+            SuitTexture::TwoTone => write!(f, "Two-Tone"),
+            SuitTexture::ThreeSuited => write!(f, "Three-Suited"),
+            SuitTexture::FourSuited => write!(f, "Four-Suited"),
+            SuitTexture::Monotone => write!(f, "Monotone"),
+        }
+    }
+}
+
+impl fmt::Display for Pairing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pairing::Unpaired => write!(f, "Unpaired"),
+            Pairing::Paired => write!(f, "Paired"),
+            Pairing::DoublePaired => write!(f, "Double-Paired"),
+            Pairing::Trips => write!(f, "Trips"),
+            Pairing::FullHouse => write!(f, "Full House"),
+            Pairing::Quads => write!(f, "Quads"),
+        }
+    }
+}
+
+impl fmt::Display for TextureCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureCategory::Paired => write!(f, "Paired"),
+            TextureCategory::Suited => write!(f, "Suited"),
+            TextureCategory::Connected => write!(f, "Connected"),
+            TextureCategory::Dynamic => write!(f, "Dynamic"),
+            TextureCategory::Dry => write!(f, "Dry"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::rainbow_flop("Ks 7d 2c", SuitTexture::Rainbow, None)]
+    #[case::two_tone_flop("Ks 7s 2c", SuitTexture::TwoTone, None)]
+    #[case::monotone_flop("Ks 7s 2s", SuitTexture::Monotone, Some(2))]
+    #[case::rainbow_turn("Ks 7d 2c 3h", SuitTexture::Rainbow, None)]
+    #[case::double_two_tone_turn("Ks 7s 2c 3c", SuitTexture::TwoTone, None)]
+    #[case::three_suited_turn("Ks 7s 2s 3c", SuitTexture::ThreeSuited, Some(2))]
+    #[case::monotone_turn("Ks 7s 2s 3s", SuitTexture::Monotone, Some(1))]
+    #[case::four_suited_river("Ks 7s 2s 3s 3d", SuitTexture::FourSuited, Some(1))]
+    #[case::flush_river("Ks 7s 2s 3s 9s", SuitTexture::Monotone, Some(0))]
+    fn suit_textures(
+        #[case] board: &str,
+        #[case] suits: SuitTexture,
+        #[case] flush_hole_cards: Option<usize>,
+    ) -> Result<(), ParseError> {
+        let texture = board_texture(&board.parse()?).unwrap();
+        assert_eq!(texture.suits, suits);
+        assert_eq!(texture.flush_hole_cards, flush_hole_cards);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::unpaired("Ks 7d 2c 3h 9s", Pairing::Unpaired)]
+    #[case::paired("Ks Kd 2c", Pairing::Paired)]
+    #[case::double_paired("Ks Kd 2c 2h 9s", Pairing::DoublePaired)]
+    #[case::trips("7s 7d 7c 3h", Pairing::Trips)]
+    #[case::full_house("7s 7d 7c 3h 3s", Pairing::FullHouse)]
+    #[case::quads("7s 7d 7c 7h", Pairing::Quads)]
+    fn pairings(#[case] board: &str, #[case] pairing: Pairing) -> Result<(), ParseError> {
+        assert_eq!(board_texture(&board.parse()?).unwrap().pairing, pairing);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::disconnected("Ks 7d 2c", 1, 0)]
+    #[case::one_gap("Ks Jd 2c", 2, 0)]
+    #[case::wheel("As 4d 2c", 3, 1)]
+    #[case::broadway("As Kd Qc", 3, 1)]
+    #[case::middle_connectors("9s 8d 7c", 3, 3)]
+    #[case::gapped("Ts 8d 6c", 3, 1)]
+    #[case::four_straight("Js Td 9c 8h", 4, 4)]
+    #[case::straight("6s 5d 4c 3h 2s", 5, 4)]
+    fn straight_counts(
+        #[case] board: &str,
+        #[case] connectedness: usize,
+        #[case] straights: usize,
+    ) -> Result<(), ParseError> {
+        let texture = board_texture(&board.parse()?).unwrap();
+        assert_eq!(texture.connectedness, connectedness);
+        assert_eq!(texture.straights, straights);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Ks 7d 2c", Rank::King)]
+    #[case("As 4d 2c", Rank::Ace)]
+    #[case("5s 4d 2c 3h", Rank::Five)]
+    fn high_cards(#[case] board: &str, #[case] high_card: Rank) -> Result<(), ParseError> {
+        assert_eq!(board_texture(&board.parse()?).unwrap().high_card, high_card);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::paired_monotone("Ks Kh 2s", TextureCategory::Paired)]
+    #[case::suited("Ks 9s 2s", TextureCategory::Suited)]
+    #[case::connected("9s 8d 7c", TextureCategory::Connected)]
+    #[case::two_tone("Ks 7s 2c", TextureCategory::Dynamic)]
+    #[case::gutshot_draw("Ks Jd 2c", TextureCategory::Dynamic)]
+    #[case::dry("Ks 7d 2c", TextureCategory::Dry)]
+    fn categories(
+        #[case] board: &str,
+        #[case] category: TextureCategory,
+    ) -> Result<(), ParseError> {
+        assert_eq!(board_texture(&board.parse()?).unwrap().category, category);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("Ks 7d")]
+    #[case("Ks 7d 2c 3h 9s 4d")]
+    fn invalid_boards(#[case] board: &str) -> Result<(), ParseError> {
+        let result = board_texture(&board.parse()?);
+        assert_eq!(result, Err(TextureError::InvalidBoard));
+        Ok(())
+    }
+}
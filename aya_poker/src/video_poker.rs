@@ -0,0 +1,391 @@
+//! Paytables and optimal-hold analysis for draw-based video poker games.
+//!
+//! # Examples
+//!
+//! ```
+//! use aya_poker::video_poker::{best_hold, Paytable};
+//!
+//! let dealt = "Ah Kh Qh Jh 9h".parse()?;
+//! let hold = best_hold(&Paytable::JACKS_OR_BETTER_9_6, &dealt);
+//! assert_eq!(hold.held, "Ah Kh Qh Jh".parse()?);
+//! # Ok::<(), aya_poker::base::ParseError>(())
+//! ```
+
+use aya_base::{
+    constants::{CARD_COUNT, RANK_OFFSET},
+    Hand, Rank, CARDS,
+};
+
+use crate::{for_each_subhand, poker_rank, PokerRankCategory};
+
+/// Number of distinct hand classes that can be assigned a payout.
+const HAND_CLASS_COUNT: usize = 14;
+
+/// A final video poker hand classified by the categories which paytables
+/// distinguish between.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub enum VideoPokerHand {
+    /// A hand which does not qualify for any payout.
+    Nothing = 0,
+    /// A pair of jacks, queens, kings or aces.
+    JacksOrBetter,
+    /// Two pairs.
+    TwoPair,
+    /// Three cards of the same rank.
+    ThreeOfAKind,
+    /// Five cards of sequential rank.
+    Straight,
+    /// Five cards of the same suit.
+    Flush,
+    /// Three cards of one rank and two cards of another rank.
+    FullHouse,
+    /// Four fives through four kings.
+    FourFivesThroughKings,
+    /// Four twos, threes or fours with a five through king kicker.
+    FourTwosThroughFours,
+    /// Four twos, threes or fours with an ace, two, three or four kicker.
+    FourTwosThroughFoursWithKicker,
+    /// Four aces with a five through king kicker.
+    FourAces,
+    /// Four aces with a two, three or four kicker.
+    FourAcesWithKicker,
+    /// Five cards of sequential rank, all of the same suit.
+    StraightFlush,
+    /// The sequence A-K-Q-J-T all of the same suit.
+    RoyalFlush,
+}
+
+impl VideoPokerHand {
+    /// Classifies a final 5-card hand according to the video poker hand
+    /// classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aya_poker::video_poker::VideoPokerHand;
+    ///
+    /// let hand = VideoPokerHand::new(&"Ac Ad Ah As 3c".parse()?);
+    /// assert_eq!(hand, VideoPokerHand::FourAcesWithKicker);
+    /// # Ok::<(), aya_poker::base::ParseError>(())
+    /// ```
+    pub fn new(hand: &Hand) -> VideoPokerHand {
+        let rank = poker_rank(hand);
+        let r = (rank.0 as usize % RANK_OFFSET) / 256;
+
+        match rank.rank_category() {
+            PokerRankCategory::Ineligible | PokerRankCategory::HighCard => VideoPokerHand::Nothing,
+            PokerRankCategory::Pair if r >= Rank::Jack as usize => VideoPokerHand::JacksOrBetter,
+            PokerRankCategory::Pair => VideoPokerHand::Nothing,
+            PokerRankCategory::TwoPair => VideoPokerHand::TwoPair,
+            PokerRankCategory::ThreeOfAKind => VideoPokerHand::ThreeOfAKind,
+            PokerRankCategory::Straight => VideoPokerHand::Straight,
+            PokerRankCategory::Flush => VideoPokerHand::Flush,
+            PokerRankCategory::FullHouse => VideoPokerHand::FullHouse,
+            // Only wild card ranks make five of a kind, which is classified
+            // by the four of a kind it contains.
+            PokerRankCategory::FourOfAKind | PokerRankCategory::FiveOfAKind => {
+                // Bonus paytables reward four aces with a 2-4 kicker, and four
+                // 2-4s with an A-4 kicker.
+                let kicker_mask = hand.rank_mask() & !(1 << r);
+                let low_kicker = kicker_mask != 0 && kicker_mask & !0x1007 == 0;
+
+                if r == Rank::Ace as usize {
+                    if low_kicker {
+                        VideoPokerHand::FourAcesWithKicker
+                    } else {
+                        VideoPokerHand::FourAces
+                    }
+                } else if r <= Rank::Four as usize {
+                    if low_kicker {
+                        VideoPokerHand::FourTwosThroughFoursWithKicker
+                    } else {
+                        VideoPokerHand::FourTwosThroughFours
+                    }
+                } else {
+                    VideoPokerHand::FourFivesThroughKings
+                }
+            }
+            PokerRankCategory::StraightFlush => VideoPokerHand::StraightFlush,
+            PokerRankCategory::RoyalFlush => VideoPokerHand::RoyalFlush,
+        }
+    }
+}
+
+impl core::fmt::Display for VideoPokerHand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VideoPokerHand::Nothing => write!(f, "Nothing"),
+            VideoPokerHand::JacksOrBetter => write!(f, "Jacks or Better"),
+            VideoPokerHand::TwoPair => write!(f, "Two Pair"),
+            VideoPokerHand::ThreeOfAKind => write!(f, "Three of a Kind"),
+            VideoPokerHand::Straight => write!(f, "Straight"),
+            VideoPokerHand::Flush => write!(f, "Flush"),
+            VideoPokerHand::FullHouse => write!(f, "Full House"),
+            VideoPokerHand::FourFivesThroughKings => write!(f, "Four 5s-Ks"),
+            VideoPokerHand::FourTwosThroughFours => write!(f, "Four 2s-4s"),
+            VideoPokerHand::FourTwosThroughFoursWithKicker => {
+                write!(f, "Four 2s-4s with A-4 Kicker")
+            }
+            VideoPokerHand::FourAces => write!(f, "Four Aces"),
+            VideoPokerHand::FourAcesWithKicker => write!(f, "Four Aces with 2-4 Kicker"),
+            VideoPokerHand::StraightFlush => write!(f, "Straight Flush"),
+            VideoPokerHand::RoyalFlush => write!(f, "Royal Flush"),
+        }
+    }
+}
+
+/// The number of credits paid out per credit wagered for each final hand.
+///
+/// The royal flush payouts of the provided paytables are the ones for a
+/// maximum coin bet, i.e. 4000 credits for 5 credits wagered.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::video_poker::{Paytable, VideoPokerHand};
+///
+/// // Jacks or Better with a reduced full house payout
+/// let paytable = Paytable::JACKS_OR_BETTER_9_6.with_payout(VideoPokerHand::FullHouse, 8);
+/// assert_eq!(paytable.payout(&"Kh Kc Ks 4d 4c".parse()?), 8);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct Paytable {
+    payouts: [u32; HAND_CLASS_COUNT],
+}
+
+impl Paytable {
+    /// Full-pay Jacks or Better, with a 99.54% return for optimal play.
+    pub const JACKS_OR_BETTER_9_6: Paytable = Paytable::new()
+        .with_payout(VideoPokerHand::JacksOrBetter, 1)
+        .with_payout(VideoPokerHand::TwoPair, 2)
+        .with_payout(VideoPokerHand::ThreeOfAKind, 3)
+        .with_payout(VideoPokerHand::Straight, 4)
+        .with_payout(VideoPokerHand::Flush, 6)
+        .with_payout(VideoPokerHand::FullHouse, 9)
+        .with_payout(VideoPokerHand::FourFivesThroughKings, 25)
+        .with_payout(VideoPokerHand::FourTwosThroughFours, 25)
+        .with_payout(VideoPokerHand::FourTwosThroughFoursWithKicker, 25)
+        .with_payout(VideoPokerHand::FourAces, 25)
+        .with_payout(VideoPokerHand::FourAcesWithKicker, 25)
+        .with_payout(VideoPokerHand::StraightFlush, 50)
+        .with_payout(VideoPokerHand::RoyalFlush, 800);
+
+    /// Full-pay Bonus Poker, with a 99.17% return for optimal play.
+    pub const BONUS_POKER_8_5: Paytable = Paytable::JACKS_OR_BETTER_9_6
+        .with_payout(VideoPokerHand::Flush, 5)
+        .with_payout(VideoPokerHand::FullHouse, 8)
+        .with_payout(VideoPokerHand::FourTwosThroughFours, 40)
+        .with_payout(VideoPokerHand::FourTwosThroughFoursWithKicker, 40)
+        .with_payout(VideoPokerHand::FourAces, 80)
+        .with_payout(VideoPokerHand::FourAcesWithKicker, 80);
+
+    /// Full-pay Double Bonus Poker, with a 100.17% return for optimal play.
+    pub const DOUBLE_BONUS_10_7: Paytable = Paytable::JACKS_OR_BETTER_9_6
+        .with_payout(VideoPokerHand::TwoPair, 1)
+        .with_payout(VideoPokerHand::Straight, 5)
+        .with_payout(VideoPokerHand::Flush, 7)
+        .with_payout(VideoPokerHand::FullHouse, 10)
+        .with_payout(VideoPokerHand::FourFivesThroughKings, 50)
+        .with_payout(VideoPokerHand::FourTwosThroughFours, 80)
+        .with_payout(VideoPokerHand::FourTwosThroughFoursWithKicker, 80)
+        .with_payout(VideoPokerHand::FourAces, 160)
+        .with_payout(VideoPokerHand::FourAcesWithKicker, 160);
+
+    /// Full-pay Double Double Bonus Poker, with a 98.98% return for optimal
+    /// play.
+    pub const DOUBLE_DOUBLE_BONUS_9_6: Paytable = Paytable::JACKS_OR_BETTER_9_6
+        .with_payout(VideoPokerHand::TwoPair, 1)
+        .with_payout(VideoPokerHand::FourFivesThroughKings, 50)
+        .with_payout(VideoPokerHand::FourTwosThroughFours, 80)
+        .with_payout(VideoPokerHand::FourTwosThroughFoursWithKicker, 160)
+        .with_payout(VideoPokerHand::FourAces, 160)
+        .with_payout(VideoPokerHand::FourAcesWithKicker, 400);
+
+    /// Constructs a paytable that does not pay out for any hand.
+    pub const fn new() -> Paytable {
+        Paytable {
+            payouts: [0; HAND_CLASS_COUNT],
+        }
+    }
+
+    /// Returns the paytable with the payout for the given hand class replaced.
+    pub const fn with_payout(mut self, hand: VideoPokerHand, payout: u32) -> Paytable {
+        self.payouts[hand as usize] = payout;
+        self
+    }
+
+    /// Returns the payout for the given hand class.
+    pub fn payout_for(&self, hand: VideoPokerHand) -> u32 {
+        self.payouts[hand as usize]
+    }
+
+    /// Returns the payout for the given final 5-card hand.
+    pub fn payout(&self, hand: &Hand) -> u32 {
+        self.payout_for(VideoPokerHand::new(hand))
+    }
+}
+
+impl Default for Paytable {
+    fn default() -> Paytable {
+        Paytable::new()
+    }
+}
+
+/// The expected payout of holding some of the cards of a dealt hand and
+/// replacing the rest.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct HoldValue {
+    /// The cards kept from the dealt hand.
+    pub held: Hand,
+    /// The expected payout per credit wagered over all possible draws.
+    pub expected_value: f64,
+}
+
+/// Returns the expected value of each of the 32 ways to hold cards from the
+/// dealt 5-card hand.
+///
+/// The `i`-th entry corresponds to holding the cards whose positions in
+/// `dealt.iter()` are the set bits of `i`, i.e. the first entry discards the
+/// whole hand and the last entry holds all five cards. The replacement cards
+/// are drawn from the 47 cards which were not dealt, and every possible draw
+/// is enumerated exactly.
+///
+/// # Panics
+///
+/// Panics if `dealt` does not contain exactly 5 cards.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::video_poker::{hold_values, Paytable};
+///
+/// let dealt = "Ah Kh Qh Jh 9h".parse()?;
+/// let values = hold_values(&Paytable::JACKS_OR_BETTER_9_6, &dealt);
+/// assert_eq!(values[31].expected_value, 6.0);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn hold_values(paytable: &Paytable, dealt: &Hand) -> [HoldValue; 32] {
+    assert_eq!(dealt.len(), 5, "dealt hand must contain exactly 5 cards");
+
+    let mut dealt_cards = [CARDS[0]; 5];
+    for (i, &card) in dealt.iter().enumerate() {
+        dealt_cards[i] = card;
+    }
+
+    let mut deck = [CARDS[0]; CARD_COUNT - 5];
+    for (i, &card) in CARDS.iter().filter(|c| !dealt.contains(c)).enumerate() {
+        deck[i] = card;
+    }
+
+    let mut values = [HoldValue {
+        held: Hand::new(),
+        expected_value: 0.0,
+    }; 32];
+    for (hold, value) in values.iter_mut().enumerate() {
+        let held = (0..5)
+            .filter(|i| hold & (1 << i) != 0)
+            .map(|i| dealt_cards[i])
+            .collect::<Hand>();
+        let draws = 5 - held.len();
+
+        let (mut total, mut count) = (0u64, 0u64);
+        for_each_subhand(&deck, draws, held, &mut |hand| {
+            total += paytable.payout(&hand) as u64;
+            count += 1;
+        });
+        value.held = held;
+        value.expected_value = total as f64 / count as f64;
+    }
+
+    values
+}
+
+/// Returns the hold with the highest expected value for the dealt 5-card
+/// hand.
+///
+/// If several holds have the same expected value, the one holding the most
+/// cards is returned.
+///
+/// # Panics
+///
+/// Panics if `dealt` does not contain exactly 5 cards.
+pub fn best_hold(paytable: &Paytable, dealt: &Hand) -> HoldValue {
+    let values = hold_values(paytable, dealt);
+
+    let mut best = values[0];
+    for value in values {
+        if value.expected_value > best.expected_value
+            || (value.expected_value == best.expected_value && value.held.len() > best.held.len())
+        {
+            best = value;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::ParseError;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::nothing("Ah Kd 9s 5c 2c", VideoPokerHand::Nothing)]
+    #[case::low_pair("Th Td 9s 5c 2c", VideoPokerHand::Nothing)]
+    #[case::jacks("Jh Jd 9s 5c 2c", VideoPokerHand::JacksOrBetter)]
+    #[case::two_pair("3h 3d 9s 9c 2c", VideoPokerHand::TwoPair)]
+    #[case::wheel("Ah 2d 3s 4c 5c", VideoPokerHand::Straight)]
+    #[case::four_kings("Kh Kd Ks Kc 2c", VideoPokerHand::FourFivesThroughKings)]
+    #[case::four_threes("3h 3d 3s 3c 5c", VideoPokerHand::FourTwosThroughFours)]
+    #[case::four_threes_ace("3h 3d 3s 3c Ac", VideoPokerHand::FourTwosThroughFoursWithKicker)]
+    #[case::four_twos_four("2h 2d 2s 2c 4c", VideoPokerHand::FourTwosThroughFoursWithKicker)]
+    #[case::four_aces("Ah Ad As Ac 5c", VideoPokerHand::FourAces)]
+    #[case::four_aces_deuce("Ah Ad As Ac 2c", VideoPokerHand::FourAcesWithKicker)]
+    #[case::royal_flush("Ah Kh Qh Jh Th", VideoPokerHand::RoyalFlush)]
+    fn classify(#[case] cards: &str, #[case] expected: VideoPokerHand) -> Result<(), ParseError> {
+        let hand = cards.parse()?;
+        assert_eq!(VideoPokerHand::new(&hand), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Ah Ad As Ac 2c", [25, 80, 160, 400])]
+    #[case("4h 4d 4s 4c Ac", [25, 40, 80, 160])]
+    #[case("9h 9d 9s 9c Ac", [25, 25, 50, 50])]
+    #[case("9h 9d 6s 6c Ac", [2, 2, 1, 1])]
+    #[case("Kh Jh 9h 6h 3h", [6, 5, 7, 6])]
+    fn payouts(#[case] cards: &str, #[case] expected: [u32; 4]) -> Result<(), ParseError> {
+        let hand = cards.parse()?;
+        let paytables = [
+            Paytable::JACKS_OR_BETTER_9_6,
+            Paytable::BONUS_POKER_8_5,
+            Paytable::DOUBLE_BONUS_10_7,
+            Paytable::DOUBLE_DOUBLE_BONUS_9_6,
+        ];
+        assert_eq!(paytables.map(|p| p.payout(&hand)), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn four_to_a_royal() -> Result<(), ParseError> {
+        let dealt = "Ah Kh Qh Jh 9h".parse()?;
+        let values = hold_values(&Paytable::JACKS_OR_BETTER_9_6, &dealt);
+
+        // Holding all five cards locks up the flush
+        assert_eq!(values[31].held, dealt);
+        assert_eq!(values[31].expected_value, 6.0);
+
+        // Out of the 47 remaining cards, one makes a royal flush, seven a
+        // flush, three a straight and twelve a high pair.
+        let royal_draw = values
+            .iter()
+            .find(|v| v.held == "Ah Kh Qh Jh".parse().unwrap());
+        let expected_value = (800.0 + 7.0 * 6.0 + 3.0 * 4.0 + 12.0) / 47.0;
+        assert_eq!(royal_draw.unwrap().expected_value, expected_value);
+
+        let best = best_hold(&Paytable::JACKS_OR_BETTER_9_6, &dealt);
+        assert_eq!(best.expected_value, expected_value);
+        Ok(())
+    }
+}
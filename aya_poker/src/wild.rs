@@ -0,0 +1,476 @@
+use core::fmt;
+use core::str::FromStr;
+
+use aya_base::{
+    constants::{MAX_HAND_SIZE, RANK_COUNT, RANK_OFFSET},
+    Card, Hand, ParseError, Rank, Suit, CARDS,
+};
+
+use crate::{poker_rank, PokerHandRank};
+
+/// The numeric value of the worst five of a kind, i.e. five deuces.
+const WORST_FIVE_OF_A_KIND: u16 = 10 * RANK_OFFSET as u16;
+
+/// Rank masks of all straights ordered from the ace-high straight down to
+/// the wheel.
+const STRAIGHTS: [u16; 10] = [
+    0x1f00, 0x0f80, 0x07c0, 0x03e0, 0x01f0, 0x00f8, 0x007c, 0x003e, 0x001f, 0x100f,
+];
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+/// A hand of 0-7 cards from a standard 52-card deck with any number of the
+/// cards being jokers.
+///
+/// When parsing or displaying a [`JokerHand`], jokers are written as `Jk`.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::JokerHand;
+///
+/// let hand: JokerHand = "Ah Kh Jk Th Jk".parse()?;
+/// assert_eq!(hand.jokers(), 2);
+/// assert_eq!(hand.cards().len(), 3);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct JokerHand {
+    cards: Hand,
+    jokers: u8,
+}
+
+impl JokerHand {
+    /// Constructs a hand from the given cards and number of jokers.
+    ///
+    /// The caller is responsible for verifying that the hand does not contain
+    /// more than 7 cards in total.
+    pub fn new(cards: Hand, jokers: u8) -> JokerHand {
+        JokerHand { cards, jokers }
+    }
+
+    /// Returns the cards in the hand which are not jokers.
+    pub fn cards(&self) -> Hand {
+        self.cards
+    }
+
+    /// Returns the number of jokers in the hand.
+    pub fn jokers(&self) -> u8 {
+        self.jokers
+    }
+
+    /// Returns the total number of cards in the hand, including jokers.
+    pub fn len(&self) -> usize {
+        self.cards.len() + self.jokers as usize
+    }
+
+    /// Returns `true` if the hand does not contain any cards or jokers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl FromStr for JokerHand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hand = JokerHand::default();
+        if s.trim() == "" {
+            return Ok(hand);
+        }
+
+        for token in s.trim().split(' ') {
+            if token == "Jk" {
+                hand.jokers += 1;
+            } else {
+                let card = token.parse::<Card>()?;
+                if hand.cards.contains(&card) {
+                    return Err(ParseError);
+                }
+                hand.cards.insert_unchecked(&card);
+            }
+
+            if hand.len() > MAX_HAND_SIZE {
+                return Err(ParseError);
+            }
+        }
+
+        Ok(hand)
+    }
+}
+
+impl fmt::Debug for JokerHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.cards)?;
+        for i in 0..self.jokers() {
+            if i == 0 && self.cards.is_empty() {
+                write!(f, "Jk")?;
+            } else {
+                write!(f, " Jk")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for JokerHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.cards)?;
+        for i in 0..self.jokers() {
+            if i == 0 && self.cards.is_empty() {
+                write!(f, "🃏")?;
+            } else {
+                write!(f, " 🃏")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the rank of the best standard 5-card poker hand that can be made
+/// from the given cards, with the jokers being fully wild.
+///
+/// Each joker can stand in for any card, including a card already present
+/// in the hand, so hands with jokers can rank as
+/// [`FiveOfAKind`](crate::PokerRankCategory::FiveOfAKind), the best possible
+/// hand. As in [`poker_rank`], any missing cards are considered to be the
+/// worst possible kickers for the made hand.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{joker_rank, PokerRankCategory};
+///
+/// let hand = "Qs Qd Jk 5c Qh".parse()?;
+/// let rank = joker_rank(&hand);
+/// assert_eq!(rank.rank_category(), PokerRankCategory::FourOfAKind);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn joker_rank(hand: &JokerHand) -> PokerHandRank {
+    wild_rank(&hand.cards, hand.jokers as usize)
+}
+
+/// Returns the rank of the best standard 5-card poker hand that can be made
+/// from the given cards, with the jokers playing as the "bug".
+///
+/// Under the bug rule, a joker can only be used to complete a straight, a
+/// flush or a straight flush, and otherwise counts as an ace. Four aces
+/// together with a joker rank as five aces, the best possible hand.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{bug_rank, PokerRankCategory};
+///
+/// let pair = bug_rank(&"Qs Qd Jk 5c 8h".parse()?);
+/// assert_eq!(pair.rank_category(), PokerRankCategory::Pair);
+/// let straight = bug_rank(&"Qs Jd Jk 9c 8h".parse()?);
+/// assert_eq!(straight.rank_category(), PokerRankCategory::Straight);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn bug_rank(hand: &JokerHand) -> PokerHandRank {
+    let cards = &hand.cards;
+    let jokers = hand.jokers as usize;
+    if jokers == 0 {
+        return poker_rank(cards);
+    }
+
+    let ace = Rank::Ace as usize;
+    let counts = rank_counts(cards);
+    if counts[ace] as usize + jokers >= 5 {
+        return five_of_a_kind(ace);
+    }
+
+    let mut aces = *cards;
+    for _ in 0..jokers {
+        let card = absent_card(&aces, ace);
+        aces.insert_unchecked(&card);
+    }
+
+    let suit_masks = SUITS.map(|s| cards.suit_mask(s));
+    poker_rank(&aces)
+        .max(straight_rank(cards.rank_mask(), jokers))
+        .max(straight_flush_rank(&suit_masks, jokers))
+        .max(flush_rank(&suit_masks, jokers))
+}
+
+/// Returns the rank of the best standard 5-card poker hand that can be made
+/// from the given cards, with all deuces being wild.
+///
+/// See [`joker_rank`] for the rules of evaluating hands with wild cards.
+///
+/// # Examples
+///
+/// ```
+/// use aya_poker::{deuces_wild_rank, PokerRankCategory};
+///
+/// let hand = "2c 2h 7s 7d 7c".parse()?;
+/// let rank = deuces_wild_rank(&hand);
+/// assert_eq!(rank.rank_category(), PokerRankCategory::FiveOfAKind);
+/// # Ok::<(), aya_poker::base::ParseError>(())
+/// ```
+pub fn deuces_wild_rank(hand: &Hand) -> PokerHandRank {
+    let cards = hand
+        .iter()
+        .filter(|c| c.rank() != Rank::Two)
+        .collect::<Hand>();
+    wild_rank(&cards, hand.len() - cards.len())
+}
+
+fn wild_rank(cards: &Hand, jokers: usize) -> PokerHandRank {
+    if jokers == 0 {
+        return poker_rank(cards);
+    }
+
+    let counts = rank_counts(cards);
+    if let Some(r) = (0..RANK_COUNT)
+        .rev()
+        .find(|&r| counts[r] as usize + jokers >= 5)
+    {
+        return five_of_a_kind(r);
+    }
+
+    let suit_masks = SUITS.map(|s| cards.suit_mask(s));
+    let straight_flush = straight_flush_rank(&suit_masks, jokers);
+    if straight_flush != PokerHandRank(0) {
+        return straight_flush;
+    }
+
+    best_substitution(*cards, jokers, 0).max(flush_rank(&suit_masks, jokers))
+}
+
+/// Returns the best rank that can be made by substituting the jokers with
+/// cards of rank at least `min_rank`.
+///
+/// The caller is responsible for verifying that no rank would need to be
+/// present in the hand five times.
+fn best_substitution(cards: Hand, jokers: usize, min_rank: usize) -> PokerHandRank {
+    if jokers == 0 {
+        return poker_rank(&cards);
+    }
+
+    (min_rank..RANK_COUNT)
+        .map(|r| {
+            let mut hand = cards;
+            hand.insert_unchecked(&absent_card(&cards, r));
+            best_substitution(hand, jokers - 1, r)
+        })
+        .max()
+        .unwrap()
+}
+
+fn straight_rank(rank_mask: u16, jokers: usize) -> PokerHandRank {
+    match best_straight(rank_mask, jokers) {
+        Some(straight) => {
+            let hand = (0..RANK_COUNT)
+                .filter(|&r| straight & (1 << r) != 0)
+                .enumerate()
+                .map(|(i, r)| CARDS[4 * r + i % 2])
+                .collect();
+            poker_rank(&hand)
+        }
+        None => PokerHandRank(0),
+    }
+}
+
+fn straight_flush_rank(suit_masks: &[u16; 4], jokers: usize) -> PokerHandRank {
+    let mut best = PokerHandRank(0);
+    for (s, &mask) in suit_masks.iter().enumerate() {
+        if let Some(straight) = best_straight(mask, jokers) {
+            best = best.max(poker_rank(&suited_hand(straight, s)));
+        }
+    }
+    best
+}
+
+fn flush_rank(suit_masks: &[u16; 4], jokers: usize) -> PokerHandRank {
+    let mut best = PokerHandRank(0);
+    for (s, &mask) in suit_masks.iter().enumerate() {
+        if mask.count_ones() as usize + jokers < 5 {
+            continue;
+        }
+
+        // The jokers are best used as the highest cards missing from the suit.
+        let mut flush = mask;
+        let mut remaining = jokers;
+        for r in (0..RANK_COUNT).rev() {
+            if remaining > 0 && flush & (1 << r) == 0 {
+                flush |= 1 << r;
+                remaining -= 1;
+            }
+        }
+
+        best = best.max(poker_rank(&suited_hand(flush, s)));
+    }
+    best
+}
+
+/// Returns the rank mask of the best straight that can be completed by
+/// adding at most `jokers` ranks to the given rank mask.
+fn best_straight(rank_mask: u16, jokers: usize) -> Option<u16> {
+    STRAIGHTS
+        .iter()
+        .copied()
+        .find(|&straight| 5 - (rank_mask & straight).count_ones() as usize <= jokers)
+}
+
+fn suited_hand(rank_mask: u16, suit: usize) -> Hand {
+    (0..RANK_COUNT)
+        .filter(|&r| rank_mask & (1 << r) != 0)
+        .map(|r| CARDS[4 * r + suit])
+        .collect()
+}
+
+fn absent_card(hand: &Hand, rank: usize) -> Card {
+    CARDS[4 * rank..4 * rank + 4]
+        .iter()
+        .copied()
+        .find(|c| !hand.contains(c))
+        .unwrap()
+}
+
+fn rank_counts(hand: &Hand) -> [u8; RANK_COUNT] {
+    let mut counts = [0; RANK_COUNT];
+    for suit in SUITS {
+        let mask = hand.suit_mask(suit);
+        for (r, count) in counts.iter_mut().enumerate() {
+            *count += ((mask >> r) & 1) as u8;
+        }
+    }
+    counts
+}
+
+fn five_of_a_kind(rank: usize) -> PokerHandRank {
+    PokerHandRank(WORST_FIVE_OF_A_KIND + 256 * rank as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PokerRankCategory;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::no_jokers("Ah Kd", "Kd Ah")]
+    #[case::only_jokers("Jk Jk", "Jk Jk")]
+    #[case::mixed("Jk 7c Jk 8d", "7c 8d Jk Jk")]
+    fn parse(#[case] s: &str, #[case] expected: &str) -> Result<(), ParseError> {
+        let hand = s.parse::<JokerHand>()?;
+        assert_eq!(format!("{:?}", hand), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::duplicate_card("Ah Jk Ah")]
+    #[case::eight_cards("Jk Jk Jk Jk Jk Jk Jk Jk")]
+    #[case::invalid_card("Ah Jx")]
+    fn failed_parse(#[case] s: &str) {
+        assert!(s.parse::<JokerHand>().is_err());
+    }
+
+    #[rstest]
+    #[case::high_card("Jk", PokerRankCategory::HighCard)]
+    #[case::pair("Kh 9c 7d 4s Jk", PokerRankCategory::Pair)]
+    #[case::three_of_a_kind("Kh Kc 7d 4s Jk", PokerRankCategory::ThreeOfAKind)]
+    #[case::straight("Kh Qc Td 9s Jk", PokerRankCategory::Straight)]
+    #[case::flush("Kh 2h 7h 4h Jk", PokerRankCategory::Flush)]
+    #[case::full_house("Kh Kc 7d 7s Jk", PokerRankCategory::FullHouse)]
+    #[case::four_of_a_kind("Kh Kc Jk 4s Jk", PokerRankCategory::FourOfAKind)]
+    #[case::straight_flush("9h 8h Jk 6h 3c Jk", PokerRankCategory::StraightFlush)]
+    #[case::royal_flush("Ah Jk Jk Th Qh", PokerRankCategory::RoyalFlush)]
+    #[case::five_of_a_kind("7h 7c 7d Jk Jk", PokerRankCategory::FiveOfAKind)]
+    #[case::five_jokers("Jk Jk Jk Jk Jk", PokerRankCategory::FiveOfAKind)]
+    fn joker_rank_category(
+        #[case] cards: &str,
+        #[case] expected_category: PokerRankCategory,
+    ) -> Result<(), ParseError> {
+        let hand = cards.parse()?;
+        assert_eq!(joker_rank(&hand).rank_category(), expected_category);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::ace("Kh 9c 7d 4s Jk", PokerRankCategory::HighCard)]
+    #[case::pair_of_aces("Ah 9c 7d 4s Jk", PokerRankCategory::Pair)]
+    #[case::not_wild_pair("Kh Kc 7d 4s Jk", PokerRankCategory::Pair)]
+    #[case::aces_full("Ah Ac 7d 7s Jk", PokerRankCategory::FullHouse)]
+    #[case::straight("Kh Qc Td 9s Jk", PokerRankCategory::Straight)]
+    #[case::wheel("Jk 2c 3d 4s 5h", PokerRankCategory::Straight)]
+    #[case::flush("Kh 2h 7h 4h Jk", PokerRankCategory::Flush)]
+    #[case::straight_flush("9h 8h Jk 6h 5h", PokerRankCategory::StraightFlush)]
+    #[case::five_aces("Ah Ac Ad As Jk", PokerRankCategory::FiveOfAKind)]
+    fn bug_rank_category(
+        #[case] cards: &str,
+        #[case] expected_category: PokerRankCategory,
+    ) -> Result<(), ParseError> {
+        let hand = cards.parse()?;
+        assert_eq!(bug_rank(&hand).rank_category(), expected_category);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Ah Jk Kh", "Ah Ac Kh")]
+    #[case("Kh 9c 7d 4s Jk", "Kh 9c 7d 4s Ac")]
+    #[case("Kh Qc Td 9s Jk", "Kh Qc Td 9s Jc")]
+    #[case("Kh 2h 7h 4h Jk", "Kh 2h 7h 4h Ah")]
+    #[case("Jk Jk 9h 9c", "Ad Ac 9h 9c")]
+    fn bug_equal_rank(#[case] joker_hand: &str, #[case] hand: &str) -> Result<(), ParseError> {
+        let joker_hand = joker_hand.parse()?;
+        let hand = hand.parse()?;
+        assert_eq!(bug_rank(&joker_hand), poker_rank(&hand));
+        Ok(())
+    }
+
+    #[test]
+    fn joker_rank_ordering() -> Result<(), ParseError> {
+        let hands = [
+            "Jk",                   // A 5 4 3 2
+            "Jk 3c",                // 3 3 4 5 2
+            "Kc Jk 8d 5h 4s",       // K K 8 5 4
+            "Ac Ad Jk 9c 7h",       // A A A 9 7
+            "Ac Jk Jk 9c 7h",       // A A A 9 7
+            "2c 3h Jk 5d 6s",       // 6 5 4 3 2
+            "Td Jh Qs Jk Jk",       // A K Q J T
+            "2h Jk 9h 7h 4h",       // A 9 7 4 2
+            "Jk Jk Ac Kc Kd",       // K K K K A
+            "Jk Jk Jk Jk",          // A A A A 2
+            "3h 4h 5h Jk Jk",       // 7 6 5 4 3
+            "Jk Jh Jk Kh Qh 9h 2c", // A K Q J T
+            "2c 2d 2h Jk Jk Jk",    // 2 2 2 2 2
+            "8c 8d 8h 8s Jk",       // 8 8 8 8 8
+            "Ac Ad Jk Jk Jk",       // A A A A A
+        ]
+        .map(|s| s.parse::<JokerHand>());
+
+        for (&h1, &h2) in hands.iter().zip(hands.iter().skip(1)) {
+            let r1 = joker_rank(&h1?);
+            let r2 = joker_rank(&h2?);
+
+            assert!(
+                r1 <= r2,
+                "{:?} is ranked {:?}, which is larger than {:?} ({:?}).",
+                &h1,
+                r1,
+                &h2,
+                r2
+            );
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::natural("7h 8h 9h Th Jh", PokerRankCategory::StraightFlush)]
+    #[case::wild_royal("2c Kd Qd Jd Td", PokerRankCategory::RoyalFlush)]
+    #[case::four_deuces("2c 2d 2h 2s 3d", PokerRankCategory::FiveOfAKind)]
+    #[case::three_of_a_kind("2c 9d 6h Jd 9s", PokerRankCategory::ThreeOfAKind)]
+    #[case::straight("2c 2h 3s 6d 7c", PokerRankCategory::Straight)]
+    fn deuces_wild_category(
+        #[case] cards: &str,
+        #[case] expected_category: PokerRankCategory,
+    ) -> Result<(), ParseError> {
+        let hand = cards.parse()?;
+        assert_eq!(deuces_wild_rank(&hand).rank_category(), expected_category);
+        Ok(())
+    }
+}
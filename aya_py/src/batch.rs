@@ -0,0 +1,263 @@
+//! Vectorized evaluation of NumPy arrays of hands.
+//!
+//! Each row of a `uint8` array of shape `(n, k)` holds the indices
+//! `4 * rank + suit` of the cards of one hand, with unused slots set to
+//! `NO_CARD` (255), so that hands of up to 7 cards can be stored in the same
+//! array. Each function returns a `uint16` array of the `n` ranks, computed
+//! without holding the GIL.
+
+use aya_poker::base::{Hand, CARDS};
+use numpy::{
+    ndarray::{ArrayView1, ArrayView2},
+    IntoPyArray, PyArray1, PyReadonlyArray2,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+/// The value marking an unused slot in a row of card indices.
+pub const NO_CARD: u8 = 255;
+
+/// Converts a row of card indices into a hand, returning `None` if it holds
+/// more than 7 cards, an invalid index or a card twice.
+fn row_hand(row: ArrayView1<'_, u8>) -> Option<Hand> {
+    let mut hand = Hand::new();
+    for &idx in row.iter().filter(|&&idx| idx != NO_CARD) {
+        let card = CARDS.get(idx as usize)?;
+        if hand.contains(card) || hand.len() == 7 {
+            return None;
+        }
+        hand.insert_unchecked(card);
+    }
+    Some(hand)
+}
+
+/// Ranks each row of the array, returning the index of the first invalid row
+/// as the error.
+fn rank_rows(cards: ArrayView2<'_, u8>, rank: impl Fn(&Hand) -> u16) -> Result<Vec<u16>, usize> {
+    cards
+        .rows()
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| row_hand(row).map(|hand| rank(&hand)).ok_or(i))
+        .collect()
+}
+
+/// Ranks each pair of rows of the hole and board arrays, which must hold at
+/// least two hole and three board cards that do not overlap, returning the
+/// index of the first invalid pair as the error.
+fn rank_row_pairs(
+    hole: ArrayView2<'_, u8>,
+    board: ArrayView2<'_, u8>,
+    rank: impl Fn(&Hand, &Hand) -> u16,
+) -> Result<Vec<u16>, usize> {
+    hole.rows()
+        .into_iter()
+        .zip(board.rows())
+        .enumerate()
+        .map(|(i, (hole, board))| {
+            let (hole, board) = row_hand(hole).zip(row_hand(board)).ok_or(i)?;
+            match hole.len() >= 2 && board.len() >= 3 && hole.is_disjoint(&board) {
+                true => Ok(rank(&hole, &board)),
+                false => Err(i),
+            }
+        })
+        .collect()
+}
+
+fn into_ranks<'py>(
+    py: Python<'py>,
+    ranks: Result<Vec<u16>, usize>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    match ranks {
+        Ok(ranks) => Ok(ranks.into_pyarray(py)),
+        Err(row) => Err(PyValueError::new_err(format!(
+            "invalid hand in row {}",
+            row
+        ))),
+    }
+}
+
+fn batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+    rank: fn(&Hand) -> u16,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    let cards = cards.as_array();
+    let ranks = py.detach(|| rank_rows(cards, rank));
+    into_ranks(py, ranks)
+}
+
+fn pair_batch<'py>(
+    py: Python<'py>,
+    hole: PyReadonlyArray2<'py, u8>,
+    board: PyReadonlyArray2<'py, u8>,
+    rank: fn(&Hand, &Hand) -> u16,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    let (hole, board) = (hole.as_array(), board.as_array());
+    if hole.nrows() != board.nrows() {
+        return Err(PyValueError::new_err(
+            "hole and board arrays must have the same number of rows",
+        ));
+    }
+    let ranks = py.detach(|| rank_row_pairs(hole, board, rank));
+    into_ranks(py, ranks)
+}
+
+/// Returns the `poker_rank` of each row of card indices.
+#[pyfunction]
+fn poker_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::poker_rank(hand).0)
+}
+
+/// Returns the `short_deck_rank` of each row of card indices.
+#[pyfunction]
+fn short_deck_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::short_deck_rank(hand).0)
+}
+
+/// Returns the `ace_five_rank` of each row of card indices.
+#[pyfunction]
+fn ace_five_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::ace_five_rank(hand).0)
+}
+
+/// Returns the `deuce_seven_rank` of each row of card indices.
+#[pyfunction]
+fn deuce_seven_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::deuce_seven_rank(hand).0)
+}
+
+/// Returns the `badugi_rank` of each row of card indices.
+#[pyfunction]
+fn badugi_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::badugi_rank(hand).0)
+}
+
+/// Returns the `baduci_rank` of each row of card indices.
+#[pyfunction]
+fn baduci_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::baduci_rank(hand).0)
+}
+
+/// Returns the `deuces_wild_rank` of each row of card indices.
+#[pyfunction]
+fn deuces_wild_rank_batch<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    batch(py, cards, |hand| aya_poker::deuces_wild_rank(hand).0)
+}
+
+/// Returns the `omaha_rank` of each pair of rows of hole and board card
+/// indices.
+#[pyfunction]
+fn omaha_rank_batch<'py>(
+    py: Python<'py>,
+    hole: PyReadonlyArray2<'py, u8>,
+    board: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    pair_batch(py, hole, board, |hole, board| {
+        aya_poker::omaha_rank(hole, board).0
+    })
+}
+
+/// Returns the `omaha_lo_rank` of each pair of rows of hole and board card
+/// indices.
+#[pyfunction]
+fn omaha_lo_rank_batch<'py>(
+    py: Python<'py>,
+    hole: PyReadonlyArray2<'py, u8>,
+    board: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u16>>> {
+    pair_batch(py, hole, board, |hole, board| {
+        aya_poker::omaha_lo_rank(hole, board).0
+    })
+}
+
+pub(crate) fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("NO_CARD", NO_CARD)?;
+    module.add_function(wrap_pyfunction!(poker_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(short_deck_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(ace_five_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(deuce_seven_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(badugi_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(baduci_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(deuces_wild_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(omaha_rank_batch, module)?)?;
+    module.add_function(wrap_pyfunction!(omaha_lo_rank_batch, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numpy::ndarray::array;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[51, 50, NO_CARD], Some(2))]
+    #[case(&[NO_CARD, NO_CARD, NO_CARD], Some(0))]
+    #[case(&[51, 51, NO_CARD], None)]
+    #[case(&[51, 52, NO_CARD], None)]
+    fn converts_rows(#[case] row: &[u8], #[case] len: Option<usize>) {
+        let row = ArrayView1::from(row);
+        assert_eq!(row_hand(row).map(|hand| hand.len()), len);
+    }
+
+    #[test]
+    fn rejects_rows_of_more_than_seven_cards() {
+        let row: Vec<u8> = (0..8).collect();
+        assert_eq!(row_hand(ArrayView1::from(&row[..])), None);
+    }
+
+    #[test]
+    fn ranks_rows() {
+        let royal_flush: Hand = "Ah Kh Qh Jh Th".parse().unwrap();
+        let pair: Hand = "Ks Kd 6s 4c".parse().unwrap();
+        let cards = array![[50, 46, 42, 38, 34], [47, 45, 19, 8, NO_CARD]];
+        let ranks = rank_rows(cards.view(), |hand| aya_poker::poker_rank(hand).0);
+        assert_eq!(
+            ranks,
+            Ok(vec![
+                aya_poker::poker_rank(&royal_flush).0,
+                aya_poker::poker_rank(&pair).0,
+            ])
+        );
+
+        let cards = array![[50, 46], [47, 47]];
+        let ranks = rank_rows(cards.view(), |hand| aya_poker::poker_rank(hand).0);
+        assert_eq!(ranks, Err(1));
+    }
+
+    #[test]
+    fn ranks_row_pairs() {
+        let hole = array![[37, 23, 9, 0], [37, 23, 9, 0]];
+        let board = array![[11, 16, 36, 1, 39], [37, 16, 36, NO_CARD, NO_CARD]];
+        let rank = |hole: &Hand, board: &Hand| aya_poker::omaha_rank(hole, board).0;
+        assert_eq!(rank_row_pairs(hole.view(), board.view(), rank), Err(1));
+
+        let expected = aya_poker::omaha_rank(
+            &"Jd 7s 4d 2c".parse().unwrap(),
+            &"4s 6c Jc 2d Js".parse().unwrap(),
+        );
+        let ranks = rank_row_pairs(hole.slice(numpy::ndarray::s![..1, ..]), board.view(), rank);
+        assert_eq!(ranks, Ok(vec![expected.0]));
+    }
+}
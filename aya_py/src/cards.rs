@@ -0,0 +1,223 @@
+//! Python classes for cards, hands and decks.
+
+use aya_poker::{
+    base::{Card, Hand, CARDS},
+    deck::Deck,
+};
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyIterator, PyList, PyString},
+};
+
+/// A single playing card, e.g. `Card("Ah")`.
+#[pyclass(module = "aya_poker", name = "Card", frozen, eq, hash)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PyCard(pub(crate) Card);
+
+#[pymethods]
+impl PyCard {
+    #[new]
+    fn new(text: &str) -> PyResult<PyCard> {
+        text.parse()
+            .map(PyCard)
+            .map_err(|_| PyValueError::new_err(format!("invalid card '{}'", text)))
+    }
+
+    /// Returns the card with the given index `4 * rank + suit`.
+    #[staticmethod]
+    fn from_index(index: usize) -> PyResult<PyCard> {
+        CARDS
+            .get(index)
+            .map(|&card| PyCard(card))
+            .ok_or_else(|| PyValueError::new_err(format!("invalid card index {}", index)))
+    }
+
+    /// The rank of the card, from 0 for a two to 12 for an ace.
+    #[getter]
+    fn rank(&self) -> u8 {
+        self.0.rank() as u8
+    }
+
+    /// The suit of the card, from 0 to 3 for clubs, diamonds, hearts and
+    /// spades.
+    #[getter]
+    fn suit(&self) -> u8 {
+        self.0.suit() as u8
+    }
+
+    /// The index `4 * rank + suit` of the card, as used by the batch
+    /// evaluation functions.
+    #[getter]
+    fn index(&self) -> usize {
+        self.0.idx()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Card('{:?}')", self.0)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// An unordered hand of up to 7 distinct cards, e.g. `Hand("Ah Kd")`.
+#[pyclass(module = "aya_poker", name = "Hand", eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct PyHand(pub(crate) Hand);
+
+#[pymethods]
+impl PyHand {
+    #[new]
+    #[pyo3(signature = (cards = None))]
+    fn new(cards: Option<&Bound<'_, PyAny>>) -> PyResult<PyHand> {
+        match cards {
+            Some(cards) => extract_hand(cards).map(PyHand),
+            None => Ok(PyHand(Hand::new())),
+        }
+    }
+
+    /// Adds a card to the hand.
+    fn insert(&mut self, card: PyCard) -> PyResult<()> {
+        if self.0.contains(&card.0) {
+            return Err(PyValueError::new_err(format!(
+                "hand already contains {:?}",
+                card.0
+            )));
+        }
+        if self.0.len() == 7 {
+            return Err(PyValueError::new_err("hand cannot hold more than 7 cards"));
+        }
+        self.0.insert_unchecked(&card.0);
+        Ok(())
+    }
+
+    /// Returns `True` if the hands do not share a card.
+    fn is_disjoint(&self, other: &PyHand) -> bool {
+        self.0.is_disjoint(&other.0)
+    }
+
+    /// Returns the indices of the cards, e.g. to build the rows of an array
+    /// for batch evaluation.
+    fn indices(&self) -> Vec<usize> {
+        self.0.iter().map(|card| card.idx()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __contains__(&self, card: PyCard) -> bool {
+        self.0.contains(&card.0)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let cards = self.0.iter().map(|&card| PyCard(card));
+        PyList::new(py, cards)?.try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Hand('{}')", self.__str__())
+    }
+
+    fn __str__(&self) -> String {
+        let cards: Vec<_> = self.0.iter().map(|card| format!("{:?}", card)).collect();
+        cards.join(" ")
+    }
+}
+
+/// A deck of cards which can be dealt in a random order.
+///
+/// By default the deck holds all 52 cards and is shuffled by a random seed.
+#[pyclass(module = "aya_poker", name = "Deck")]
+pub struct PyDeck(Deck);
+
+#[pymethods]
+impl PyDeck {
+    #[new]
+    #[pyo3(signature = (cards = None, seed = None))]
+    fn new(cards: Option<&Bound<'_, PyAny>>, seed: Option<u64>) -> PyResult<PyDeck> {
+        let cards = match cards {
+            Some(cards) => extract_cards(cards)?,
+            None => CARDS.to_vec(),
+        };
+        for (i, card) in cards.iter().enumerate() {
+            if cards[..i].contains(card) {
+                return Err(PyValueError::new_err(format!(
+                    "deck already contains {:?}",
+                    card
+                )));
+            }
+        }
+        Ok(PyDeck(new_deck(&cards, seed)))
+    }
+
+    /// Returns a short deck of the 36 cards from six to ace.
+    #[staticmethod]
+    #[pyo3(signature = (seed = None))]
+    fn short(seed: Option<u64>) -> PyDeck {
+        PyDeck(new_deck(&CARDS[16..], seed))
+    }
+
+    /// Deals the given number of cards from the deck.
+    fn deal(&mut self, count: usize) -> PyResult<Vec<PyCard>> {
+        if count > self.0.len() {
+            return Err(PyValueError::new_err(format!(
+                "cannot deal {} cards from a deck of {}",
+                count,
+                self.0.len()
+            )));
+        }
+        let cards = self.0.deal(count).unwrap_or_default();
+        Ok(cards.iter().map(|&card| PyCard(card)).collect())
+    }
+
+    /// Returns all the dealt cards to the deck.
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn new_deck(cards: &[Card], seed: Option<u64>) -> Deck {
+    match seed {
+        Some(seed) => Deck::with_seed(cards, seed),
+        None => Deck::new(cards),
+    }
+}
+
+/// Extracts cards given either as a string of space-separated cards, e.g.
+/// `"Ah Kd"`, or as an iterable of `Card`s.
+fn extract_cards(cards: &Bound<'_, PyAny>) -> PyResult<Vec<Card>> {
+    if let Ok(text) = cards.cast::<PyString>() {
+        let text = text.to_str()?;
+        return text
+            .split_whitespace()
+            .map(|card| {
+                card.parse()
+                    .map_err(|_| PyValueError::new_err(format!("invalid card '{}'", card)))
+            })
+            .collect();
+    }
+    cards
+        .try_iter()?
+        .map(|card| Ok(card?.extract::<PyCard>()?.0))
+        .collect()
+}
+
+/// Extracts a hand given either as a `Hand`, a string of space-separated
+/// cards, e.g. `"Ah Kd"`, or an iterable of `Card`s.
+pub(crate) fn extract_hand(cards: &Bound<'_, PyAny>) -> PyResult<Hand> {
+    if let Ok(hand) = cards.extract::<PyHand>() {
+        return Ok(hand.0);
+    }
+    let mut hand = PyHand(Hand::new());
+    for card in extract_cards(cards)? {
+        hand.insert(PyCard(card))?;
+    }
+    Ok(hand.0)
+}
@@ -0,0 +1,44 @@
+//! Python bindings for the AyaPoker hand evaluators.
+//!
+//! This crate builds the `aya_poker` Python extension module with
+//! [PyO3](https://pyo3.rs) and [maturin](https://www.maturin.rs). It wraps
+//! cards, hands and decks, every hand-ranking function of the `aya_poker`
+//! crate and their rank categories, along with vectorized versions of the
+//! evaluators which rank NumPy arrays of hands without any per-hand Python
+//! overhead.
+//!
+//! ```python
+//! import numpy as np
+//! import aya_poker as aya
+//!
+//! rank = aya.poker_rank("Ks Kd Ac 6s 4c Jc Th")
+//! print(aya.poker_rank_category(rank))  # Pair
+//!
+//! deck = aya.Deck(seed=42)
+//! rows = []
+//! for _ in range(1_000_000):
+//!     deck.reset()
+//!     rows.append([card.index for card in deck.deal(7)])
+//! ranks = aya.poker_rank_batch(np.array(rows, dtype=np.uint8))
+//! ```
+
+mod batch;
+mod cards;
+mod rank;
+
+use pyo3::prelude::*;
+
+pub use batch::NO_CARD;
+pub use cards::{PyCard, PyDeck, PyHand};
+pub use rank::{BadugiRankCategory, PokerRankCategory};
+
+#[pymodule]
+#[pyo3(name = "aya_poker")]
+fn aya_poker_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyCard>()?;
+    module.add_class::<PyHand>()?;
+    module.add_class::<PyDeck>()?;
+    rank::register(module)?;
+    batch::register(module)?;
+    Ok(())
+}
@@ -0,0 +1,367 @@
+//! Python functions ranking single hands, and the hand-ranking categories.
+//!
+//! Hands can be given as a `Hand`, a string of space-separated cards or an
+//! iterable of `Card`s. Ranks are returned as integers, which are only
+//! comparable with ranks returned by the same function, with greater values
+//! being better.
+
+use aya_poker::{
+    base::{Hand, CARDS},
+    AceFiveHandRank, BaduciHandRank, BadugiHandRank, DeuceSevenHandRank, JokerHand, PokerHandRank,
+    ShortDeckHandRank,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::cards::extract_hand;
+
+/// A poker hand-ranking category, i.e. a straight, a flush, etc.
+#[pyclass(module = "aya_poker", eq, eq_int, frozen, hash)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum PokerRankCategory {
+    Ineligible,
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+    FiveOfAKind,
+}
+
+impl From<aya_poker::PokerRankCategory> for PokerRankCategory {
+    fn from(category: aya_poker::PokerRankCategory) -> PokerRankCategory {
+        use aya_poker::PokerRankCategory as Category;
+        match category {
+            Category::Ineligible => PokerRankCategory::Ineligible,
+            Category::HighCard => PokerRankCategory::HighCard,
+            Category::Pair => PokerRankCategory::Pair,
+            Category::TwoPair => PokerRankCategory::TwoPair,
+            Category::ThreeOfAKind => PokerRankCategory::ThreeOfAKind,
+            Category::Straight => PokerRankCategory::Straight,
+            Category::Flush => PokerRankCategory::Flush,
+            Category::FullHouse => PokerRankCategory::FullHouse,
+            Category::FourOfAKind => PokerRankCategory::FourOfAKind,
+            Category::StraightFlush => PokerRankCategory::StraightFlush,
+            Category::RoyalFlush => PokerRankCategory::RoyalFlush,
+            Category::FiveOfAKind => PokerRankCategory::FiveOfAKind,
+        }
+    }
+}
+
+#[pymethods]
+impl PokerRankCategory {
+    fn __str__(&self) -> &'static str {
+        match self {
+            PokerRankCategory::Ineligible => "Ineligible",
+            PokerRankCategory::HighCard => "High Card",
+            PokerRankCategory::Pair => "Pair",
+            PokerRankCategory::TwoPair => "Two Pair",
+            PokerRankCategory::ThreeOfAKind => "Three of a Kind",
+            PokerRankCategory::Straight => "Straight",
+            PokerRankCategory::Flush => "Flush",
+            PokerRankCategory::FullHouse => "Full House",
+            PokerRankCategory::FourOfAKind => "Four of a Kind",
+            PokerRankCategory::StraightFlush => "Straight Flush",
+            PokerRankCategory::RoyalFlush => "Royal Flush",
+            PokerRankCategory::FiveOfAKind => "Five of a Kind",
+        }
+    }
+}
+
+/// A Badugi/Baduci hand-ranking category corresponding to the size of the
+/// made hand.
+#[pyclass(module = "aya_poker", eq, eq_int, frozen, hash)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum BadugiRankCategory {
+    OneCard,
+    TwoCards,
+    ThreeCards,
+    FourCards,
+}
+
+impl From<aya_poker::BadugiRankCategory> for BadugiRankCategory {
+    fn from(category: aya_poker::BadugiRankCategory) -> BadugiRankCategory {
+        use aya_poker::BadugiRankCategory as Category;
+        match category {
+            Category::OneCard => BadugiRankCategory::OneCard,
+            Category::TwoCards => BadugiRankCategory::TwoCards,
+            Category::ThreeCards => BadugiRankCategory::ThreeCards,
+            Category::FourCards => BadugiRankCategory::FourCards,
+        }
+    }
+}
+
+#[pymethods]
+impl BadugiRankCategory {
+    fn __str__(&self) -> &'static str {
+        match self {
+            BadugiRankCategory::OneCard => "One Card",
+            BadugiRankCategory::TwoCards => "Two Cards",
+            BadugiRankCategory::ThreeCards => "Three Cards",
+            BadugiRankCategory::FourCards => "Four Cards",
+        }
+    }
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards.
+#[pyfunction]
+fn poker_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::poker_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the rank of the best 5-card short-deck (6+) poker hand that can be
+/// made from the cards.
+#[pyfunction]
+fn short_deck_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::short_deck_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the rank of the best 5-card ace-five lowball hand that can be made
+/// from the cards.
+#[pyfunction]
+fn ace_five_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::ace_five_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the rank of the best 5-card deuce-seven lowball hand that can be
+/// made from the cards.
+#[pyfunction]
+fn deuce_seven_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::deuce_seven_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the rank of the best Badugi hand that can be made from the cards.
+#[pyfunction]
+fn badugi_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::badugi_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the rank of the best Baduci hand that can be made from the cards.
+#[pyfunction]
+fn baduci_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::baduci_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards with deuces wild.
+#[pyfunction]
+fn deuces_wild_rank(hand: &Bound<'_, PyAny>) -> PyResult<u16> {
+    Ok(aya_poker::deuces_wild_rank(&extract_hand(hand)?).0)
+}
+
+/// Returns the Baduci and deuce-seven lowball ranks of a Badeucy hand.
+#[pyfunction]
+fn badeucy_rank(hand: &Bound<'_, PyAny>) -> PyResult<(u16, u16)> {
+    let rank = aya_poker::badeucy_rank(&extract_hand(hand)?);
+    Ok((rank.baduci.0, rank.deuce_seven.0))
+}
+
+/// Returns the Badugi and ace-five lowball ranks of a Badacey hand.
+#[pyfunction]
+fn badacey_rank(hand: &Bound<'_, PyAny>) -> PyResult<(u16, u16)> {
+    let rank = aya_poker::badacey_rank(&extract_hand(hand)?);
+    Ok((rank.badugi.0, rank.ace_five.0))
+}
+
+/// Extracts hole and board cards which do not share a card and of which
+/// there are at least `min_hole` and `min_board`.
+fn extract_hole_and_board(
+    hole: &Bound<'_, PyAny>,
+    board: &Bound<'_, PyAny>,
+    min_hole: usize,
+    min_board: usize,
+) -> PyResult<(Hand, Hand)> {
+    let (hole, board) = (extract_hand(hole)?, extract_hand(board)?);
+    if !hole.is_disjoint(&board) {
+        return Err(PyValueError::new_err("hole and board cannot share a card"));
+    }
+    if hole.len() < min_hole || board.len() < min_board {
+        return Err(PyValueError::new_err(format!(
+            "need at least {} hole and {} board cards",
+            min_hole, min_board
+        )));
+    }
+    Ok((hole, board))
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made with two
+/// hole cards and three board cards.
+#[pyfunction]
+fn omaha_rank(hole: &Bound<'_, PyAny>, board: &Bound<'_, PyAny>) -> PyResult<u16> {
+    let (hole, board) = extract_hole_and_board(hole, board, 2, 3)?;
+    Ok(aya_poker::omaha_rank(&hole, &board).0)
+}
+
+/// Returns the rank of the best 5-card ace-five lowball hand that can be made
+/// with two hole cards and three board cards.
+#[pyfunction]
+fn omaha_lo_rank(hole: &Bound<'_, PyAny>, board: &Bound<'_, PyAny>) -> PyResult<u16> {
+    let (hole, board) = extract_hole_and_board(hole, board, 2, 3)?;
+    Ok(aya_poker::omaha_lo_rank(&hole, &board).0)
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made with
+/// exactly `hole_count` hole cards and `5 - hole_count` board cards.
+#[pyfunction]
+fn exact_hole_rank(
+    hole: &Bound<'_, PyAny>,
+    board: &Bound<'_, PyAny>,
+    hole_count: usize,
+) -> PyResult<u16> {
+    if hole_count > 5 {
+        return Err(PyValueError::new_err("cannot use more than 5 hole cards"));
+    }
+    let (hole, board) = extract_hole_and_board(hole, board, hole_count, 5 - hole_count)?;
+    Ok(aya_poker::exact_hole_rank(&hole, &board, hole_count).0)
+}
+
+/// Returns the rank of the best 5-card Manila poker hand that can be made
+/// with two hole cards and three board cards.
+#[pyfunction]
+fn manila_rank(hole: &Bound<'_, PyAny>, board: &Bound<'_, PyAny>) -> PyResult<u16> {
+    let (hole, board) = extract_hole_and_board(hole, board, 2, 3)?;
+    if hole.iter().chain(board.iter()).any(|card| card.idx() < 20) {
+        return Err(PyValueError::new_err(
+            "Manila is played with sevens through aces",
+        ));
+    }
+    Ok(aya_poker::manila_rank(&hole, &board).map_or(0, |rank| rank.0))
+}
+
+fn extract_joker_hand(cards: &Bound<'_, PyAny>, jokers: usize) -> PyResult<JokerHand> {
+    let cards = extract_hand(cards)?;
+    if cards.len() + jokers > 7 {
+        return Err(PyValueError::new_err("hand cannot hold more than 7 cards"));
+    }
+    Ok(JokerHand::new(cards, jokers as u8))
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards and the given number of fully wild jokers.
+#[pyfunction]
+#[pyo3(signature = (cards, jokers = 1))]
+fn joker_rank(cards: &Bound<'_, PyAny>, jokers: usize) -> PyResult<u16> {
+    Ok(aya_poker::joker_rank(&extract_joker_hand(cards, jokers)?).0)
+}
+
+/// Returns the rank of the best 5-card poker hand that can be made from the
+/// cards and the given number of jokers, which can only complete straights
+/// and flushes or otherwise play as aces.
+#[pyfunction]
+#[pyo3(signature = (cards, jokers = 1))]
+fn bug_rank(cards: &Bound<'_, PyAny>, jokers: usize) -> PyResult<u16> {
+    Ok(aya_poker::bug_rank(&extract_joker_hand(cards, jokers)?).0)
+}
+
+/// Converts an ace-five lowball rank into an eight-or-better rank, i.e.
+/// returns 0 for hands which do not qualify.
+#[pyfunction]
+fn ace_five_to_lo_8_rank(rank: u16) -> u16 {
+    AceFiveHandRank(rank).to_lo_8_rank().0
+}
+
+/// Converts a deuce-seven lowball rank into an eight-or-better rank, i.e.
+/// returns 0 for hands which do not qualify.
+#[pyfunction]
+fn deuce_seven_to_lo_8_rank(rank: u16) -> u16 {
+    DeuceSevenHandRank(rank).to_lo_8_rank().0
+}
+
+/// Builds a hand from indices into [`CARDS`].
+fn hand_of(indices: &[usize]) -> Hand {
+    indices.iter().map(|&idx| CARDS[idx]).collect()
+}
+
+/// Returns an error unless the rank is at most the rank of the best hand.
+fn check_rank(rank: u16, best: u16) -> PyResult<()> {
+    match rank <= best {
+        true => Ok(()),
+        false => Err(PyValueError::new_err(format!("invalid rank {}", rank))),
+    }
+}
+
+/// Returns the category of a rank returned by `poker_rank` or any of the
+/// other functions ranking regular poker hands.
+#[pyfunction]
+fn poker_rank_category(rank: u16) -> PyResult<PokerRankCategory> {
+    // The best possible hand is five aces, which needs wild cards.
+    let best = aya_poker::deuces_wild_rank(&hand_of(&[48, 49, 50, 51, 0]));
+    check_rank(rank, best.0)?;
+    Ok(PokerHandRank(rank).rank_category().into())
+}
+
+/// Returns the category of a rank returned by `short_deck_rank` or
+/// `manila_rank`.
+#[pyfunction]
+fn short_deck_rank_category(rank: u16) -> PyResult<PokerRankCategory> {
+    let best = aya_poker::short_deck_rank(&hand_of(&[51, 47, 43, 39, 35]));
+    check_rank(rank, best.0)?;
+    Ok(ShortDeckHandRank(rank).rank_category().into())
+}
+
+/// Returns the category of a rank returned by `ace_five_rank` or
+/// `omaha_lo_rank`.
+#[pyfunction]
+fn ace_five_rank_category(rank: u16) -> PyResult<PokerRankCategory> {
+    let best = aya_poker::ace_five_rank(&hand_of(&[48, 1, 6, 11, 12]));
+    check_rank(rank, best.0)?;
+    Ok(AceFiveHandRank(rank).rank_category().into())
+}
+
+/// Returns the category of a rank returned by `deuce_seven_rank`.
+#[pyfunction]
+fn deuce_seven_rank_category(rank: u16) -> PyResult<PokerRankCategory> {
+    let best = aya_poker::deuce_seven_rank(&hand_of(&[20, 13, 10, 7, 0]));
+    check_rank(rank, best.0)?;
+    Ok(DeuceSevenHandRank(rank).rank_category().into())
+}
+
+/// Returns the category of a rank returned by `badugi_rank`.
+#[pyfunction]
+fn badugi_rank_category(rank: u16) -> PyResult<BadugiRankCategory> {
+    let best = aya_poker::badugi_rank(&hand_of(&[48, 1, 6, 11]));
+    check_rank(rank, best.0)?;
+    Ok(BadugiHandRank(rank).rank_category().into())
+}
+
+/// Returns the category of a rank returned by `baduci_rank`.
+#[pyfunction]
+fn baduci_rank_category(rank: u16) -> PyResult<BadugiRankCategory> {
+    let best = aya_poker::baduci_rank(&hand_of(&[0, 5, 10, 15]));
+    check_rank(rank, best.0)?;
+    Ok(BaduciHandRank(rank).rank_category().into())
+}
+
+pub(crate) fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PokerRankCategory>()?;
+    module.add_class::<BadugiRankCategory>()?;
+    module.add_function(wrap_pyfunction!(poker_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(short_deck_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(ace_five_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(deuce_seven_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(badugi_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(baduci_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(deuces_wild_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(badeucy_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(badacey_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(omaha_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(omaha_lo_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(exact_hole_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(manila_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(joker_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(bug_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(ace_five_to_lo_8_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(deuce_seven_to_lo_8_rank, module)?)?;
+    module.add_function(wrap_pyfunction!(poker_rank_category, module)?)?;
+    module.add_function(wrap_pyfunction!(short_deck_rank_category, module)?)?;
+    module.add_function(wrap_pyfunction!(ace_five_rank_category, module)?)?;
+    module.add_function(wrap_pyfunction!(deuce_seven_rank_category, module)?)?;
+    module.add_function(wrap_pyfunction!(badugi_rank_category, module)?)?;
+    module.add_function(wrap_pyfunction!(baduci_rank_category, module)?)?;
+    Ok(())
+}